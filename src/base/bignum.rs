@@ -0,0 +1,364 @@
+//! # Bignum
+//!
+//! `bignum` is the module providing `BigUint`, an arbitrary-precision unsigned integer that can be
+//! used as the amount type of chains whose amounts do not fit in a 64-bit integer.
+//!
+//! A `BigUint` is stored as little-endian 32-bit limbs, without trailing zero limbs. Its checked
+//! additions and multiplications never overflow, while subtractions overflow below zero.
+
+use std::fmt;
+use std::cmp::Ordering;
+use std::ops::{Add, Sub, Mul};
+
+use base::Result;
+use base::Checkable;
+use base::Sizable;
+use base::Serializable;
+use base::Datable;
+use base::Numerical;
+
+/// Type representing an arbitrary-precision unsigned integer.
+#[derive(Clone, Eq, PartialEq, Debug, Default, Hash, Serialize, Deserialize)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    /// Creates a new zero `BigUint`.
+    pub fn new() -> Self {
+        BigUint::default()
+    }
+
+    /// Creates a `BigUint` from little-endian 32-bit limbs.
+    pub fn from_limbs(limbs: &[u32]) -> Self {
+        let mut n = BigUint {
+            limbs: limbs.to_owned(),
+        };
+
+        n.normalize();
+
+        n
+    }
+
+    /// Returns the little-endian 32-bit limbs of the `BigUint`.
+    pub fn limbs(&self) -> &[u32] {
+        &self.limbs
+    }
+
+    /// Converts the `BigUint` to a `u64`, if it fits.
+    pub fn to_u64(&self) -> Option<u64> {
+        if self.limbs.len() > 2 {
+            return None;
+        }
+
+        let mut n = 0u64;
+
+        for limb in self.limbs.iter().rev() {
+            n = (n << 32) | u64::from(*limb);
+        }
+
+        Some(n)
+    }
+
+    /// Converts the `BigUint` to a `u128`, if it fits.
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.limbs.len() > 4 {
+            return None;
+        }
+
+        let mut n = 0u128;
+
+        for limb in self.limbs.iter().rev() {
+            n = (n << 32) | u128::from(*limb);
+        }
+
+        Some(n)
+    }
+
+    /// Parses a `BigUint` from a decimal `&str`.
+    pub fn parse(s: &str) -> Result<BigUint> {
+        if s.is_empty() {
+            return Err(String::from("invalid number"));
+        }
+
+        let mut n = BigUint::new();
+
+        for c in s.chars() {
+            let digit = c.to_digit(10)
+                            .ok_or_else(|| String::from("invalid number"))?;
+
+            n = n.mul_small(10).add_small(digit);
+        }
+
+        Ok(n)
+    }
+
+    /// Removes the trailing zero limbs.
+    fn normalize(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+
+    /// Adds a `u32` to the `BigUint`.
+    fn add_small(mut self, n: u32) -> BigUint {
+        let mut carry = u64::from(n);
+
+        for limb in self.limbs.iter_mut() {
+            if carry == 0 {
+                break;
+            }
+
+            let sum = u64::from(*limb) + carry;
+            *limb = sum as u32;
+            carry = sum >> 32;
+        }
+
+        if carry > 0 {
+            self.limbs.push(carry as u32);
+        }
+
+        self
+    }
+
+    /// Multiplies the `BigUint` by a `u32`.
+    fn mul_small(mut self, n: u32) -> BigUint {
+        let mut carry = 0u64;
+
+        for limb in self.limbs.iter_mut() {
+            let prod = u64::from(*limb) * u64::from(n) + carry;
+            *limb = prod as u32;
+            carry = prod >> 32;
+        }
+
+        if carry > 0 {
+            self.limbs.push(carry as u32);
+        }
+
+        self.normalize();
+
+        self
+    }
+
+    /// Divides the `BigUint` by a nonzero `u32`, returning the quotient and the remainder.
+    fn div_rem_small(&self, n: u32) -> (BigUint, u32) {
+        let mut limbs = vec![0u32; self.limbs.len()];
+        let mut rem = 0u64;
+
+        for (idx, limb) in self.limbs.iter().enumerate().rev() {
+            let cur = (rem << 32) | u64::from(*limb);
+            limbs[idx] = (cur / u64::from(n)) as u32;
+            rem = cur % u64::from(n);
+        }
+
+        (BigUint::from_limbs(&limbs), rem as u32)
+    }
+
+    /// Adds two `BigUint`s.
+    fn add_big(&self, other: &BigUint) -> BigUint {
+        let len = self.limbs.len().max(other.limbs.len());
+        let mut limbs = Vec::with_capacity(len + 1);
+        let mut carry = 0u64;
+
+        for idx in 0..len {
+            let a = u64::from(*self.limbs.get(idx).unwrap_or(&0));
+            let b = u64::from(*other.limbs.get(idx).unwrap_or(&0));
+
+            let sum = a + b + carry;
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+
+        if carry > 0 {
+            limbs.push(carry as u32);
+        }
+
+        BigUint::from_limbs(&limbs)
+    }
+
+    /// Subtracts two `BigUint`s, returning `None` if the result would be negative.
+    fn sub_big(&self, other: &BigUint) -> Option<BigUint> {
+        if self < other {
+            return None;
+        }
+
+        let mut limbs = Vec::with_capacity(self.limbs.len());
+        let mut borrow = 0i64;
+
+        for idx in 0..self.limbs.len() {
+            let a = i64::from(self.limbs[idx]);
+            let b = i64::from(*other.limbs.get(idx).unwrap_or(&0));
+
+            let mut diff = a - b - borrow;
+
+            if diff < 0 {
+                diff += 1 << 32;
+                borrow = 1;
+            } else {
+                borrow = 0;
+            }
+
+            limbs.push(diff as u32);
+        }
+
+        Some(BigUint::from_limbs(&limbs))
+    }
+
+    /// Multiplies two `BigUint`s.
+    fn mul_big(&self, other: &BigUint) -> BigUint {
+        if self.limbs.is_empty() || other.limbs.is_empty() {
+            return BigUint::new();
+        }
+
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+
+        for (i, a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+
+            for (j, b) in other.limbs.iter().enumerate() {
+                let prod = u64::from(*a) * u64::from(*b) + u64::from(limbs[i + j]) + carry;
+                limbs[i + j] = prod as u32;
+                carry = prod >> 32;
+            }
+
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+
+        BigUint::from_limbs(&limbs)
+    }
+}
+
+impl From<u32> for BigUint {
+    fn from(n: u32) -> BigUint {
+        BigUint::from_limbs(&[n])
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(n: u64) -> BigUint {
+        BigUint::from_limbs(&[n as u32, (n >> 32) as u32])
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(n: u128) -> BigUint {
+        BigUint::from_limbs(&[n as u32, (n >> 32) as u32, (n >> 64) as u32, (n >> 96) as u32])
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &BigUint) -> Ordering {
+        self.limbs.len().cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: BigUint) -> BigUint {
+        self.add_big(&other)
+    }
+}
+
+impl Sub for BigUint {
+    type Output = BigUint;
+
+    fn sub(self, other: BigUint) -> BigUint {
+        self.sub_big(&other)
+            .expect("attempt to subtract with overflow")
+    }
+}
+
+impl Mul for BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: BigUint) -> BigUint {
+        self.mul_big(&other)
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.limbs.is_empty() {
+            return write!(f, "0");
+        }
+
+        let mut chunks = Vec::new();
+        let mut n = self.clone();
+
+        while !n.limbs.is_empty() {
+            let (quot, rem) = n.div_rem_small(1_000_000_000);
+            chunks.push(rem);
+            n = quot;
+        }
+
+        let mut chunks = chunks.iter().rev();
+
+        if let Some(chunk) = chunks.next() {
+            write!(f, "{}", chunk)?;
+        }
+
+        for chunk in chunks {
+            write!(f, "{:09}", chunk)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Sizable for BigUint {
+    fn size(&self) -> u64 {
+        self.limbs.size()
+    }
+}
+
+impl Checkable for BigUint {
+    fn check(&self) -> Result<()> {
+        if self.limbs.last() == Some(&0) {
+            return Err(String::from("invalid limbs"));
+        }
+
+        Ok(())
+    }
+}
+
+impl Serializable for BigUint {}
+
+impl Datable for BigUint {}
+
+impl Numerical for BigUint {
+    fn zero() -> BigUint {
+        BigUint::new()
+    }
+
+    fn checked_add(&self, other: &BigUint) -> Option<BigUint> {
+        Some(self.add_big(other))
+    }
+
+    fn checked_sub(&self, other: &BigUint) -> Option<BigUint> {
+        self.sub_big(other)
+    }
+
+    fn checked_mul(&self, other: &BigUint) -> Option<BigUint> {
+        Some(self.mul_big(other))
+    }
+
+    fn saturating_add(&self, other: &BigUint) -> BigUint {
+        self.add_big(other)
+    }
+
+    fn saturating_sub(&self, other: &BigUint) -> BigUint {
+        self.sub_big(other).unwrap_or_default()
+    }
+
+    fn saturating_mul(&self, other: &BigUint) -> BigUint {
+        self.mul_big(other)
+    }
+}
\ No newline at end of file
@@ -33,6 +33,10 @@ impl Checkable for u64 {}
 
 impl Checkable for i64 {}
 
+impl Checkable for u128 {}
+
+impl Checkable for i128 {}
+
 impl Checkable for String {}
 
 impl<T> Checkable for Vec<T>
@@ -30,6 +30,10 @@ impl Datable for u64 {}
 
 impl Datable for i64 {}
 
+impl Datable for u128 {}
+
+impl Datable for i128 {}
+
 impl Datable for String {}
 
 impl<T> Datable for Vec<T>
@@ -22,6 +22,9 @@ pub mod size;
 /// Trait to be implemented by types that can be added, subtracted, multiplied.
 pub mod numerical;
 
+/// Type used to represent arbitrary-precision unsigned integers.
+pub mod bignum;
+
 /// Trait to be implemented by types that can be evaluated (computed).
 pub mod eval;
 
@@ -36,6 +39,7 @@ pub use self::check::Checkable;
 pub use self::data::Datable;
 pub use self::serialize::Serializable;
 pub use self::numerical::Numerical;
+pub use self::bignum::BigUint;
 pub use self::size::{Sizable, VariableSize, ConstantSize};
 pub use self::eval::{Eval, EvalMut};
 pub use self::stage::Stage;
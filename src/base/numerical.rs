@@ -2,26 +2,122 @@
 //!
 //! `numerical` is the module providing the traits implemented by types that can be added,
 //! subtracted or multiplied.
+//!
+//! Besides the arithmetic operators, `Numerical` types provide checked operations, returning `None`
+//! on overflow, and saturating operations, clamping the result at the bounds of the type, so that
+//! amounts can be summed without silently wrapping in release builds or panicking in debug builds.
 
 use std::ops::{Add, Sub, Mul};
 
 use base::data::Datable;
 
 /// Trait implemented by types that can be added, subtracted or multiplied.
-pub trait Numerical : Sized + Ord + Datable + Add + Sub + Mul {}
+pub trait Numerical : Sized + Ord + Datable + Add + Sub + Mul {
+    /// Returns the zero value of the type.
+    fn zero() -> Self;
+
+    /// Returns if the value is zero.
+    fn is_zero(&self) -> bool {
+        self == &Self::zero()
+    }
+
+    /// Adds two values, returning `None` on overflow.
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+
+    /// Subtracts two values, returning `None` on overflow.
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+
+    /// Multiplies two values, returning `None` on overflow.
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+
+    /// Adds two values, saturating at the bounds of the type.
+    fn saturating_add(&self, other: &Self) -> Self;
+
+    /// Subtracts two values, saturating at the bounds of the type.
+    fn saturating_sub(&self, other: &Self) -> Self;
+
+    /// Multiplies two values, saturating at the bounds of the type.
+    fn saturating_mul(&self, other: &Self) -> Self;
+
+    /// Sums a sequence of values, returning `None` on overflow.
+    fn checked_sum<'a, I>(values: I) -> Option<Self>
+        where   I: IntoIterator<Item=&'a Self>,
+                Self: 'a
+    {
+        let mut sum = Self::zero();
+
+        for value in values {
+            sum = sum.checked_add(value)?;
+        }
+
+        Some(sum)
+    }
+
+    /// Sums a sequence of values, saturating at the bounds of the type.
+    fn saturating_sum<'a, I>(values: I) -> Self
+        where   I: IntoIterator<Item=&'a Self>,
+                Self: 'a
+    {
+        let mut sum = Self::zero();
+
+        for value in values {
+            sum = sum.saturating_add(value);
+        }
+
+        sum
+    }
+}
+
+macro_rules! impl_numerical {
+    ($t:ty) => {
+        impl Numerical for $t {
+            fn zero() -> $t {
+                0
+            }
+
+            fn checked_add(&self, other: &$t) -> Option<$t> {
+                <$t>::checked_add(*self, *other)
+            }
+
+            fn checked_sub(&self, other: &$t) -> Option<$t> {
+                <$t>::checked_sub(*self, *other)
+            }
+
+            fn checked_mul(&self, other: &$t) -> Option<$t> {
+                <$t>::checked_mul(*self, *other)
+            }
+
+            fn saturating_add(&self, other: &$t) -> $t {
+                <$t>::saturating_add(*self, *other)
+            }
+
+            fn saturating_sub(&self, other: &$t) -> $t {
+                <$t>::saturating_sub(*self, *other)
+            }
+
+            fn saturating_mul(&self, other: &$t) -> $t {
+                <$t>::saturating_mul(*self, *other)
+            }
+        }
+    }
+}
+
+impl_numerical!(u8);
+
+impl_numerical!(i8);
 
-impl Numerical for u8 {}
+impl_numerical!(u16);
 
-impl Numerical for i8 {}
+impl_numerical!(i16);
 
-impl Numerical for u16 {}
+impl_numerical!(u32);
 
-impl Numerical for i16 {}
+impl_numerical!(i32);
 
-impl Numerical for u32 {}
+impl_numerical!(u64);
 
-impl Numerical for i32 {}
+impl_numerical!(i64);
 
-impl Numerical for u64 {}
+impl_numerical!(u128);
 
-impl Numerical for i64 {}
\ No newline at end of file
+impl_numerical!(i128);
\ No newline at end of file
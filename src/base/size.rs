@@ -72,6 +72,18 @@ impl Sizable for i64 {
     }
 }
 
+impl Sizable for u128 {
+    fn size(&self) -> u64 {
+        16
+    }
+}
+
+impl Sizable for i128 {
+    fn size(&self) -> u64 {
+        16
+    }
+}
+
 impl Sizable for String {
     fn size(&self) -> u64 {
         (self.len() * 2) as u64
@@ -0,0 +1,299 @@
+//! # Merkle
+//!
+//! `merkle` is the module providing the Merkle trees used to commit to an ordered list of digests
+//! (e.g. the ids of the transactions of a `Block`) and to prove the inclusion of a single digest
+//! without the rest of the list.
+//!
+//! Leaves and inner nodes are hashed with distinct prefixes, so that a leaf cannot be passed off as
+//! an inner node. A node without a sibling is promoted unchanged to the next level, instead of being
+//! paired with itself, so that distinct lists never have the same root. The root of an empty tree
+//! is the digest of the empty message.
+//!
+//! A `MerkleTree` can be deserialized or built field by field, so its root and proofs are returned
+//! only if the shape of its levels matches its number of leaves.
+
+use base::Result;
+use base::Checkable;
+use base::Datable;
+use base::Serializable;
+use base::{Sizable, ConstantSize};
+use crypto::Hash;
+
+/// Prefix of the hashed leaves.
+const LEAF_PREFIX: u8 = 0;
+
+/// Prefix of the hashed inner nodes.
+const NODE_PREFIX: u8 = 1;
+
+/// Hashes a leaf of a Merkle tree.
+fn hash_leaf<D, H>(hasher: &mut H, leaf: &D) -> Result<D>
+    where   D: Datable + ConstantSize + Serializable,
+            H: Hash<D>
+{
+    let mut msg = vec![LEAF_PREFIX];
+    msg.extend_from_slice(&leaf.to_bytes()?);
+
+    hasher.digest(&msg)
+}
+
+/// Hashes an inner node of a Merkle tree from its children.
+fn hash_node<D, H>(hasher: &mut H, left: &D, right: &D) -> Result<D>
+    where   D: Datable + ConstantSize + Serializable,
+            H: Hash<D>
+{
+    let mut msg = vec![NODE_PREFIX];
+    msg.extend_from_slice(&left.to_bytes()?);
+    msg.extend_from_slice(&right.to_bytes()?);
+
+    hasher.digest(&msg)
+}
+
+/// Type representing a Merkle tree over an ordered list of digests.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default, Hash, Serialize, Deserialize)]
+pub struct MerkleTree<D>
+    where   D: Datable + ConstantSize
+{
+    /// Number of leaves of the tree.
+    pub leaves_len: u64,
+    /// Tree levels, from the hashed leaves to the root.
+    pub levels: Vec<Vec<D>>,
+}
+
+impl<D> MerkleTree<D>
+    where   D: Datable + ConstantSize + Serializable
+{
+    /// Builds the `MerkleTree` of a list of leaves.
+    pub fn new<H: Hash<D>>(hasher: &mut H, leaves: &[D]) -> Result<Self> {
+        let mut level = Vec::new();
+
+        for leaf in leaves.iter() {
+            leaf.check()?;
+            leaf.check_size()?;
+
+            level.push(hash_leaf(hasher, leaf)?);
+        }
+
+        let mut levels = vec![level];
+
+        while levels[levels.len() - 1].len() > 1 {
+            let mut next_level = Vec::new();
+
+            for pair in levels[levels.len() - 1].chunks(2) {
+                if pair.len() == 2 {
+                    next_level.push(hash_node(hasher, &pair[0], &pair[1])?);
+                } else {
+                    next_level.push(pair[0].clone());
+                }
+            }
+
+            levels.push(next_level);
+        }
+
+        if leaves.is_empty() {
+            levels[0].push(hasher.digest(&[])?);
+        }
+
+        let tree = MerkleTree {
+            leaves_len: leaves.len() as u64,
+            levels,
+        };
+
+        Ok(tree)
+    }
+
+    /// Returns the root of the `MerkleTree`.
+    pub fn root(&self) -> Result<D> {
+        self.check()?;
+
+        Ok(self.levels[self.levels.len() - 1][0].clone())
+    }
+
+    /// Returns the `MerkleProof` of inclusion of the leaf at index `idx`.
+    pub fn proof(&self, idx: u64) -> Result<MerkleProof<D>> {
+        self.check()?;
+
+        if idx >= self.leaves_len {
+            return Err(String::from("invalid index"));
+        }
+
+        let mut siblings = Vec::new();
+        let mut node_idx = idx as usize;
+
+        for level in self.levels[..self.levels.len() - 1].iter() {
+            let sibling_idx = node_idx ^ 1;
+
+            if sibling_idx < level.len() {
+                siblings.push(level[sibling_idx].clone());
+            }
+
+            node_idx /= 2;
+        }
+
+        let proof = MerkleProof {
+            leaf_idx: idx,
+            leaves_len: self.leaves_len,
+            siblings,
+        };
+
+        Ok(proof)
+    }
+}
+
+impl<D> Sizable for MerkleTree<D>
+    where   D: Datable + ConstantSize
+{
+    fn size(&self) -> u64 {
+        self.leaves_len.size() +
+            self.levels.size()
+    }
+}
+
+impl<D> Checkable for MerkleTree<D>
+    where   D: Datable + ConstantSize
+{
+    fn check(&self) -> Result<()> {
+        let leaves_level_len = if self.leaves_len == 0 { 1 } else { self.leaves_len };
+
+        match self.levels.first() {
+            Some(level) if level.len() as u64 == leaves_level_len => {},
+            _ => return Err(String::from("invalid length")),
+        }
+
+        for pair in self.levels.windows(2) {
+            let len = pair[0].len();
+
+            if len < 2 || pair[1].len() != len / 2 + len % 2 {
+                return Err(String::from("invalid length"));
+            }
+        }
+
+        match self.levels.last() {
+            Some(level) if level.len() == 1 => {},
+            _ => return Err(String::from("invalid length")),
+        }
+
+        for level in self.levels.iter() {
+            for node in level.iter() {
+                node.check()?;
+                node.check_size()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<D> Serializable for MerkleTree<D>
+    where   D: Datable + ConstantSize + Serializable
+{}
+
+impl<D> Datable for MerkleTree<D>
+    where   D: Datable + ConstantSize
+{}
+
+/// Type representing the proof of inclusion of a leaf in a `MerkleTree`.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default, Hash, Serialize, Deserialize)]
+pub struct MerkleProof<D>
+    where   D: Datable + ConstantSize
+{
+    /// Index of the leaf.
+    pub leaf_idx: u64,
+    /// Number of leaves of the tree.
+    pub leaves_len: u64,
+    /// Siblings of the nodes on the path from the leaf to the root.
+    pub siblings: Vec<D>,
+}
+
+impl<D> MerkleProof<D>
+    where   D: Datable + ConstantSize + Serializable
+{
+    /// Computes the root of the tree from the leaf and the `MerkleProof`.
+    pub fn root<H: Hash<D>>(&self, hasher: &mut H, leaf: &D) -> Result<D> {
+        self.check()?;
+        leaf.check()?;
+        leaf.check_size()?;
+
+        let mut node = hash_leaf(hasher, leaf)?;
+        let mut node_idx = self.leaf_idx;
+        let mut len = self.leaves_len;
+        let mut siblings = self.siblings.iter();
+
+        while len > 1 {
+            if node_idx % 2 == 1 {
+                let sibling = siblings.next()
+                                .ok_or_else(|| String::from("invalid length"))?;
+
+                node = hash_node(hasher, sibling, &node)?;
+            } else if node_idx + 1 < len {
+                let sibling = siblings.next()
+                                .ok_or_else(|| String::from("invalid length"))?;
+
+                node = hash_node(hasher, &node, sibling)?;
+            }
+
+            node_idx /= 2;
+            len = len / 2 + len % 2;
+        }
+
+        if siblings.next().is_some() {
+            return Err(String::from("invalid length"));
+        }
+
+        Ok(node)
+    }
+
+    /// Verifies the `MerkleProof` of a leaf against a root.
+    pub fn verify<H: Hash<D>>(&self, hasher: &mut H, leaf: &D, root: &D) -> Result<bool> {
+        root.check()?;
+        root.check_size()?;
+
+        match self.root(hasher, leaf) {
+            Ok(ref proof_root) => Ok(proof_root == root),
+            Err(_) => Ok(false),
+        }
+    }
+
+    /// Checks the `MerkleProof` of a leaf against a root.
+    pub fn check_root<H: Hash<D>>(&self, hasher: &mut H, leaf: &D, root: &D) -> Result<()> {
+        if !self.verify(hasher, leaf, root)? {
+            return Err(String::from("invalid proof"));
+        }
+
+        Ok(())
+    }
+}
+
+impl<D> Sizable for MerkleProof<D>
+    where   D: Datable + ConstantSize
+{
+    fn size(&self) -> u64 {
+        self.leaf_idx.size() +
+            self.leaves_len.size() +
+            self.siblings.size()
+    }
+}
+
+impl<D> Checkable for MerkleProof<D>
+    where   D: Datable + ConstantSize
+{
+    fn check(&self) -> Result<()> {
+        if self.leaf_idx >= self.leaves_len {
+            return Err(String::from("invalid index"));
+        }
+
+        for sibling in self.siblings.iter() {
+            sibling.check()?;
+            sibling.check_size()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<D> Serializable for MerkleProof<D>
+    where   D: Datable + ConstantSize + Serializable
+{}
+
+impl<D> Datable for MerkleProof<D>
+    where   D: Datable + ConstantSize
+{}
\ No newline at end of file
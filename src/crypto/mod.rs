@@ -17,8 +17,12 @@ pub mod authenticate;
 /// Trait implemented by types that implement cryptographic proving.
 pub mod prove;
 
+/// Types used to build Merkle trees and proofs of inclusion.
+pub mod merkle;
+
 pub use self::hash::Hash;
 pub use self::sign::Sign;
 pub use self::commit::Commit;
 pub use self::authenticate::Authenticate;
-pub use self::prove::Prove;
\ No newline at end of file
+pub use self::prove::Prove;
+pub use self::merkle::{MerkleTree, MerkleProof};
\ No newline at end of file
@@ -0,0 +1,237 @@
+//! # ACL
+//!
+//! `acl` is the module providing the types used to grant fine-grained access to the network
+//! resources: a `Capability` grants a `Method` on a `Resource`, and an `Acl` is a set of capabilities.
+//!
+//! Capabilities are parsed from strings in the form `method:resource`, where `*` stands for any
+//! method or resource (e.g. `get:block`, `eval:*`, `*:coin`), and access control lists from
+//! comma-separated capabilities (e.g. `count:block,list:block,get:block`).
+
+use std::fmt;
+
+use base::Result;
+use base::Checkable;
+use base::Sizable;
+use base::Serializable;
+use base::Datable;
+use io::network::{Method, Resource};
+
+/// Type representing the grant of a `Method` on a `Resource`. A missing method or resource stands
+/// for any method or resource.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Default, Hash, Serialize, Deserialize)]
+pub struct Capability {
+    /// Granted method, or any method if missing.
+    pub method: Option<Method>,
+    /// Resource the method is granted on, or any resource if missing.
+    pub resource: Option<Resource>,
+}
+
+impl Capability {
+    /// Creates a new `Capability`.
+    pub fn new(method: Option<Method>, resource: Option<Resource>) -> Self {
+        Capability {
+            method,
+            resource,
+        }
+    }
+
+    /// Parses a `Capability` from a `&str`.
+    pub fn parse(s: &str) -> Result<Capability> {
+        let mut parts = s.trim().splitn(2, ':');
+
+        let method = match parts.next() {
+            Some("*") => None,
+            Some(method) => Some(Method::parse(method)?),
+            None => return Err(String::from("unknown capability")),
+        };
+
+        let resource = match parts.next() {
+            Some("*") | None => None,
+            Some(resource) => Some(Resource::parse(resource)?),
+        };
+
+        Ok(Capability::new(method, resource))
+    }
+
+    /// Returns if the `Capability` grants a `Method` on a `Resource`.
+    pub fn allows(&self, method: &Method, resource: &Resource) -> bool {
+        let method_allowed = match self.method {
+            Some(ref granted) => granted == method,
+            None => true,
+        };
+
+        let resource_allowed = match self.resource {
+            Some(ref granted) => granted == resource,
+            None => true,
+        };
+
+        method_allowed && resource_allowed
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.method {
+            Some(ref method) => write!(f, "{}:", method)?,
+            None => write!(f, "*:")?,
+        }
+
+        match self.resource {
+            Some(ref resource) => write!(f, "{}", resource),
+            None => write!(f, "*"),
+        }
+    }
+}
+
+impl Sizable for Capability {
+    fn size(&self) -> u64 {
+        self.method.size() +
+            self.resource.size()
+    }
+}
+
+impl Checkable for Capability {
+    fn check(&self) -> Result<()> {
+        self.method.check()?;
+        self.resource.check()
+    }
+}
+
+impl Serializable for Capability {}
+
+impl Datable for Capability {}
+
+/// Type representing an access control list, granting a set of capabilities. An empty `Acl` grants
+/// no capability.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Default, Hash, Serialize, Deserialize)]
+pub struct Acl {
+    /// Granted capabilities, sorted and without duplicates.
+    pub capabilities: Vec<Capability>,
+}
+
+impl Acl {
+    /// Creates a new empty `Acl`.
+    pub fn new() -> Self {
+        Acl::default()
+    }
+
+    /// Parses an `Acl` from a `&str` of comma-separated capabilities.
+    pub fn parse(s: &str) -> Result<Acl> {
+        let mut acl = Acl::new();
+
+        for capability in s.split(',') {
+            if capability.trim().is_empty() {
+                continue;
+            }
+
+            acl = acl.grant(&Capability::parse(capability)?)?;
+        }
+
+        Ok(acl)
+    }
+
+    /// Adds a `Capability` to the `Acl`.
+    pub fn grant(mut self, capability: &Capability) -> Result<Self> {
+        capability.check()?;
+
+        if let Err(idx) = self.capabilities.binary_search(capability) {
+            self.capabilities.insert(idx, capability.to_owned());
+        }
+
+        Ok(self)
+    }
+
+    /// Removes a `Capability` from the `Acl`.
+    pub fn revoke(mut self, capability: &Capability) -> Result<Self> {
+        capability.check()?;
+
+        match self.capabilities.binary_search(capability) {
+            Ok(idx) => {
+                self.capabilities.remove(idx);
+            },
+            Err(_) => {
+                return Err(String::from("not found"));
+            },
+        }
+
+        Ok(self)
+    }
+
+    /// Returns if the `Acl` grants no capability.
+    pub fn is_empty(&self) -> bool {
+        self.capabilities.is_empty()
+    }
+
+    /// Returns if the `Acl` grants a `Method` on a `Resource`.
+    pub fn allows(&self, method: &Method, resource: &Resource) -> bool {
+        self.capabilities
+            .iter()
+            .any(|capability| capability.allows(method, resource))
+    }
+
+    /// Returns if the `Acl` grants a `Method` on any `Resource`.
+    pub fn allows_method(&self, method: &Method) -> bool {
+        self.capabilities
+            .iter()
+            .any(|capability| {
+                match capability.method {
+                    Some(ref granted) => granted == method,
+                    None => true,
+                }
+            })
+    }
+
+    /// Checks that the `Acl` grants a `Method` on a `Resource`. Ping and session methods are always
+    /// granted, and error messages are granted for all the methods granted on any resource.
+    pub fn check_access(&self, method: &Method, resource: &Resource) -> Result<()> {
+        let allowed = match (method, resource) {
+            (&Method::Ping, _) | (&Method::Session, _) => true,
+            (_, &Resource::Error) => self.allows_method(method),
+            _ => self.allows(method, resource),
+        };
+
+        if !allowed {
+            return Err(String::from("invalid permission"));
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Acl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (idx, capability) in self.capabilities.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ",")?;
+            }
+
+            write!(f, "{}", capability)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Sizable for Acl {
+    fn size(&self) -> u64 {
+        self.capabilities.size()
+    }
+}
+
+impl Checkable for Acl {
+    fn check(&self) -> Result<()> {
+        self.capabilities.check()?;
+
+        for idx in 1..self.capabilities.len() {
+            if self.capabilities[idx - 1] >= self.capabilities[idx] {
+                return Err(String::from("invalid order"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Serializable for Acl {}
+
+impl Datable for Acl {}
\ No newline at end of file
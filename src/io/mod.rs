@@ -5,16 +5,29 @@
 /// Type used to represent read/write permissions.
 pub mod permission;
 
+/// Types used to represent fine-grained access control lists.
+pub mod acl;
+
 /// Type used to represent I/O sessions.
 pub mod session;
 
-/// Traits implemented by stores and storable types.
+/// Type used to issue, renew and revoke I/O sessions.
+pub mod session_manager;
+
+/// Traits implemented by stores and storable types, and store implementations.
 pub mod store;
 
 /// Traits implemented by networking facilities and types that can use them.
 pub mod network;
 
 pub use self::permission::Permission;
+pub use self::acl::{Capability, Acl};
 pub use self::session::Session;
-pub use self::store::{Store, Storable};
+pub use self::session_manager::SessionManager;
+pub use self::store::{Store, Storable, MemoryStore, WalStore};
+pub use self::store::{Batch, BatchOp};
+pub use self::store::{Direction, StoreIter, StorableIter};
+pub use self::store::{StoreKey, IndexKey};
+pub use self::store::{Filter, Query};
+pub use self::store::{StoreEvent, EventChannel};
 pub use self::network::*;
\ No newline at end of file
@@ -11,7 +11,7 @@ use base::Serializable;
 use base::Datable;
 use base::{Eval, EvalMut};
 use base::Meta;
-use crypto::Hash;
+use crypto::{Hash, Authenticate, Sign};
 use io::Session;
 use io::Method;
 use io::Resource;
@@ -178,6 +178,54 @@ impl<S, D, P> Message<S, D, P>
         hasher.check(&msg, &digest)
     }
 
+    /// Verifies the `Message` session authentication tag against an authentication key.
+    pub fn verify_session_authentication<K, T, A>(&self, authenticator: &mut A, key: &K) -> Result<bool>
+        where   S: Serializable,
+                K: Datable + ConstantSize,
+                T: Datable + ConstantSize + Serializable,
+                A: Authenticate<K, T>
+    {
+        self.session.verify_authentication(authenticator, key)
+    }
+
+    /// Checks the `Message` session authentication tag against an authentication key.
+    pub fn check_session_authentication<K, T, A>(&self, authenticator: &mut A, key: &K) -> Result<()>
+        where   S: Serializable,
+                K: Datable + ConstantSize,
+                T: Datable + ConstantSize + Serializable,
+                A: Authenticate<K, T>
+    {
+        self.check()?;
+
+        self.session.check_authentication(authenticator, key)
+    }
+
+    /// Verifies the `Message` session signature against a public key.
+    pub fn verify_session_signature<Seed, Pk, Sk, Sig, Sg>(&self, signer: &mut Sg, pk: &Pk) -> Result<bool>
+        where   S: Serializable,
+                Seed: Datable + ConstantSize,
+                Pk: Datable + ConstantSize,
+                Sk: Datable + ConstantSize,
+                Sig: Datable + ConstantSize + Serializable,
+                Sg: Sign<Seed, Pk, Sk, Sig>
+    {
+        self.session.verify_signature(signer, pk)
+    }
+
+    /// Checks the `Message` session signature against a public key.
+    pub fn check_session_signature<Seed, Pk, Sk, Sig, Sg>(&self, signer: &mut Sg, pk: &Pk) -> Result<()>
+        where   S: Serializable,
+                Seed: Datable + ConstantSize,
+                Pk: Datable + ConstantSize,
+                Sk: Datable + ConstantSize,
+                Sig: Datable + ConstantSize + Serializable,
+                Sg: Sign<Seed, Pk, Sk, Sig>
+    {
+        self.check()?;
+
+        self.session.check_signature(signer, pk)
+    }
+
     /// Evals the `Message`.
     pub fn eval<Ev, EP, ER>(&self, params: &EP, evaluator: &Ev)
         -> Result<ER>
@@ -244,11 +292,11 @@ impl<S, D, P> Checkable for Message<S, D, P>
         self.session.check()?;
 
         self.method.check()?;
-        self.method.check_permission(&self.session.permission)?;
-
         self.resource.check()?;
         self.resource.check_method(&self.method)?;
 
+        self.session.check_access(&self.method, &self.resource)?;
+
         self.payload.check()?;
 
         Ok(())
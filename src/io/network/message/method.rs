@@ -61,24 +61,26 @@ impl Method {
         }
     }
 
-    /// Checks a `Permission` against the `Method`.
+    /// Checks a `Permission` against the `Method`. Read methods require a permission containing
+    /// `Permission::Read`, write methods one containing `Permission::Write`.
     pub fn check_permission(&self, permission: &Permission) -> Result<()> {
-        if self == &Method::Session {
-            return Ok(());
-        }
+        let allowed = match self {
+            &Method::Session => true,
+            &Method::Ping => permission.is_empty(),
+            &Method::Count |
+            &Method::List |
+            &Method::Lookup |
+            &Method::Get |
+            &Method::Eval => permission.can_read(),
+            &Method::Create |
+            &Method::Update |
+            &Method::Upsert |
+            &Method::Delete |
+            &Method::EvalMut => permission.can_write(),
+        };
 
-        if permission >= &Permission::Write {
-            if self < &Method::Create || self == &Method::Eval {
-                return Err(String::from("invalid permission"));
-            }
-        } else if permission > &Permission::None && permission < &Permission::Write {
-            if self == &Method::Ping || (self >= &Method::Create && self != &Method::Eval) {
-                return Err(String::from("invalid permission"));
-            }
-        } else if permission == &Permission::None {
-            if self != &Method::Ping {
-                return Err(String::from("invalid permission"));
-            }
+        if !allowed {
+            return Err(String::from("invalid permission"));
         }
 
         Ok(())
@@ -2,14 +2,14 @@
 //!
 //! `request` is the module providing the type representing network request messages.
 
-use std::mem;
-
 use base::Result;
 use base::{Sizable, ConstantSize};
 use base::Checkable;
 use base::Serializable;
 use base::Datable;
 use io::store::{Store, Storable};
+use io::store::prefix::encode_prefix;
+use io::store::StoreKey;
 use io::network::message::Resource;
 use io::network::message::Message;
 
@@ -93,16 +93,11 @@ impl<St, S, MS, D, P>
     where   St: Store<S>,
             S: Datable + Serializable,
             MS: Datable + Serializable,
-            D: Ord + Datable + ConstantSize + Serializable,
+            D: Ord + Datable + ConstantSize + Serializable + StoreKey,
             P: Datable + Serializable
 {
     fn store_prefix() -> Vec<u8> {
-        let mut prefix = Vec::new();
-
-        let _prefix: [u8; 8] = unsafe { mem::transmute(REQUEST_CODE) };
-        prefix.extend_from_slice(&_prefix[..]);
-
-        prefix
+        encode_prefix(REQUEST_CODE)
     }
 
     fn store_key(&self) -> Result<D> {
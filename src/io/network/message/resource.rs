@@ -45,6 +45,8 @@ pub enum Resource {
     EvalMutResult,
     /// Error data.
     Error,
+    /// Blockheader data.
+    BlockHeader,
 }
 
 impl Resource {
@@ -66,6 +68,7 @@ impl Resource {
             "evalmutparams" => Ok(Resource::EvalMutParams),
             "evalmutresult" => Ok(Resource::EvalMutResult),
             "error" => Ok(Resource::Error),
+            "blockheader" => Ok(Resource::BlockHeader),
             _ => Err("unknown resource".into())
         }
     }
@@ -91,8 +94,19 @@ impl Resource {
                 }
             },
             2...9 => {
-                if self <= &Resource::Session || self >= &Resource::EvalParams {
-                    return Err(String::from("invalid method"));
+                match self {
+                    &Resource::Node |
+                    &Resource::Coin |
+                    &Resource::Input |
+                    &Resource::Output |
+                    &Resource::Transaction |
+                    &Resource::BlockNode |
+                    &Resource::Block |
+                    &Resource::BlockHeader |
+                    &Resource::BlockGraph => {},
+                    _ => {
+                        return Err(String::from("invalid method"));
+                    },
                 }
             },
             10 => {
@@ -144,6 +158,7 @@ impl fmt::Display for Resource {
             Resource::EvalMutParams => write!(f, "evalmutparams"),
             Resource::EvalMutResult => write!(f, "evalmutresult"),
             Resource::Error => write!(f, "error"),
+            Resource::BlockHeader => write!(f, "blockheader"),
         }
     }
 }
@@ -2,14 +2,14 @@
 //!
 //! `response` is the module providing the type representing network response messages.
 
-use std::mem;
-
 use base::Result;
 use base::{Sizable, ConstantSize};
 use base::Checkable;
 use base::Serializable;
 use base::Datable;
 use io::store::{Store, Storable};
+use io::store::prefix::encode_prefix;
+use io::store::StoreKey;
 use io::network::message::Message;
 
 /// Code of the `Response` type.
@@ -78,16 +78,11 @@ impl<St, S, MS, D, P>
     where   St: Store<S>,
             S: Datable + Serializable,
             MS: Datable + Serializable,
-            D: Ord + Datable + ConstantSize + Serializable,
+            D: Ord + Datable + ConstantSize + Serializable + StoreKey,
             P: Datable + Serializable
 {
     fn store_prefix() -> Vec<u8> {
-        let mut prefix = Vec::new();
-
-        let _prefix: [u8; 8] = unsafe { mem::transmute(RESPONSE_CODE) };
-        prefix.extend_from_slice(&_prefix[..]);
-
-        prefix
+        encode_prefix(RESPONSE_CODE)
     }
 
     fn store_key(&self) -> Result<D> {
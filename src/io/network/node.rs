@@ -2,8 +2,6 @@
 //!
 //! `node` is the module providing the type used to represent a node in the distributed ledger network.
 
-use std::mem;
-
 use base::Result;
 use base::Checkable;
 use base::Datable;
@@ -12,6 +10,8 @@ use base::{Sizable, VariableSize};
 use base::{Eval, EvalMut};
 use base::Meta;
 use io::{Store, Storable};
+use io::store::prefix::encode_prefix;
+use io::StoreKey;
 
 /// Code of the `Node` type.
 pub const NODE_CODE: u64 = 8;
@@ -135,16 +135,11 @@ impl<St, S, A, P>
     for Node<A, P>
     where   St: Store<S>,
             S: Datable + Serializable,
-            A: Ord + Datable + VariableSize + Serializable,
+            A: Ord + Datable + VariableSize + Serializable + StoreKey,
             P: Datable + Serializable
 {
     fn store_prefix() -> Vec<u8> {
-        let mut prefix = Vec::new();
-
-        let _prefix: [u8; 8] = unsafe { mem::transmute(NODE_CODE) };
-        prefix.extend_from_slice(&_prefix[..]);
-
-        prefix
+        encode_prefix(NODE_CODE)
     }
 
     fn store_key(&self) -> Result<A> {
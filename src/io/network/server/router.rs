@@ -33,6 +33,25 @@ pub trait Router<St, StS, S, D, MP, H>
             Ok(vec![])
     }
 
+    /// Checks that the session of a `Request` has been issued by the node. The check is applied by
+    /// `route` to all the requests but ping and session requests, which can be sent without a session,
+    /// before any middleware. The default check only rejects the expired sessions and the sessions
+    /// without an authentication tag or signature: routers holding the node key should verify it with
+    /// `Message::check_session_authentication` or `Message::check_session_signature`.
+    fn check_session(&mut self, request: &Request<S, D, MP>) -> Result<()> {
+        let session = &request.message.session;
+
+        if !session.is_authenticated() {
+            return Err(String::from("unauthenticated session"));
+        }
+
+        if session.is_expired()? {
+            return Err(String::from("expired session"));
+        }
+
+        Ok(())
+    }
+
     /// Routes an incoming request to the right handler.
     fn route<Ev, EvM>(&mut self,
                      store: &mut St,
@@ -46,6 +65,11 @@ pub trait Router<St, StS, S, D, MP, H>
     {
         request.check()?;
 
+        match request.message.method {
+            Method::Ping | Method::Session => {},
+            _ => self.check_session(request)?,
+        }
+
         let mut request = request.to_owned();
 
         for mut cb in self.middlewares()? {
@@ -1,6 +1,10 @@
 //! # IO
 //!
 //! `io` is the module providing the type that represent read and write permissions.
+//!
+//! Permissions can be combined, so that a `Permission::Read | Permission::Write` permission grants
+//! both read and write operations. Permissions must be compared by containment (e.g. with
+//! `Permission::contains`), not by order.
 
 use base::Result;
 use base::Checkable;
@@ -24,14 +28,31 @@ bitflags! {
 }
 
 impl Permission {
-    /// Parses a `Permission` from a `&str`.
+    /// Parses a `Permission` from a `&str`. Combined permissions are separated by commas
+    /// (e.g. "read,write").
     pub fn parse(s: &str) -> Result<Permission> {
-        match s {
-            "none" => Ok(Permission::None),
-            "read" => Ok(Permission::Read),
-            "write" => Ok(Permission::Write),
-            _ => Err("unknown permission".into())
+        let mut permission = Permission::None;
+
+        for flag in s.split(',') {
+            match flag.trim() {
+                "none" => {},
+                "read" => permission |= Permission::Read,
+                "write" => permission |= Permission::Write,
+                _ => return Err("unknown permission".into())
+            }
         }
+
+        Ok(permission)
+    }
+
+    /// Returns if the `Permission` grants read operations.
+    pub fn can_read(&self) -> bool {
+        self.contains(Permission::Read)
+    }
+
+    /// Returns if the `Permission` grants write operations.
+    pub fn can_write(&self) -> bool {
+        self.contains(Permission::Write)
     }
 }
 
@@ -43,16 +64,24 @@ impl Default for Permission {
 
 impl fmt::Display for Permission {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            &Permission::None => write!(f, "none"),
-            &Permission::Read => write!(f, "read"),
-            &Permission::Write => write!(f, "write"),
-            &Permission { .. } => Err(fmt::Error), // unreachable
+        match (self.can_read(), self.can_write()) {
+            (false, false) => write!(f, "none"),
+            (true, false) => write!(f, "read"),
+            (false, true) => write!(f, "write"),
+            (true, true) => write!(f, "read,write"),
         }
     }
 }
 
-impl Checkable for Permission {}
+impl Checkable for Permission {
+    fn check(&self) -> Result<()> {
+        if Permission::from_bits(self.bits()).is_none() {
+            return Err(String::from("invalid permission"));
+        }
+
+        Ok(())
+    }
+}
 
 impl Serializable for Permission {}
 
@@ -1,14 +1,26 @@
 //! # Session
 //!
 //! `session` is the module providing the type that represent an I/O session.
+//!
+//! A `Session` can be authenticated by the node issuing it, either with a `crypto::Authenticate`
+//! tag computed with a key known only to the node, or with a `crypto::Sign` signature. The tag covers
+//! all the other fields of the session, so a session cannot be forged nor altered (e.g. to raise its
+//! permission or extend its expiration time) without the node key.
+//!
+//! A `Session` can also carry an `Acl`, granting access to specific methods on specific resources.
+//! When the access control list is not empty, it is enforced in place of the session permission.
 
 use base::Result;
 use base::Checkable;
 use base::Serializable;
 use base::Sizable;
 use base::Datable;
+use base::ConstantSize;
+use crypto::{Authenticate, Sign};
 use util::Timestamp;
 use io::Permission;
+use io::Acl;
+use io::network::{Method, Resource};
 
 /// A type representing an I/O session.
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Default, Hash, Serialize, Deserialize)]
@@ -19,10 +31,14 @@ pub struct Session<P>
     pub id: u64,
     /// Permission guaranteed by the session.
     pub permission: Permission,
+    /// Capabilities granted by the session, enforced in place of the permission if not empty.
+    pub acl: Acl,
     /// Expiration time of the session.
     pub expires_at: Timestamp,
     /// Payload of the session.
     pub payload: P,
+    /// Authentication tag or signature of the session, empty if the session is not authenticated.
+    pub tag: Vec<u8>,
 }
 
 impl<P> Session<P>
@@ -36,18 +52,168 @@ impl<P> Session<P>
         let session = Session {
             id: id,
             permission: permission.to_owned(),
+            acl: Acl::new(),
             expires_at: expires_at.to_owned(),
             payload: payload.to_owned(),
+            tag: Vec::new(),
         };
 
         Ok(session)
     }
 
+    /// Sets the `Acl` of the `Session`. The authentication tag of the session is cleared, as it does
+    /// not cover the new capabilities, and the session no longer matches the one tracked by its
+    /// `SessionManager`: use `SessionManager::issue_with_acl` to issue a session with an `Acl`.
+    pub fn acl(mut self, acl: &Acl) -> Result<Self> {
+        acl.check()?;
+
+        self.acl = acl.to_owned();
+        self.tag = Vec::new();
+
+        Ok(self)
+    }
+
+    /// Checks that the `Session` grants a `Method` on a `Resource`. The `Session` permission is
+    /// checked if the `Session` has no `Acl`.
+    pub fn check_access(&self, method: &Method, resource: &Resource) -> Result<()> {
+        if self.acl.is_empty() {
+            return method.check_permission(&self.permission);
+        }
+
+        self.acl.check_access(method, resource)
+    }
+
     /// Returns if the `Session` has already expired.
     pub fn is_expired(&self) -> Result<bool> {
         let now = Timestamp::now()?;
         Ok(self.expires_at <= now)
     }
+
+    /// Returns if the `Session` has an authentication tag or signature.
+    pub fn is_authenticated(&self) -> bool {
+        !self.tag.is_empty()
+    }
+
+    /// Returns the message authenticated by the `Session` tag, which covers all the session fields
+    /// but the tag itself.
+    pub fn tag_message(&self) -> Result<Vec<u8>>
+        where   P: Serializable
+    {
+        let mut session = self.clone();
+        session.tag = Vec::new();
+
+        session.to_bytes()
+    }
+
+    /// Authenticates the `Session` with an authentication key.
+    pub fn authenticate<K, T, A>(mut self, authenticator: &mut A, key: &K) -> Result<Self>
+        where   P: Serializable,
+                K: Datable + ConstantSize,
+                T: Datable + ConstantSize + Serializable,
+                A: Authenticate<K, T>
+    {
+        self.check()?;
+        key.check()?;
+
+        let tag = authenticator.authenticate(&self.tag_message()?, key)?;
+        self.tag = tag.to_bytes()?;
+
+        Ok(self)
+    }
+
+    /// Verifies the `Session` authentication tag against an authentication key.
+    pub fn verify_authentication<K, T, A>(&self, authenticator: &mut A, key: &K) -> Result<bool>
+        where   P: Serializable,
+                K: Datable + ConstantSize,
+                T: Datable + ConstantSize + Serializable,
+                A: Authenticate<K, T>
+    {
+        self.check()?;
+        key.check()?;
+
+        if !self.is_authenticated() {
+            return Ok(false);
+        }
+
+        let tag = match T::from_bytes(&self.tag) {
+            Ok(tag) => tag,
+            Err(_) => return Ok(false),
+        };
+
+        authenticator.verify(&self.tag_message()?, key, &tag)
+    }
+
+    /// Checks the `Session` authentication tag against an authentication key.
+    pub fn check_authentication<K, T, A>(&self, authenticator: &mut A, key: &K) -> Result<()>
+        where   P: Serializable,
+                K: Datable + ConstantSize,
+                T: Datable + ConstantSize + Serializable,
+                A: Authenticate<K, T>
+    {
+        if !self.verify_authentication(authenticator, key)? {
+            return Err(String::from("invalid tag"));
+        }
+
+        Ok(())
+    }
+
+    /// Signs the `Session` with a secret key.
+    pub fn sign<Seed, Pk, Sk, Sig, Sg>(mut self, signer: &mut Sg, sk: &Sk) -> Result<Self>
+        where   P: Serializable,
+                Seed: Datable + ConstantSize,
+                Pk: Datable + ConstantSize,
+                Sk: Datable + ConstantSize,
+                Sig: Datable + ConstantSize + Serializable,
+                Sg: Sign<Seed, Pk, Sk, Sig>
+    {
+        self.check()?;
+        sk.check()?;
+
+        let signature = signer.sign(&self.tag_message()?, sk)?;
+        self.tag = signature.to_bytes()?;
+
+        Ok(self)
+    }
+
+    /// Verifies the `Session` signature against a public key.
+    pub fn verify_signature<Seed, Pk, Sk, Sig, Sg>(&self, signer: &mut Sg, pk: &Pk) -> Result<bool>
+        where   P: Serializable,
+                Seed: Datable + ConstantSize,
+                Pk: Datable + ConstantSize,
+                Sk: Datable + ConstantSize,
+                Sig: Datable + ConstantSize + Serializable,
+                Sg: Sign<Seed, Pk, Sk, Sig>
+    {
+        self.check()?;
+        pk.check()?;
+
+        if !self.is_authenticated() {
+            return Ok(false);
+        }
+
+        let signature = match Sig::from_bytes(&self.tag) {
+            Ok(signature) => signature,
+            Err(_) => return Ok(false),
+        };
+
+        signer.verify(&self.tag_message()?, pk, &signature)
+    }
+
+    /// Checks the `Session` signature against a public key.
+    pub fn check_signature<Seed, Pk, Sk, Sig, Sg>(&self, signer: &mut Sg, pk: &Pk) -> Result<()>
+        where   P: Serializable,
+                Seed: Datable + ConstantSize,
+                Pk: Datable + ConstantSize,
+                Sk: Datable + ConstantSize,
+                Sig: Datable + ConstantSize + Serializable,
+                Sg: Sign<Seed, Pk, Sk, Sig>
+    {
+        if !self.verify_signature(signer, pk)? {
+            return Err(String::from("invalid signature"));
+        }
+
+        Ok(())
+    }
 }
 
 impl<P> Checkable for Session<P>
@@ -55,7 +221,9 @@ impl<P> Checkable for Session<P>
 {
     fn check(&self) -> Result<()> {
         self.permission.check()?;
-        self.payload.check()
+        self.acl.check()?;
+        self.payload.check()?;
+        self.tag.check()
     }
 }         
 
@@ -65,8 +233,10 @@ impl<P> Sizable for Session<P>
     fn size(&self) -> u64 {
         self.id.size() +
             self.permission.size() +
+            self.acl.size() +
             self.expires_at.size() +
-            self.payload.size()
+            self.payload.size() +
+            self.tag.size()
     }
 }       
 
@@ -0,0 +1,447 @@
+//! # Session Manager
+//!
+//! `session_manager` is the module providing `SessionManager`, the type used to issue, track, renew
+//! and revoke I/O sessions. Session ids are drawn from the operating system random number generator,
+//! so they cannot be guessed from the ids previously issued.
+//!
+//! A `SessionManager` is cheaply clonable, and its clones share the same sessions, so the same
+//! sessions can be validated by a store and by a network handler.
+
+use rand::{OsRng, Rng};
+
+use std::sync::{Arc, Mutex};
+use std::collections::HashMap;
+
+use base::Result;
+use base::Checkable;
+use base::Sizable;
+use base::Serializable;
+use base::Datable;
+use base::ConstantSize;
+use crypto::{Hash, Authenticate, Sign};
+use util::{Timestamp, TimestampDiff};
+use io::Permission;
+use io::Acl;
+use io::Session;
+use io::store::memory::lock;
+use io::network::{Message, Method, Resource, Request, Response};
+
+/// Default duration of the sessions issued by a `SessionManager`, in seconds.
+pub const DEFAULT_SESSION_DURATION: u64 = 3600;
+
+/// Type used to issue and track I/O sessions.
+#[derive(Clone, Debug)]
+pub struct SessionManager<S>
+    where   S: Datable
+{
+    duration: TimestampDiff,
+    sessions: Arc<Mutex<HashMap<u64, Session<S>>>>,
+}
+
+impl<S> SessionManager<S>
+    where   S: Datable
+{
+    /// Creates a new `SessionManager`.
+    pub fn new() -> Self {
+        SessionManager {
+            duration: TimestampDiff::from_secs(DEFAULT_SESSION_DURATION),
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Sets the duration of the sessions issued and renewed by the `SessionManager`.
+    pub fn duration(mut self, duration: &TimestampDiff) -> Result<Self> {
+        if duration.as_i64() <= 0 {
+            return Err(String::from("invalid duration"));
+        }
+
+        self.duration = duration.to_owned();
+
+        Ok(self)
+    }
+
+    /// Returns the number of tracked sessions.
+    pub fn len(&self) -> u64 {
+        lock(&self.sessions).len() as u64
+    }
+
+    /// Returns if there are no tracked sessions.
+    pub fn is_empty(&self) -> bool {
+        lock(&self.sessions).is_empty()
+    }
+
+    /// Issues a new `Session`. The expired sessions are garbage-collected beforehand.
+    pub fn issue(&self, permission: &Permission, payload: &S) -> Result<Session<S>> {
+        self.issue_with_acl(permission, &Acl::new(), payload)
+    }
+
+    /// Issues a new `Session` granting the capabilities of an `Acl`. The `Acl` is set before the
+    /// session is tracked, so the issued session is validated and can be authenticated as is,
+    /// unlike a session whose `Acl` is set with `Session::acl` after it has been issued.
+    pub fn issue_with_acl(&self, permission: &Permission, acl: &Acl, payload: &S) -> Result<Session<S>> {
+        permission.check()?;
+        acl.check()?;
+        payload.check()?;
+
+        self.clear_expired()?;
+
+        let mut rng = OsRng::new()
+            .map_err(|e| format!("{}", e))?;
+
+        let expires_at = Timestamp::now()? + self.duration;
+
+        let sessions = &mut *lock(&self.sessions);
+
+        let mut id = rng.next_u64();
+
+        while id == 0 || sessions.contains_key(&id) {
+            id = rng.next_u64();
+        }
+
+        let session = Session::new(id, permission, &expires_at, payload)?
+                        .acl(acl)?;
+        sessions.insert(id, session.clone());
+
+        Ok(session)
+    }
+
+    /// Retrieves a live `Session` from its id.
+    pub fn get(&self, id: u64) -> Result<Session<S>> {
+        match lock(&self.sessions).get(&id) {
+            Some(session) => {
+                if session.is_expired()? {
+                    return Err(String::from("expired session"));
+                }
+
+                Ok(session.to_owned())
+            },
+            None => Err(String::from("session not found")),
+        }
+    }
+
+    /// Validates a `Session`, checking that it has been issued by the `SessionManager`, that it
+    /// has not been tampered with and that it is still valid.
+    pub fn validate(&self, session: &Session<S>) -> Result<()> {
+        session.check()?;
+
+        if session.is_expired()? {
+            return Err(String::from("expired session"));
+        }
+
+        match lock(&self.sessions).get(&session.id) {
+            Some(issued) => {
+                if issued != session {
+                    return Err(String::from("invalid session"));
+                }
+            },
+            None => {
+                return Err(String::from("session not found"));
+            },
+        }
+
+        Ok(())
+    }
+
+    /// Validates a `Session` used in a read operation.
+    pub fn validate_read(&self, session: &Session<S>) -> Result<()> {
+        self.validate(session)?;
+
+        if !session.permission.can_read() {
+            return Err(String::from("invalid permission"));
+        }
+
+        Ok(())
+    }
+
+    /// Validates a `Session` used in a write operation.
+    pub fn validate_write(&self, session: &Session<S>) -> Result<()> {
+        self.validate(session)?;
+
+        if !session.permission.can_write() {
+            return Err(String::from("invalid permission"));
+        }
+
+        Ok(())
+    }
+
+    /// Renews a live `Session`, returning it with a new expiration time. Authenticated sessions
+    /// cannot be renewed without their authentication key: use `SessionManager::renew_authenticated`
+    /// or `SessionManager::renew_signed` instead.
+    pub fn renew(&self, session: &Session<S>) -> Result<Session<S>> {
+        self.renew_with(session, |session| {
+            if session.is_authenticated() {
+                return Err(String::from("authenticated session"));
+            }
+
+            Ok(session)
+        })
+    }
+
+    /// Renews a live `Session`, authenticating it with an authentication key.
+    pub fn renew_authenticated<K, T, A>(&self, session: &Session<S>, authenticator: &mut A, key: &K)
+        -> Result<Session<S>>
+        where   S: Serializable,
+                K: Datable + ConstantSize,
+                T: Datable + ConstantSize + Serializable,
+                A: Authenticate<K, T>
+    {
+        self.renew_with(session, |session| session.authenticate(authenticator, key))
+    }
+
+    /// Renews a live `Session`, signing it with a secret key.
+    pub fn renew_signed<Seed, Pk, Sk, Sig, Sg>(&self, session: &Session<S>, signer: &mut Sg, sk: &Sk)
+        -> Result<Session<S>>
+        where   S: Serializable,
+                Seed: Datable + ConstantSize,
+                Pk: Datable + ConstantSize,
+                Sk: Datable + ConstantSize,
+                Sig: Datable + ConstantSize + Serializable,
+                Sg: Sign<Seed, Pk, Sk, Sig>
+    {
+        self.renew_with(session, |session| session.sign(signer, sk))
+    }
+
+    /// Renews a live `Session`, sealing the renewed session with a given closure before it replaces
+    /// the tracked one.
+    fn renew_with<F>(&self, session: &Session<S>, seal: F) -> Result<Session<S>>
+        where   F: FnOnce(Session<S>) -> Result<Session<S>>
+    {
+        self.validate(session)?;
+
+        let expires_at = Timestamp::now()? + self.duration;
+
+        let sessions = &mut *lock(&self.sessions);
+
+        match sessions.get_mut(&session.id) {
+            Some(issued) => {
+                let mut renewed = issued.to_owned();
+                renewed.expires_at = expires_at;
+
+                *issued = seal(renewed)?;
+
+                Ok(issued.to_owned())
+            },
+            None => Err(String::from("session not found")),
+        }
+    }
+
+    /// Authenticates a live `Session` with an authentication key. The authenticated session replaces
+    /// the tracked one, so only the authenticated session is valid afterwards.
+    pub fn authenticate<K, T, A>(&self, session: &Session<S>, authenticator: &mut A, key: &K)
+        -> Result<Session<S>>
+        where   S: Serializable,
+                K: Datable + ConstantSize,
+                T: Datable + ConstantSize + Serializable,
+                A: Authenticate<K, T>
+    {
+        self.validate(session)?;
+
+        let session = session.to_owned().authenticate(authenticator, key)?;
+
+        self.replace(&session)?;
+
+        Ok(session)
+    }
+
+    /// Signs a live `Session` with a secret key. The signed session replaces the tracked one, so
+    /// only the signed session is valid afterwards.
+    pub fn sign<Seed, Pk, Sk, Sig, Sg>(&self, session: &Session<S>, signer: &mut Sg, sk: &Sk)
+        -> Result<Session<S>>
+        where   S: Serializable,
+                Seed: Datable + ConstantSize,
+                Pk: Datable + ConstantSize,
+                Sk: Datable + ConstantSize,
+                Sig: Datable + ConstantSize + Serializable,
+                Sg: Sign<Seed, Pk, Sk, Sig>
+    {
+        self.validate(session)?;
+
+        let session = session.to_owned().sign(signer, sk)?;
+
+        self.replace(&session)?;
+
+        Ok(session)
+    }
+
+    /// Replaces a tracked `Session` with an updated version.
+    fn replace(&self, session: &Session<S>) -> Result<()> {
+        match lock(&self.sessions).get_mut(&session.id) {
+            Some(issued) => {
+                *issued = session.to_owned();
+
+                Ok(())
+            },
+            None => Err(String::from("session not found")),
+        }
+    }
+
+    /// Revokes a `Session` from its id.
+    pub fn revoke(&self, id: u64) -> Result<()> {
+        match lock(&self.sessions).remove(&id) {
+            Some(_) => Ok(()),
+            None => Err(String::from("session not found")),
+        }
+    }
+
+    /// Revokes all the sessions.
+    pub fn revoke_all(&self) {
+        lock(&self.sessions).clear();
+    }
+
+    /// Removes the expired sessions, returning their number.
+    pub fn clear_expired(&self) -> Result<u64> {
+        let now = Timestamp::now()?;
+
+        let sessions = &mut *lock(&self.sessions);
+
+        let len = sessions.len();
+
+        sessions.retain(|_, session| session.expires_at > now);
+
+        Ok((len - sessions.len()) as u64)
+    }
+
+    /// Handles a session `Request`, as in `Handler::handle_session`. The `Request` session is
+    /// renewed if live, otherwise a new session is issued with the requested permission and payload.
+    /// The sessions are not authenticated: use `SessionManager::handle_session_authenticated` or
+    /// `SessionManager::handle_session_signed` when the sessions are checked by a `Router`.
+    pub fn handle_session<D, P, H>(&self, request: &Request<S, D, P>, hasher: &mut H)
+        -> Result<Response<S, D, P>>
+        where   S: Serializable,
+                D: Ord + Datable + ConstantSize + Serializable,
+                P: Datable + Serializable,
+                H: Hash<D>
+    {
+        self.handle_session_with(request, hasher, |manager, session| {
+            if manager.validate(session).is_ok() {
+                manager.renew(session)
+            } else {
+                manager.issue(&session.permission, &session.payload)
+            }
+        })
+    }
+
+    /// Handles a session `Request`, as `SessionManager::handle_session` does, authenticating the
+    /// renewed or issued session with an authentication key.
+    pub fn handle_session_authenticated<D, P, H, K, T, A>(&self,
+                                                          request: &Request<S, D, P>,
+                                                          hasher: &mut H,
+                                                          authenticator: &mut A,
+                                                          key: &K)
+        -> Result<Response<S, D, P>>
+        where   S: Serializable,
+                D: Ord + Datable + ConstantSize + Serializable,
+                P: Datable + Serializable,
+                H: Hash<D>,
+                K: Datable + ConstantSize,
+                T: Datable + ConstantSize + Serializable,
+                A: Authenticate<K, T>
+    {
+        self.handle_session_with(request, hasher, |manager, session| {
+            if manager.validate(session).is_ok() {
+                manager.renew_authenticated(session, authenticator, key)
+            } else {
+                let session = manager.issue(&session.permission, &session.payload)?;
+                manager.authenticate(&session, authenticator, key)
+            }
+        })
+    }
+
+    /// Handles a session `Request`, as `SessionManager::handle_session` does, signing the renewed
+    /// or issued session with a secret key.
+    pub fn handle_session_signed<D, P, H, Seed, Pk, Sk, Sig, Sg>(&self,
+                                                                 request: &Request<S, D, P>,
+                                                                 hasher: &mut H,
+                                                                 signer: &mut Sg,
+                                                                 sk: &Sk)
+        -> Result<Response<S, D, P>>
+        where   S: Serializable,
+                D: Ord + Datable + ConstantSize + Serializable,
+                P: Datable + Serializable,
+                H: Hash<D>,
+                Seed: Datable + ConstantSize,
+                Pk: Datable + ConstantSize,
+                Sk: Datable + ConstantSize,
+                Sig: Datable + ConstantSize + Serializable,
+                Sg: Sign<Seed, Pk, Sk, Sig>
+    {
+        self.handle_session_with(request, hasher, |manager, session| {
+            if manager.validate(session).is_ok() {
+                manager.renew_signed(session, signer, sk)
+            } else {
+                let session = manager.issue(&session.permission, &session.payload)?;
+                manager.sign(&session, signer, sk)
+            }
+        })
+    }
+
+    /// Handles a session `Request`, getting the `Response` session from the `Request` session with
+    /// a given closure.
+    fn handle_session_with<D, P, H, F>(&self, request: &Request<S, D, P>, hasher: &mut H, get_session: F)
+        -> Result<Response<S, D, P>>
+        where   S: Serializable,
+                D: Ord + Datable + ConstantSize + Serializable,
+                P: Datable + Serializable,
+                H: Hash<D>,
+                F: FnOnce(&Self, &Session<S>) -> Result<Session<S>>
+    {
+        request.check()?;
+
+        let message = &request.message;
+
+        if message.method != Method::Session {
+            return Err(String::from("invalid method"));
+        }
+
+        let session = get_session(self, &message.session)?;
+
+        let message = Message::new()
+                        .meta(&message.meta)?
+                        .session(&session)?
+                        .method(&Method::Session)?
+                        .resource(&Resource::Session)?
+                        .payload(&message.payload)?
+                        .finalize(hasher)?;
+
+        Response::new(&message)
+    }
+}
+
+impl<S> Default for SessionManager<S>
+    where   S: Datable
+{
+    fn default() -> Self {
+        SessionManager::new()
+    }
+}
+
+impl<S> Sizable for SessionManager<S>
+    where   S: Datable
+{
+    fn size(&self) -> u64 {
+        let mut size = 0;
+
+        for (id, session) in lock(&self.sessions).iter() {
+            size += id.size();
+            size += session.size();
+        }
+
+        size
+    }
+}
+
+impl<S> Checkable for SessionManager<S>
+    where   S: Datable
+{
+    fn check(&self) -> Result<()> {
+        for (id, session) in lock(&self.sessions).iter() {
+            session.check()?;
+
+            if session.id != *id {
+                return Err(String::from("invalid id"));
+            }
+        }
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,190 @@
+//! # Batch
+//!
+//! `batch` is the module providing the types used to stage store write operations and commit
+//! them atomically.
+
+use base::Result;
+use base::Checkable;
+use base::Sizable;
+use base::Serializable;
+use base::Datable;
+
+/// Type representing a staged store write operation.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash, Serialize, Deserialize)]
+pub enum BatchOp {
+    /// Creates an item. The item should not exist in the store before the operation.
+    Create(Vec<u8>, Vec<u8>),
+    /// Updates an item. The item should already exist in the store before the operation.
+    Update(Vec<u8>, Vec<u8>),
+    /// Creates an item if absent, updates it if present.
+    Upsert(Vec<u8>, Vec<u8>),
+    /// Deletes an item. The item should already exist in the store before the operation.
+    Delete(Vec<u8>),
+}
+
+impl BatchOp {
+    /// Returns the key of the item the `BatchOp` operates on.
+    pub fn key(&self) -> &[u8] {
+        match self {
+            BatchOp::Create(key, _) => key,
+            BatchOp::Update(key, _) => key,
+            BatchOp::Upsert(key, _) => key,
+            BatchOp::Delete(key) => key,
+        }
+    }
+
+    /// Returns the value written by the `BatchOp`, if any.
+    pub fn value(&self) -> Option<&[u8]> {
+        match self {
+            BatchOp::Create(_, value) => Some(value),
+            BatchOp::Update(_, value) => Some(value),
+            BatchOp::Upsert(_, value) => Some(value),
+            BatchOp::Delete(_) => None,
+        }
+    }
+
+    /// Applies the `BatchOp` to the current state of its item, returning the item's new state.
+    pub fn apply(&self, current: Option<&[u8]>) -> Result<Option<Vec<u8>>> {
+        match self {
+            BatchOp::Create(_, value) => {
+                if current.is_some() {
+                    return Err(String::from("already found"));
+                }
+
+                Ok(Some(value.to_owned()))
+            },
+            BatchOp::Update(_, value) => {
+                if current.is_none() {
+                    return Err(String::from("not found"));
+                }
+
+                Ok(Some(value.to_owned()))
+            },
+            BatchOp::Upsert(_, value) => {
+                Ok(Some(value.to_owned()))
+            },
+            BatchOp::Delete(_) => {
+                if current.is_none() {
+                    return Err(String::from("not found"));
+                }
+
+                Ok(None)
+            },
+        }
+    }
+}
+
+impl Default for BatchOp {
+    fn default() -> BatchOp {
+        BatchOp::Upsert(Vec::new(), Vec::new())
+    }
+}
+
+impl Sizable for BatchOp {
+    fn size(&self) -> u64 {
+        let value_size = match self.value() {
+            Some(value) => value.to_vec().size(),
+            None => 0,
+        };
+
+        0u8.size() +
+            self.key().to_vec().size() +
+            value_size
+    }
+}
+
+impl Checkable for BatchOp {}
+
+impl Serializable for BatchOp {}
+
+impl Datable for BatchOp {}
+
+/// Type used to stage store write operations, later committed atomically by a `Store`.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Default, Hash, Serialize, Deserialize)]
+pub struct Batch {
+    /// Batch operations length.
+    pub ops_len: u64,
+    /// Batch operations, in the order they are applied.
+    pub ops: Vec<BatchOp>,
+}
+
+impl Batch {
+    /// Creates a new `Batch`.
+    pub fn new() -> Batch {
+        Batch::default()
+    }
+
+    /// Returns if the `Batch` has no staged operations.
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Stages an operation in the `Batch`.
+    pub fn push(&mut self, op: BatchOp) -> Result<()> {
+        op.check()?;
+
+        self.ops.push(op);
+        self.ops_len = self.ops.len() as u64;
+
+        Ok(())
+    }
+
+    /// Stages the creation of an item.
+    pub fn create(&mut self, key: &[u8], value: &[u8]) -> Result<()> {
+        self.push(BatchOp::Create(key.to_owned(), value.to_owned()))
+    }
+
+    /// Stages the update of an item.
+    pub fn update(&mut self, key: &[u8], value: &[u8]) -> Result<()> {
+        self.push(BatchOp::Update(key.to_owned(), value.to_owned()))
+    }
+
+    /// Stages the upsert of an item.
+    pub fn upsert(&mut self, key: &[u8], value: &[u8]) -> Result<()> {
+        self.push(BatchOp::Upsert(key.to_owned(), value.to_owned()))
+    }
+
+    /// Stages the deletion of an item.
+    pub fn delete(&mut self, key: &[u8]) -> Result<()> {
+        self.push(BatchOp::Delete(key.to_owned()))
+    }
+
+    /// Returns the state of an item after the staged operations, if any of them writes it. Items
+    /// deleted by the `Batch` have a `None` state.
+    pub fn get(&self, key: &[u8]) -> Option<Option<&[u8]>> {
+        self.ops.iter()
+            .rev()
+            .find(|op| op.key() == key)
+            .map(|op| op.value())
+    }
+
+    /// Discards all the staged operations.
+    pub fn clear(&mut self) {
+        self.ops.clear();
+        self.ops_len = 0;
+    }
+}
+
+impl Sizable for Batch {
+    fn size(&self) -> u64 {
+        self.ops_len.size() +
+            self.ops.size()
+    }
+}
+
+impl Checkable for Batch {
+    fn check(&self) -> Result<()> {
+        self.ops_len.check()?;
+        self.ops.check()?;
+
+        if self.ops.len() != self.ops_len as usize {
+            return Err(String::from("invalid operations length"));
+        }
+
+        Ok(())
+    }
+}
+
+impl Serializable for Batch {}
+
+impl Datable for Batch {}
\ No newline at end of file
@@ -0,0 +1,112 @@
+//! # Index
+//!
+//! `index` is the module providing the types used to maintain secondary indexes over `Storable` items.
+//! Index entries are kept in their own store namespace, under the `INDEX_CODE` prefix, so that they
+//! are never counted or listed together with the items they refer to.
+//!
+//! An index entry key is made of the index prefix, the store prefix of the indexed type, the index
+//! name, the index key and the store key of the indexed item. The index name and key are encoded as
+//! store key binary strings, so that the entries of an index are ordered as their index keys, and an
+//! index key is never the prefix of another. The entry value is the store key of the indexed item.
+
+use base::Result;
+use base::Checkable;
+use base::Sizable;
+use base::Serializable;
+use base::Datable;
+use io::store::prefix::encode_prefix;
+use io::store::key::{StoreKey, encode_bytes};
+
+/// Code of the secondary index entries.
+pub const INDEX_CODE: u64 = 255;
+
+/// Type representing the key of an item in a secondary index.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Default, Hash, Serialize, Deserialize)]
+pub struct IndexKey {
+    /// Name of the index.
+    pub index: String,
+    /// Key of the item in the index.
+    pub key: Vec<u8>,
+}
+
+impl IndexKey {
+    /// Creates a new `IndexKey`.
+    pub fn new(index: &str, key: &[u8]) -> Result<IndexKey> {
+        let index_key = IndexKey {
+            index: index.to_owned(),
+            key: key.to_owned(),
+        };
+
+        index_key.check()?;
+
+        Ok(index_key)
+    }
+
+    /// Creates a new `IndexKey` from a `StoreKey`, so that the index is ordered as the keys.
+    pub fn from_key<K: StoreKey>(index: &str, key: &K) -> Result<IndexKey> {
+        IndexKey::new(index, &key.to_store_key()?)
+    }
+
+    /// Creates a new `IndexKey` from an unsigned integer. The key is encoded as big-endian, so that
+    /// the index is ordered as the integers.
+    pub fn from_u64(index: &str, value: u64) -> Result<IndexKey> {
+        IndexKey::from_key(index, &value)
+    }
+
+    /// Returns the store key of the index entry of an item, given its store prefix and store key.
+    pub fn entry_key(&self, prefix: &[u8], store_key: &[u8]) -> Result<Vec<u8>> {
+        self.check()?;
+
+        let mut entry_key = index_key_prefix(prefix, &self.index, &self.key)?;
+        entry_key.extend_from_slice(store_key);
+
+        Ok(entry_key)
+    }
+}
+
+impl Sizable for IndexKey {
+    fn size(&self) -> u64 {
+        self.index.size() +
+            self.key.size()
+    }
+}
+
+impl Checkable for IndexKey {
+    fn check(&self) -> Result<()> {
+        if self.index.is_empty() {
+            return Err(String::from("invalid index"));
+        }
+
+        Ok(())
+    }
+}
+
+impl Serializable for IndexKey {}
+
+impl Datable for IndexKey {}
+
+/// Returns the store prefix of the secondary index entries.
+pub fn index_store_prefix() -> Vec<u8> {
+    encode_prefix(INDEX_CODE)
+}
+
+/// Returns the prefix shared by the entries of an index of a type, given the type store prefix.
+pub fn index_prefix(prefix: &[u8], index: &str) -> Result<Vec<u8>> {
+    if index.is_empty() {
+        return Err(String::from("invalid index"));
+    }
+
+    let mut index_prefix = index_store_prefix();
+    index_prefix.extend_from_slice(prefix);
+    encode_bytes(index.as_bytes(), &mut index_prefix);
+
+    Ok(index_prefix)
+}
+
+/// Returns the prefix shared by the entries of an index of a type with a given index key.
+pub fn index_key_prefix(prefix: &[u8], index: &str, key: &[u8]) -> Result<Vec<u8>> {
+    let mut index_key_prefix = index_prefix(prefix, index)?;
+    encode_bytes(key, &mut index_key_prefix);
+
+    Ok(index_key_prefix)
+}
\ No newline at end of file
@@ -0,0 +1,266 @@
+//! # Iter
+//!
+//! `iter` is the module providing the iterators used to walk the store items lazily. Items are
+//! fetched from the store in pages, and every page resumes from the last key seen, so walking
+//! a range has a linear cost regardless of its length.
+
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+
+use base::Result;
+use base::Checkable;
+use base::Sizable;
+use base::Serializable;
+use base::Datable;
+use io::Session;
+use io::store::{Store, Storable, StoreKey};
+
+/// Default number of items fetched from the store at every page.
+pub const DEFAULT_PAGE_SIZE: u64 = 256;
+
+/// Type representing the direction of a store scan.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Default, Hash, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum Direction {
+    /// Scan by ascending keys.
+    #[default]
+    Forward,
+    /// Scan by descending keys.
+    Reverse,
+}
+
+impl Sizable for Direction {
+    fn size(&self) -> u64 {
+        0u8.size()
+    }
+}
+
+impl Checkable for Direction {}
+
+impl Serializable for Direction {}
+
+impl Datable for Direction {}
+
+/// Returns the range `(from, to)` of the keys starting with a given prefix.
+pub fn prefix_range(prefix: &[u8]) -> (Option<Vec<u8>>, Option<Vec<u8>>) {
+    let from = if prefix.is_empty() {
+        None
+    } else {
+        Some(prefix.to_vec())
+    };
+
+    let mut to = prefix.to_vec();
+
+    while let Some(last) = to.pop() {
+        if last < 255 {
+            to.push(last + 1);
+            return (from, Some(to));
+        }
+    }
+
+    (from, None)
+}
+
+/// Iterator over the store items (key and value) in a range, starting from the `from` key until,
+/// not included, the `to` key.
+pub struct StoreIter<'a, St, S>
+    where   St: 'a + Store<S>,
+            S: Datable + Serializable
+{
+    store: &'a mut St,
+    session: Session<S>,
+    from: Option<Vec<u8>>,
+    to: Option<Vec<u8>>,
+    direction: Direction,
+    page_size: u64,
+    cursor: Option<Vec<u8>>,
+    position: Option<Vec<u8>>,
+    page: VecDeque<(Vec<u8>, Vec<u8>)>,
+    done: bool,
+}
+
+impl<'a, St, S> StoreIter<'a, St, S>
+    where   St: 'a + Store<S>,
+            S: Datable + Serializable
+{
+    /// Creates a new `StoreIter`.
+    pub fn new(store: &'a mut St,
+               session: &Session<S>,
+               from: Option<Vec<u8>>,
+               to: Option<Vec<u8>>,
+               direction: Direction)
+        -> Result<Self>
+    {
+        session.check()?;
+        from.check()?;
+        to.check()?;
+
+        if let Some(ref from) = from {
+            if let Some(ref to) = to {
+                if from >= to {
+                    return Err(String::from("invalid range"));
+                }
+            }
+        }
+
+        let iter = StoreIter {
+            store,
+            session: session.to_owned(),
+            from,
+            to,
+            direction,
+            page_size: DEFAULT_PAGE_SIZE,
+            cursor: None,
+            position: None,
+            page: VecDeque::new(),
+            done: false,
+        };
+
+        Ok(iter)
+    }
+
+    /// Creates a new `StoreIter` over the items starting with a given prefix.
+    pub fn new_prefix(store: &'a mut St,
+                      session: &Session<S>,
+                      prefix: &[u8],
+                      direction: Direction)
+        -> Result<Self>
+    {
+        let (from, to) = prefix_range(prefix);
+
+        StoreIter::new(store, session, from, to, direction)
+    }
+
+    /// Sets the number of items fetched from the store at every page.
+    pub fn page_size(mut self, page_size: u64) -> Result<Self> {
+        if page_size == 0 {
+            return Err(String::from("invalid page size"));
+        }
+
+        self.page_size = page_size;
+
+        Ok(self)
+    }
+
+    /// Resumes the iteration after a given key, as returned by `StoreIter::position`.
+    pub fn resume(mut self, cursor: &[u8]) -> Result<Self> {
+        self.cursor = Some(cursor.to_owned());
+        self.position = Some(cursor.to_owned());
+        self.page.clear();
+        self.done = false;
+
+        Ok(self)
+    }
+
+    /// Returns the key of the last item returned by the `StoreIter`, if any. It can be used to resume
+    /// the iteration later.
+    pub fn position(&self) -> Option<Vec<u8>> {
+        self.position.clone()
+    }
+
+    /// Fetches the next page of items from the store.
+    fn fetch(&mut self) -> Result<()> {
+        let items = self.store.scan(&self.session,
+                                    self.from.clone(),
+                                    self.to.clone(),
+                                    self.cursor.clone(),
+                                    self.direction,
+                                    self.page_size)?;
+
+        if (items.len() as u64) < self.page_size {
+            self.done = true;
+        }
+
+        if let Some((key, _)) = items.last() {
+            self.cursor = Some(key.to_owned());
+        }
+
+        self.page.extend(items);
+
+        Ok(())
+    }
+}
+
+impl<'a, St, S> Iterator for StoreIter<'a, St, S>
+    where   St: 'a + Store<S>,
+            S: Datable + Serializable
+{
+    type Item = Result<(Vec<u8>, Vec<u8>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.page.is_empty() && !self.done {
+            if let Err(e) = self.fetch() {
+                self.done = true;
+                return Some(Err(e));
+            }
+        }
+
+        self.page.pop_front().map(|(key, value)| {
+            self.position = Some(key.clone());
+            Ok((key, value))
+        })
+    }
+}
+
+/// Iterator over the `Storable` items in a range, deserialized lazily from their store values.
+pub struct StorableIter<'a, St, S, K, V, T>
+    where   St: 'a + Store<S>,
+            S: Datable + Serializable,
+            K: Ord + Datable + Serializable + StoreKey,
+            V: Datable + Serializable,
+            T: Storable<St, S, K, V>
+{
+    inner: StoreIter<'a, St, S>,
+    _marker: PhantomData<(K, V, T)>,
+}
+
+impl<'a, St, S, K, V, T> StorableIter<'a, St, S, K, V, T>
+    where   St: 'a + Store<S>,
+            S: Datable + Serializable,
+            K: Ord + Datable + Serializable + StoreKey,
+            V: Datable + Serializable,
+            T: Storable<St, S, K, V>
+{
+    /// Creates a new `StorableIter` from a `StoreIter`.
+    pub fn new(inner: StoreIter<'a, St, S>) -> Self {
+        StorableIter {
+            inner,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets the number of items fetched from the store at every page.
+    pub fn page_size(mut self, page_size: u64) -> Result<Self> {
+        self.inner = self.inner.page_size(page_size)?;
+
+        Ok(self)
+    }
+
+    /// Resumes the iteration after a given store key, as returned by `StorableIter::position`.
+    pub fn resume(mut self, cursor: &[u8]) -> Result<Self> {
+        self.inner = self.inner.resume(cursor)?;
+
+        Ok(self)
+    }
+
+    /// Returns the store key of the last item returned by the `StorableIter`, if any.
+    pub fn position(&self) -> Option<Vec<u8>> {
+        self.inner.position()
+    }
+}
+
+impl<'a, St, S, K, V, T> Iterator for StorableIter<'a, St, S, K, V, T>
+    where   St: 'a + Store<S>,
+            S: Datable + Serializable,
+            K: Ord + Datable + Serializable + StoreKey,
+            V: Datable + Serializable,
+            T: Storable<St, S, K, V>
+{
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|res| {
+            res.and_then(|(_, value)| T::from_store_value(&value))
+        })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,260 @@
+//! # Key
+//!
+//! `key` is the module providing the trait implemented by types that can be used as store keys.
+//!
+//! Store keys are encoded so that the byte order of the encoded keys is the same as the order of the
+//! keys (memcomparable encoding), and range queries on the store return the items expected by the
+//! `Ord` implementation of the key type. Unsigned integers are encoded as big-endian, signed integers
+//! as big-endian with the sign bit flipped, binary strings are escaped and terminated, so that no
+//! encoded string is the prefix of another, and tuples are encoded as the concatenation of their
+//! encoded fields.
+
+use base::Result;
+use util::Timestamp;
+
+/// Trait implemented by types that can be encoded as order-preserving store keys.
+pub trait StoreKey
+    where   Self: Sized
+{
+    /// Appends the implementor store key encoding to a buffer.
+    fn encode_store_key(&self, buf: &mut Vec<u8>) -> Result<()>;
+
+    /// Decodes an instance of the implementor from the start of a buffer, returning it together
+    /// with the number of bytes read.
+    fn decode_store_key(buf: &[u8]) -> Result<(Self, usize)>;
+
+    /// Encodes the implementor into a store key.
+    fn to_store_key(&self) -> Result<Vec<u8>> {
+        let mut buf = Vec::new();
+        self.encode_store_key(&mut buf)?;
+
+        Ok(buf)
+    }
+
+    /// Decodes an instance of the implementor from a store key.
+    fn from_store_key(key: &[u8]) -> Result<Self> {
+        let (value, len) = Self::decode_store_key(key)?;
+
+        if len != key.len() {
+            return Err(String::from("invalid length"));
+        }
+
+        Ok(value)
+    }
+}
+
+/// Escape byte following a zero byte of an encoded binary string.
+const ESCAPE: u8 = 255;
+
+/// Byte terminating an encoded binary string, following a zero byte.
+const TERMINATOR: u8 = 1;
+
+/// Appends the store key encoding of a binary string to a buffer.
+pub fn encode_bytes(value: &[u8], buf: &mut Vec<u8>) {
+    buf.reserve(value.len() + 2);
+
+    for byte in value {
+        buf.push(*byte);
+
+        if *byte == 0 {
+            buf.push(ESCAPE);
+        }
+    }
+
+    buf.push(0);
+    buf.push(TERMINATOR);
+}
+
+/// Decodes a binary string from the start of a buffer, returning it together with the number of
+/// bytes read.
+pub fn decode_bytes(buf: &[u8]) -> Result<(Vec<u8>, usize)> {
+    let mut value = Vec::new();
+
+    let mut idx = 0;
+
+    while idx < buf.len() {
+        let byte = buf[idx];
+
+        if byte != 0 {
+            value.push(byte);
+            idx += 1;
+            continue;
+        }
+
+        if idx + 1 >= buf.len() {
+            break;
+        }
+
+        match buf[idx + 1] {
+            ESCAPE => {
+                value.push(0);
+                idx += 2;
+            },
+            TERMINATOR => {
+                return Ok((value, idx + 2));
+            },
+            _ => {
+                return Err(String::from("invalid escape"));
+            },
+        }
+    }
+
+    Err(String::from("invalid length"))
+}
+
+/// Returns the first `len` bytes of a buffer.
+fn take(buf: &[u8], len: usize) -> Result<&[u8]> {
+    if buf.len() < len {
+        return Err(String::from("invalid length"));
+    }
+
+    Ok(&buf[..len])
+}
+
+macro_rules! impl_store_key_unsigned {
+    ($t:ty, $len:expr) => {
+        impl StoreKey for $t {
+            fn encode_store_key(&self, buf: &mut Vec<u8>) -> Result<()> {
+                buf.extend_from_slice(&self.to_be_bytes()[..]);
+
+                Ok(())
+            }
+
+            fn decode_store_key(buf: &[u8]) -> Result<($t, usize)> {
+                let mut bytes = [0u8; $len];
+                bytes.copy_from_slice(take(buf, $len)?);
+
+                Ok((<$t>::from_be_bytes(bytes), $len))
+            }
+        }
+    }
+}
+
+macro_rules! impl_store_key_signed {
+    ($t:ty, $ut:ty, $len:expr) => {
+        impl StoreKey for $t {
+            fn encode_store_key(&self, buf: &mut Vec<u8>) -> Result<()> {
+                let flipped = (*self as $ut) ^ (1 << ($len * 8 - 1));
+
+                flipped.encode_store_key(buf)
+            }
+
+            fn decode_store_key(buf: &[u8]) -> Result<($t, usize)> {
+                let (flipped, len) = <$ut>::decode_store_key(buf)?;
+
+                Ok(((flipped ^ (1 << ($len * 8 - 1))) as $t, len))
+            }
+        }
+    }
+}
+
+impl_store_key_unsigned!(u8, 1);
+impl_store_key_unsigned!(u16, 2);
+impl_store_key_unsigned!(u32, 4);
+impl_store_key_unsigned!(u64, 8);
+impl_store_key_unsigned!(u128, 16);
+
+impl_store_key_signed!(i8, u8, 1);
+impl_store_key_signed!(i16, u16, 2);
+impl_store_key_signed!(i32, u32, 4);
+impl_store_key_signed!(i64, u64, 8);
+impl_store_key_signed!(i128, u128, 16);
+
+impl StoreKey for () {
+    fn encode_store_key(&self, _buf: &mut Vec<u8>) -> Result<()> {
+        Ok(())
+    }
+
+    fn decode_store_key(_buf: &[u8]) -> Result<((), usize)> {
+        Ok(((), 0))
+    }
+}
+
+impl StoreKey for bool {
+    fn encode_store_key(&self, buf: &mut Vec<u8>) -> Result<()> {
+        (*self as u8).encode_store_key(buf)
+    }
+
+    fn decode_store_key(buf: &[u8]) -> Result<(bool, usize)> {
+        match u8::decode_store_key(buf)? {
+            (0, len) => Ok((false, len)),
+            (1, len) => Ok((true, len)),
+            _ => Err(String::from("invalid bool")),
+        }
+    }
+}
+
+impl StoreKey for Vec<u8> {
+    fn encode_store_key(&self, buf: &mut Vec<u8>) -> Result<()> {
+        encode_bytes(self, buf);
+
+        Ok(())
+    }
+
+    fn decode_store_key(buf: &[u8]) -> Result<(Vec<u8>, usize)> {
+        decode_bytes(buf)
+    }
+}
+
+impl StoreKey for String {
+    fn encode_store_key(&self, buf: &mut Vec<u8>) -> Result<()> {
+        encode_bytes(self.as_bytes(), buf);
+
+        Ok(())
+    }
+
+    fn decode_store_key(buf: &[u8]) -> Result<(String, usize)> {
+        let (bytes, len) = decode_bytes(buf)?;
+
+        let value = String::from_utf8(bytes)
+            .map_err(|e| format!("{}", e))?;
+
+        Ok((value, len))
+    }
+}
+
+impl StoreKey for Timestamp {
+    fn encode_store_key(&self, buf: &mut Vec<u8>) -> Result<()> {
+        self.as_u64().encode_store_key(buf)
+    }
+
+    fn decode_store_key(buf: &[u8]) -> Result<(Timestamp, usize)> {
+        let (value, len) = u64::decode_store_key(buf)?;
+
+        Ok((Timestamp::from_u64(value), len))
+    }
+}
+
+macro_rules! impl_store_key_tuple {
+    ($($name:ident),+) => {
+        impl<$($name),+> StoreKey for ($($name,)+)
+            where   $($name: StoreKey),+
+        {
+            #[allow(non_snake_case)]
+            fn encode_store_key(&self, buf: &mut Vec<u8>) -> Result<()> {
+                let ($(ref $name,)+) = *self;
+
+                $($name.encode_store_key(buf)?;)+
+
+                Ok(())
+            }
+
+            #[allow(non_snake_case)]
+            fn decode_store_key(buf: &[u8]) -> Result<(($($name,)+), usize)> {
+                let mut idx = 0;
+
+                $(
+                    let ($name, len) = $name::decode_store_key(&buf[idx..])?;
+                    idx += len;
+                )+
+
+                Ok((($($name,)+), idx))
+            }
+        }
+    }
+}
+
+impl_store_key_tuple!(A);
+impl_store_key_tuple!(A, B);
+impl_store_key_tuple!(A, B, C);
+impl_store_key_tuple!(A, B, C, D);
\ No newline at end of file
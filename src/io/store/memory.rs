@@ -0,0 +1,544 @@
+//! # Memory
+//!
+//! `memory` is the module providing `MemoryStore`, a thread-safe in-memory implementation of
+//! the `Store` trait. Items are kept ordered by key, so range and prefix operations have the
+//! same semantics expected by the `Storable` methods.
+//!
+//! The sessions with an `Acl` are checked against the store method on `Resource::None`, as the
+//! store items are untyped: e.g. `get:*` grants the reads of single items, and `upsert:*` the
+//! upserts of a batch.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::collections::BTreeMap;
+use std::collections::btree_map::Range;
+use std::ops::Bound;
+
+use base::Result;
+use base::Checkable;
+use base::Sizable;
+use base::Datable;
+use base::Serializable;
+use util::TimestampDiff;
+use io::Permission;
+use io::Session;
+use io::SessionManager;
+use io::network::{Method, Resource};
+use io::Store;
+use io::store::{Batch, BatchOp};
+use io::store::{StoreEvent, EventSender, Watchers};
+use io::store::Direction;
+
+/// Default duration of the `MemoryStore` sessions, in seconds.
+pub const DEFAULT_SESSION_DURATION: u64 = 3600;
+
+/// Type used to represent an in-memory store.
+#[derive(Clone, Debug)]
+pub struct MemoryStore<S>
+    where   S: Datable
+{
+    sessions: SessionManager<S>,
+    items: Arc<Mutex<BTreeMap<Vec<u8>, Vec<u8>>>>,
+    watchers: Arc<Mutex<Watchers>>,
+}
+
+impl<S> MemoryStore<S>
+    where   S: Datable
+{
+    /// Creates a new `MemoryStore`.
+    pub fn new() -> Self {
+        MemoryStore {
+            sessions: SessionManager::new()
+                        .duration(&TimestampDiff::from_secs(DEFAULT_SESSION_DURATION))
+                        .unwrap(),
+            items: Arc::new(Mutex::new(BTreeMap::new())),
+            watchers: Arc::new(Mutex::new(Watchers::new())),
+        }
+    }
+
+    /// Sets the duration of the sessions issued by the `MemoryStore`.
+    pub fn session_duration(mut self, duration: &TimestampDiff) -> Result<Self> {
+        self.sessions = self.sessions.duration(duration)?;
+
+        Ok(self)
+    }
+
+    /// Sets the `SessionManager` issuing and validating the sessions of the `MemoryStore`. Stores
+    /// sharing a `SessionManager` accept the same sessions.
+    pub fn session_manager(mut self, manager: &SessionManager<S>) -> Self {
+        self.sessions = manager.to_owned();
+        self
+    }
+
+    /// Returns the `SessionManager` of the `MemoryStore`, which can be used to renew and revoke
+    /// its sessions.
+    pub fn sessions(&self) -> SessionManager<S> {
+        self.sessions.to_owned()
+    }
+
+    /// Returns the number of live sessions.
+    pub fn sessions_len(&self) -> u64 {
+        self.sessions.len()
+    }
+
+    /// Returns the number of items.
+    pub fn items_len(&self) -> u64 {
+        lock(&self.items).len() as u64
+    }
+
+    /// Returns the number of subscriptions to the items changes.
+    pub fn watchers_len(&self) -> u64 {
+        lock(&self.watchers).len()
+    }
+
+    /// Removes the expired sessions.
+    pub fn clear_expired_sessions(&mut self) -> Result<()> {
+        self.sessions.clear_expired()?;
+
+        Ok(())
+    }
+
+    /// Removes all the sessions and items.
+    pub fn clear(&mut self) {
+        self.sessions.revoke_all();
+        lock(&self.items).clear();
+    }
+
+    /// Checks a `Session` used in a read operation with a given `Method`.
+    fn check_read_session(&self, session: &Session<S>, method: &Method) -> Result<()> {
+        self.sessions.validate(session)?;
+
+        if !session.acl.is_empty() {
+            session.acl.check_access(method, &Resource::None)?;
+        } else if !session.permission.can_read() {
+            return Err(String::from("invalid permission"));
+        }
+
+        Ok(())
+    }
+
+    /// Checks a `Session` used to commit a `Batch`, against the method of each operation.
+    pub(crate) fn check_write_session(&self, session: &Session<S>, batch: &Batch) -> Result<()> {
+        self.sessions.validate(session)?;
+
+        if !session.acl.is_empty() {
+            for op in batch.ops.iter() {
+                session.acl.check_access(&op_method(op), &Resource::None)?;
+            }
+        } else if !session.permission.can_write() {
+            return Err(String::from("invalid permission"));
+        }
+
+        Ok(())
+    }
+
+    /// Commits a `Batch` holding the items lock from the staging of its operations to their
+    /// application, so that concurrent batches are serialized. The `write` closure receives the final
+    /// state of the items written by the batch before it is applied: if it fails, the batch is not
+    /// applied.
+    pub(crate) fn commit_with<F>(&self, batch: &Batch, write: F) -> Result<()>
+        where   F: FnOnce(&BTreeMap<Vec<u8>, Option<Vec<u8>>>) -> Result<()>
+    {
+        let events = {
+            let items = &mut *lock(&self.items);
+
+            let staged = stage_locked(items, batch)?;
+
+            write(&staged)?;
+
+            apply_locked(items, staged)
+        };
+
+        if !events.is_empty() {
+            lock(&self.watchers).notify(&events);
+        }
+
+        Ok(())
+    }
+
+    /// Returns a copy of all the items.
+    pub(crate) fn items(&self) -> Vec<(Vec<u8>, Vec<u8>)> {
+        lock(&self.items)
+            .iter()
+            .map(|(key, value)| (key.to_owned(), value.to_owned()))
+            .collect()
+    }
+}
+
+impl<S> Default for MemoryStore<S>
+    where   S: Datable
+{
+    fn default() -> Self {
+        MemoryStore::new()
+    }
+}
+
+impl<S> Sizable for MemoryStore<S>
+    where   S: Datable
+{
+    fn size(&self) -> u64 {
+        let mut size = self.sessions.size();
+
+        for (key, value) in lock(&self.items).iter() {
+            size += key.size();
+            size += value.size();
+        }
+
+        size
+    }
+}
+
+impl<S> Checkable for MemoryStore<S>
+    where   S: Datable
+{
+    fn check(&self) -> Result<()> {
+        self.sessions.check()
+    }
+}
+
+impl<S> Store<S> for MemoryStore<S>
+    where   S: Datable + Serializable
+{
+    fn session(&mut self, permission: &Permission) -> Result<Session<S>> {
+        self.sessions.issue(permission, &S::default())
+    }
+
+    fn count(&mut self,
+             session: &Session<S>,
+             from: Option<Vec<u8>>,
+             to: Option<Vec<u8>>)
+        -> Result<u64>
+    {
+        self.check_read_session(session, &Method::Count)?;
+
+        let items = &*lock(&self.items);
+
+        let count = range(items, from, to)?.count();
+
+        Ok(count as u64)
+    }
+
+    fn count_prefix(&mut self,
+                    session: &Session<S>,
+                    prefix: &[u8])
+        -> Result<u64>
+    {
+        self.check_read_session(session, &Method::Count)?;
+
+        let items = &*lock(&self.items);
+
+        let count = range_prefix(items, prefix).count();
+
+        Ok(count as u64)
+    }
+
+    fn list(&mut self,
+            session: &Session<S>,
+            from: Option<Vec<u8>>,
+            to: Option<Vec<u8>>,
+            count: Option<u64>,
+            skip: u64)
+        -> Result<Vec<Vec<u8>>>
+    {
+        self.check_read_session(session, &Method::List)?;
+        check_pagination(count)?;
+
+        let items = &*lock(&self.items);
+
+        let values = paginate(range(items, from, to)?, count, skip);
+
+        Ok(values)
+    }
+
+    fn list_prefix(&mut self,
+                   session: &Session<S>,
+                   prefix: &[u8],
+                   count: Option<u64>,
+                   skip: u64)
+        -> Result<Vec<Vec<u8>>>
+    {
+        self.check_read_session(session, &Method::List)?;
+        check_pagination(count)?;
+
+        let items = &*lock(&self.items);
+
+        let values = paginate(range_prefix(items, prefix), count, skip);
+
+        Ok(values)
+    }
+
+    fn scan(&mut self,
+            session: &Session<S>,
+            from: Option<Vec<u8>>,
+            to: Option<Vec<u8>>,
+            cursor: Option<Vec<u8>>,
+            direction: Direction,
+            count: u64)
+        -> Result<Vec<(Vec<u8>, Vec<u8>)>>
+    {
+        self.check_read_session(session, &Method::List)?;
+        check_pagination(Some(count))?;
+
+        if let Some(ref from) = from {
+            if let Some(ref to) = to {
+                if from >= to {
+                    return Err(String::from("invalid range"));
+                }
+            }
+        }
+
+        let items = &*lock(&self.items);
+
+        let (from, to) = match cursor {
+            Some(cursor) => {
+                match direction {
+                    Direction::Forward => {
+                        if let Some(ref to) = to {
+                            if &cursor >= to {
+                                return Ok(Vec::new());
+                            }
+                        }
+
+                        let lower = match from {
+                            Some(from) if from > cursor => Bound::Included(from),
+                            _ => Bound::Excluded(cursor),
+                        };
+
+                        (lower, to.map_or(Bound::Unbounded, Bound::Excluded))
+                    },
+                    Direction::Reverse => {
+                        if let Some(ref from) = from {
+                            if &cursor <= from {
+                                return Ok(Vec::new());
+                            }
+                        }
+
+                        let upper = match to {
+                            Some(to) if to < cursor => Bound::Excluded(to),
+                            _ => Bound::Excluded(cursor),
+                        };
+
+                        (from.map_or(Bound::Unbounded, Bound::Included), upper)
+                    },
+                }
+            },
+            None => {
+                (from.map_or(Bound::Unbounded, Bound::Included), to.map_or(Bound::Unbounded, Bound::Excluded))
+            },
+        };
+
+        let range = items.range((from, to));
+
+        let items = match direction {
+            Direction::Forward => {
+                range.take(count as usize)
+                    .map(|(key, value)| (key.to_owned(), value.to_owned()))
+                    .collect()
+            },
+            Direction::Reverse => {
+                range.rev()
+                    .take(count as usize)
+                    .map(|(key, value)| (key.to_owned(), value.to_owned()))
+                    .collect()
+            },
+        };
+
+        Ok(items)
+    }
+
+    fn lookup(&mut self, session: &Session<S>, key: &[u8]) -> Result<bool> {
+        self.check_read_session(session, &Method::Lookup)?;
+
+        let found = lock(&self.items).contains_key(key);
+
+        Ok(found)
+    }
+
+    fn get(&mut self, session: &Session<S>, key: &[u8]) -> Result<Vec<u8>> {
+        self.check_read_session(session, &Method::Get)?;
+
+        match lock(&self.items).get(key) {
+            Some(value) => Ok(value.to_owned()),
+            None => Err(String::from("not found")),
+        }
+    }
+
+    fn create(&mut self, session: &Session<S>, key: &[u8], value: &[u8]) -> Result<()> {
+        let mut batch = Batch::new();
+        batch.create(key, value)?;
+
+        self.commit(session, &batch)
+    }
+
+    fn update(&mut self, session: &Session<S>, key: &[u8], value: &[u8]) -> Result<()> {
+        let mut batch = Batch::new();
+        batch.update(key, value)?;
+
+        self.commit(session, &batch)
+    }
+
+    fn upsert(&mut self, session: &Session<S>, key: &[u8], value: &[u8]) -> Result<()> {
+        let mut batch = Batch::new();
+        batch.upsert(key, value)?;
+
+        self.commit(session, &batch)
+    }
+
+    fn delete(&mut self, session: &Session<S>, key: &[u8]) -> Result<()> {
+        let mut batch = Batch::new();
+        batch.delete(key)?;
+
+        self.commit(session, &batch)
+    }
+
+    fn commit(&mut self, session: &Session<S>, batch: &Batch) -> Result<()> {
+        self.check_write_session(session, batch)?;
+        batch.check()?;
+
+        self.commit_with(batch, |_| Ok(()))
+    }
+
+    fn watch(&mut self, session: &Session<S>, prefix: &[u8], sender: &EventSender) -> Result<u64> {
+        self.check_read_session(session, &Method::List)?;
+
+        Ok(lock(&self.watchers).watch(prefix, sender))
+    }
+
+    fn unwatch(&mut self, session: &Session<S>, id: u64) -> Result<()> {
+        self.check_read_session(session, &Method::List)?;
+
+        lock(&self.watchers).unwatch(id)
+    }
+}
+
+/// Returns the `Method` of a `BatchOp`, checked against the `Acl` of the committing session.
+fn op_method(op: &BatchOp) -> Method {
+    match op {
+        BatchOp::Create(..) => Method::Create,
+        BatchOp::Update(..) => Method::Update,
+        BatchOp::Upsert(..) => Method::Upsert,
+        BatchOp::Delete(..) => Method::Delete,
+    }
+}
+
+/// Locks a mutex, recovering the guard if a thread panicked while holding it.
+pub(crate) fn lock<'a, T>(mutex: &'a Mutex<T>) -> MutexGuard<'a, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Computes the final state of the items written by a `Batch`, without applying it. Items mapped
+/// to `None` are deleted by the batch, and items both created and deleted by the batch are left out.
+fn stage_locked(items: &BTreeMap<Vec<u8>, Vec<u8>>, batch: &Batch)
+    -> Result<BTreeMap<Vec<u8>, Option<Vec<u8>>>>
+{
+    let mut staged: BTreeMap<Vec<u8>, Option<Vec<u8>>> = BTreeMap::new();
+
+    for op in batch.ops.iter() {
+        let key = op.key();
+
+        let state = {
+            let current = match staged.get(key) {
+                Some(state) => state.as_ref().map(|value| value.as_slice()),
+                None => items.get(key).map(|value| value.as_slice()),
+            };
+
+            op.apply(current)?
+        };
+
+        staged.insert(key.to_owned(), state);
+    }
+
+    staged.retain(|key, state| state.is_some() || items.contains_key(key));
+
+    Ok(staged)
+}
+
+/// Applies the items states computed by `stage_locked`, returning the changes to notify to the
+/// subscriptions.
+fn apply_locked(items: &mut BTreeMap<Vec<u8>, Vec<u8>>, staged: BTreeMap<Vec<u8>, Option<Vec<u8>>>)
+    -> Vec<StoreEvent>
+{
+    let mut events = Vec::new();
+
+    for (key, state) in staged.into_iter() {
+        match state {
+            Some(value) => {
+                match items.insert(key.clone(), value.clone()) {
+                    Some(_) => events.push(StoreEvent::Update(key, value)),
+                    None => events.push(StoreEvent::Create(key, value)),
+                }
+            },
+            None => {
+                if items.remove(&key).is_some() {
+                    events.push(StoreEvent::Delete(key));
+                }
+            },
+        }
+    }
+
+    events
+}
+
+/// Checks the pagination parameters of a list operation. Any number of items can be skipped, and
+/// skipping past the last item returns no items.
+fn check_pagination(count: Option<u64>) -> Result<()> {
+    if let Some(count) = count {
+        if count == 0 {
+            return Err(String::from("invalid count"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the items starting from the `from` key until, not included, the `to` key.
+fn range<'a>(items: &'a BTreeMap<Vec<u8>, Vec<u8>>,
+             from: Option<Vec<u8>>,
+             to: Option<Vec<u8>>)
+    -> Result<Range<'a, Vec<u8>, Vec<u8>>>
+{
+    if let Some(ref from) = from {
+        if let Some(ref to) = to {
+            if from >= to {
+                return Err(String::from("invalid range"));
+            }
+        }
+    }
+
+    let lower = match from {
+        Some(from) => Bound::Included(from),
+        None => Bound::Unbounded,
+    };
+
+    let upper = match to {
+        Some(to) => Bound::Excluded(to),
+        None => Bound::Unbounded,
+    };
+
+    Ok(items.range((lower, upper)))
+}
+
+/// Returns the items whose key starts with a given prefix.
+fn range_prefix<'a>(items: &'a BTreeMap<Vec<u8>, Vec<u8>>, prefix: &'a [u8])
+    -> impl Iterator<Item=(&'a Vec<u8>, &'a Vec<u8>)>
+{
+    let lower = Bound::Included(prefix.to_vec());
+    let upper = Bound::Unbounded;
+
+    items.range((lower, upper))
+        .take_while(move |&(key, _)| key.starts_with(prefix))
+}
+
+/// Collects the values of a sequence of items, skipping `skip` items and taking at most `count` items.
+fn paginate<'a, I>(items: I, count: Option<u64>, skip: u64) -> Vec<Vec<u8>>
+    where   I: Iterator<Item=(&'a Vec<u8>, &'a Vec<u8>)>
+{
+    let values = items
+        .skip(skip as usize)
+        .map(|(_, value)| value.to_owned());
+
+    if let Some(count) = count {
+        values.take(count as usize).collect()
+    } else {
+        values.collect()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,47 @@
+//! # Store
+//!
+//! `store` is the module providing the store traits and the store implementations shipped with the library.
+
+/// Traits implemented by stores and storable types.
+pub mod store;
+
+/// Types used to stage store write operations and commit them atomically.
+pub mod batch;
+
+/// Iterators used to walk the store items lazily.
+pub mod iter;
+
+/// Trait implemented by types that can be encoded as order-preserving store keys.
+pub mod key;
+
+/// Functions used to encode and migrate the store prefixes of storable types.
+pub mod prefix;
+
+/// Types used to maintain secondary indexes over storable items.
+pub mod index;
+
+/// Types used to query the items of storable types.
+pub mod query;
+
+/// Types used to subscribe to the changes of the store items.
+pub mod watch;
+
+/// Types used to export and import snapshots of the store items.
+pub mod snapshot;
+
+/// Type used to represent an in-memory store.
+pub mod memory;
+
+/// Type used to represent a durable store backed by a write-ahead log.
+pub mod wal;
+
+pub use self::store::{Store, Storable};
+pub use self::batch::{Batch, BatchOp};
+pub use self::iter::{Direction, StoreIter, StorableIter};
+pub use self::key::StoreKey;
+pub use self::index::IndexKey;
+pub use self::query::{Filter, Query, QueryPlan, QueryFilter};
+pub use self::watch::{StoreEvent, EventSender, EventReceiver, EventChannel, Watchers};
+pub use self::snapshot::{Snapshot, SnapshotHeader};
+pub use self::memory::MemoryStore;
+pub use self::wal::{WalStore, SyncPolicy};
\ No newline at end of file
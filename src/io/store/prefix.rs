@@ -0,0 +1,118 @@
+//! # Prefix
+//!
+//! `prefix` is the module providing the encoding of the store prefixes of the `Storable` types.
+//!
+//! A store prefix is made of a version byte followed by the type code encoded as big-endian, so that
+//! the store keys do not depend on the host endianness and a store can be copied between machines.
+//! The version byte has the high bit set, which is never the case for the first byte of the legacy
+//! prefixes, where the type code was transmuted with the host (generally little-endian) byte order.
+
+use base::Result;
+use base::Datable;
+use base::Serializable;
+use io::Permission;
+use io::store::{Store, Batch, Direction};
+use io::store::iter::{prefix_range, DEFAULT_PAGE_SIZE};
+
+/// Current version of the store prefix encoding.
+pub const PREFIX_VERSION: u8 = 1;
+
+/// Flag of the versioned store prefixes.
+const PREFIX_VERSION_FLAG: u8 = 0x80;
+
+/// Length of an encoded store prefix.
+pub const PREFIX_LEN: usize = 9;
+
+/// Length of a legacy store prefix.
+pub const LEGACY_PREFIX_LEN: usize = 8;
+
+/// Encodes the store prefix of a type code.
+pub fn encode_prefix(code: u64) -> Vec<u8> {
+    let mut prefix = Vec::with_capacity(PREFIX_LEN);
+
+    prefix.push(PREFIX_VERSION_FLAG | PREFIX_VERSION);
+    prefix.extend_from_slice(&code.to_be_bytes()[..]);
+
+    prefix
+}
+
+/// Decodes the type code of a store key starting with an encoded store prefix.
+pub fn decode_prefix(key: &[u8]) -> Result<u64> {
+    if key.len() < PREFIX_LEN {
+        return Err(String::from("invalid length"));
+    }
+
+    if key[0] != PREFIX_VERSION_FLAG | PREFIX_VERSION {
+        return Err(String::from("invalid version"));
+    }
+
+    let mut code = [0u8; 8];
+    code.copy_from_slice(&key[1..PREFIX_LEN]);
+
+    Ok(u64::from_be_bytes(code))
+}
+
+/// Encodes the legacy store prefix of a type code, as written on little-endian hosts.
+pub fn encode_legacy_prefix(code: u64) -> Vec<u8> {
+    code.to_le_bytes().to_vec()
+}
+
+/// Migrates the store items with a legacy prefix of the given type codes to the current store prefix
+/// encoding, returning the number of migrated items. The migration is committed in a single `Batch`.
+/// Items are moved as they are, keeping the rest of their keys, so no secondary index entry is created
+/// for them. `Storable` types should be migrated with `Storable::store_migrate`, which also re-encodes
+/// their store keys.
+pub fn migrate_prefixes<St, S>(store: &mut St, codes: &[u64]) -> Result<u64>
+    where   St: Store<S>,
+            S: Datable + Serializable
+{
+    store.check()?;
+
+    let read_session = store.session(&Permission::Read)?;
+
+    let mut batch = Batch::new();
+
+    for code in codes.iter() {
+        let legacy_prefix = encode_legacy_prefix(*code);
+        let prefix = encode_prefix(*code);
+
+        let (from, to) = prefix_range(&legacy_prefix);
+
+        let mut cursor = None;
+
+        loop {
+            let items = store.scan(&read_session,
+                                   from.clone(),
+                                   to.clone(),
+                                   cursor.clone(),
+                                   Direction::Forward,
+                                   DEFAULT_PAGE_SIZE)?;
+
+            if items.is_empty() {
+                break;
+            }
+
+            for (key, value) in items.iter() {
+                let mut new_key = prefix.clone();
+                new_key.extend_from_slice(&key[LEGACY_PREFIX_LEN..]);
+
+                batch.create(&new_key, value)?;
+                batch.delete(key)?;
+            }
+
+            cursor = items.last().map(|(key, _)| key.to_owned());
+        }
+    }
+
+    let migrated = batch.ops_len / 2;
+
+    if batch.is_empty() {
+        return Ok(migrated);
+    }
+
+    let write_session = store.session(&Permission::Write)?;
+
+    store.commit(&write_session, &batch)?;
+
+    Ok(migrated)
+}
\ No newline at end of file
@@ -0,0 +1,377 @@
+//! # Query
+//!
+//! `query` is the module providing the types used to query the items of `Storable` types with
+//! composable filters, ordering, projections and limits.
+//!
+//! A `Query` is executed in two steps. The key range, the direction and, when no custom ordering is
+//! required, the skip and limit are collected in a `QueryPlan` and pushed down to the store with
+//! `Store::query`, together with a callback applying the query filters to the raw items. The store
+//! default implementation walks the range page by page, while stores able to evaluate queries natively
+//! can override it. The items returned by the store are then decoded, ordered and projected.
+
+use std::cmp::Ordering;
+use std::mem;
+
+use base::Result;
+use base::Checkable;
+use base::Sizable;
+use base::Serializable;
+use base::Datable;
+use io::Permission;
+use io::store::{Store, Storable, StoreKey, Direction};
+use io::store::iter::prefix_range;
+
+/// Predicate applied to the items of a query.
+pub type FilterFn<T> = Box<dyn Fn(&T) -> Result<bool>>;
+
+/// Ordering applied to the items of a query.
+pub type OrderFn<T> = Box<dyn Fn(&T, &T) -> Ordering>;
+
+/// Type used to represent a composable predicate over the items of a query.
+#[derive(Default)]
+pub enum Filter<T> {
+    /// Matches all the items.
+    #[default]
+    All,
+    /// Matches the items satisfying a predicate.
+    Predicate(FilterFn<T>),
+    /// Matches the items matched by both the filters.
+    And(Box<Filter<T>>, Box<Filter<T>>),
+    /// Matches the items matched by at least one of the filters.
+    Or(Box<Filter<T>>, Box<Filter<T>>),
+    /// Matches the items not matched by the filter.
+    Not(Box<Filter<T>>),
+}
+
+impl<T> Filter<T> {
+    /// Creates a new `Filter` from a predicate.
+    pub fn new<F>(predicate: F) -> Self
+        where   F: 'static + Fn(&T) -> Result<bool>
+    {
+        Filter::Predicate(Box::new(predicate))
+    }
+
+    /// Combines the `Filter` with another one, matching the items matched by both.
+    pub fn and(self, other: Filter<T>) -> Self {
+        match (self, other) {
+            (Filter::All, other) => other,
+            (filter, Filter::All) => filter,
+            (filter, other) => Filter::And(Box::new(filter), Box::new(other)),
+        }
+    }
+
+    /// Combines the `Filter` with another one, matching the items matched by at least one of them.
+    pub fn or(self, other: Filter<T>) -> Self {
+        match (self, other) {
+            (Filter::All, _) | (_, Filter::All) => Filter::All,
+            (filter, other) => Filter::Or(Box::new(filter), Box::new(other)),
+        }
+    }
+
+    /// Negates the `Filter`.
+    pub fn negate(self) -> Self {
+        Filter::Not(Box::new(self))
+    }
+
+    /// Returns if the `Filter` matches an item.
+    pub fn matches(&self, item: &T) -> Result<bool> {
+        match self {
+            Filter::All => Ok(true),
+            Filter::Predicate(predicate) => predicate(item),
+            Filter::And(left, right) => Ok(left.matches(item)? && right.matches(item)?),
+            Filter::Or(left, right) => Ok(left.matches(item)? || right.matches(item)?),
+            Filter::Not(filter) => Ok(!filter.matches(item)?),
+        }
+    }
+
+    /// Returns if the `Filter` matches all the items.
+    pub fn is_all(&self) -> bool {
+        matches!(*self, Filter::All)
+    }
+}
+
+/// Callback applying the filters of a `Query` to the raw store items, as keys and values.
+pub type QueryFilter<'a> = dyn 'a + FnMut(&[u8], &[u8]) -> Result<bool>;
+
+/// Type used to represent the part of a `Query` pushed down to a store: the range of the store keys,
+/// the scan direction and the number of matching items to skip and to return.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Default, Hash, Serialize, Deserialize)]
+pub struct QueryPlan {
+    /// Store key the range starts from.
+    pub from: Option<Vec<u8>>,
+    /// Store key the range ends at, not included.
+    pub to: Option<Vec<u8>>,
+    /// Scan direction.
+    pub direction: Direction,
+    /// Number of matching items to skip.
+    pub skip: u64,
+    /// Maximum number of matching items to return.
+    pub limit: Option<u64>,
+}
+
+impl Sizable for QueryPlan {
+    fn size(&self) -> u64 {
+        self.from.size() +
+            self.to.size() +
+            self.direction.size() +
+            self.skip.size() +
+            self.limit.size()
+    }
+}
+
+impl Checkable for QueryPlan {
+    fn check(&self) -> Result<()> {
+        self.from.check()?;
+        self.to.check()?;
+        self.direction.check()?;
+        self.skip.check()?;
+        self.limit.check()?;
+
+        if let Some(ref from) = self.from {
+            if let Some(ref to) = self.to {
+                if from >= to {
+                    return Err(String::from("invalid range"));
+                }
+            }
+        }
+
+        if self.limit == Some(0) {
+            return Err(String::from("invalid limit"));
+        }
+
+        Ok(())
+    }
+}
+
+impl Serializable for QueryPlan {}
+
+impl Datable for QueryPlan {}
+
+/// Type used to represent a query over the items of a `Storable` type.
+pub struct Query<K, T>
+    where   K: Ord + Datable + Serializable + StoreKey
+{
+    from: Option<K>,
+    to: Option<K>,
+    direction: Direction,
+    filter: Filter<T>,
+    order: Option<OrderFn<T>>,
+    skip: u64,
+    limit: Option<u64>,
+}
+
+impl<K, T> Query<K, T>
+    where   K: Ord + Datable + Serializable + StoreKey
+{
+    /// Creates a new `Query` matching all the items of a type.
+    pub fn new() -> Self {
+        Query {
+            from: None,
+            to: None,
+            direction: Direction::Forward,
+            filter: Filter::All,
+            order: None,
+            skip: 0,
+            limit: None,
+        }
+    }
+
+    /// Sets the key the `Query` range starts from.
+    pub fn from(mut self, key: &K) -> Result<Self> {
+        key.check()?;
+
+        self.from = Some(key.to_owned());
+
+        Ok(self)
+    }
+
+    /// Sets the key the `Query` range ends at, not included.
+    pub fn to(mut self, key: &K) -> Result<Self> {
+        key.check()?;
+
+        self.to = Some(key.to_owned());
+
+        Ok(self)
+    }
+
+    /// Sets the direction the `Query` range is scanned in. Without a custom ordering, the items are
+    /// returned in this direction.
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Adds a predicate to the `Query` filter. Items must satisfy all the predicates of the `Query`.
+    pub fn filter<F>(self, predicate: F) -> Self
+        where   F: 'static + Fn(&T) -> Result<bool>
+    {
+        self.filter_by(Filter::new(predicate))
+    }
+
+    /// Adds a `Filter` to the `Query` filter. Items must match all the filters of the `Query`.
+    pub fn filter_by(mut self, filter: Filter<T>) -> Self {
+        let current = mem::replace(&mut self.filter, Filter::All);
+        self.filter = current.and(filter);
+        self
+    }
+
+    /// Sets a custom ordering of the `Query` items. The matching items are sorted before being
+    /// skipped and limited, so the whole range is scanned.
+    pub fn order_by<F>(mut self, order: F) -> Self
+        where   F: 'static + Fn(&T, &T) -> Ordering
+    {
+        self.order = Some(Box::new(order));
+        self
+    }
+
+    /// Sets a custom ordering of the `Query` items by a key extracted from each item.
+    pub fn order_by_key<F, O>(self, key: F) -> Self
+        where   F: 'static + Fn(&T) -> O,
+                O: Ord
+    {
+        self.order_by(move |a, b| key(a).cmp(&key(b)))
+    }
+
+    /// Sets the number of matching items skipped by the `Query`.
+    pub fn skip(mut self, skip: u64) -> Self {
+        self.skip = skip;
+        self
+    }
+
+    /// Sets the maximum number of items returned by the `Query`.
+    pub fn limit(mut self, limit: u64) -> Result<Self> {
+        if limit == 0 {
+            return Err(String::from("invalid limit"));
+        }
+
+        self.limit = Some(limit);
+
+        Ok(self)
+    }
+
+    /// Returns the `QueryPlan` pushed down to the store for the items with a given store prefix.
+    pub fn plan(&self, prefix: &[u8]) -> Result<QueryPlan> {
+        self.from.check()?;
+        self.to.check()?;
+
+        if let Some(ref from) = self.from {
+            if let Some(ref to) = self.to {
+                if from >= to {
+                    return Err(String::from("invalid range"));
+                }
+            }
+        }
+
+        let (prefix_from, prefix_to) = prefix_range(prefix);
+
+        let from = if let Some(ref k) = self.from {
+            let mut from_key = prefix.to_owned();
+            from_key.extend(&k.to_store_key()?);
+
+            Some(from_key)
+        } else {
+            prefix_from
+        };
+
+        let to = if let Some(ref k) = self.to {
+            let mut to_key = prefix.to_owned();
+            to_key.extend(&k.to_store_key()?);
+
+            Some(to_key)
+        } else {
+            prefix_to
+        };
+
+        let (skip, limit) = if self.order.is_some() {
+            (0, None)
+        } else {
+            (self.skip, self.limit)
+        };
+
+        let plan = QueryPlan {
+            from,
+            to,
+            direction: self.direction,
+            skip,
+            limit,
+        };
+
+        plan.check()?;
+
+        Ok(plan)
+    }
+
+    /// Executes the `Query` in a store, returning the matching items.
+    pub fn execute<St, S, V>(&self, store: &mut St) -> Result<Vec<T>>
+        where   St: Store<S>,
+                S: Datable + Serializable,
+                V: Datable + Serializable,
+                T: Storable<St, S, K, V>
+    {
+        let plan = self.plan(&T::store_prefix())?;
+
+        let session = store.session(&Permission::Read)?;
+
+        let filter = &self.filter;
+
+        let items = store.query(&session, &plan, &mut |_, value| {
+            if filter.is_all() {
+                return Ok(true);
+            }
+
+            filter.matches(&T::from_store_value(value)?)
+        })?;
+
+        let mut list = Vec::new();
+
+        for (_, value) in items.iter() {
+            list.push(T::from_store_value(value)?);
+        }
+
+        if let Some(ref order) = self.order {
+            list.sort_by(|a, b| order(a, b));
+
+            let skip = self.skip as usize;
+            let limit = self.limit.map(|limit| limit as usize).unwrap_or(list.len());
+
+            list = list.into_iter().skip(skip).take(limit).collect();
+        }
+
+        Ok(list)
+    }
+
+    /// Executes the `Query` in a store, returning a projection of the matching items.
+    pub fn select<St, S, V, R, F>(&self, store: &mut St, projection: F) -> Result<Vec<R>>
+        where   St: Store<S>,
+                S: Datable + Serializable,
+                V: Datable + Serializable,
+                T: Storable<St, S, K, V>,
+                F: Fn(&T) -> Result<R>
+    {
+        let mut list = Vec::new();
+
+        for item in self.execute(store)?.iter() {
+            list.push(projection(item)?);
+        }
+
+        Ok(list)
+    }
+
+    /// Counts the items matched by the `Query` in a store.
+    pub fn count<St, S, V>(&self, store: &mut St) -> Result<u64>
+        where   St: Store<S>,
+                S: Datable + Serializable,
+                V: Datable + Serializable,
+                T: Storable<St, S, K, V>
+    {
+        Ok(self.execute(store)?.len() as u64)
+    }
+}
+
+impl<K, T> Default for Query<K, T>
+    where   K: Ord + Datable + Serializable + StoreKey
+{
+    fn default() -> Query<K, T> {
+        Query::new()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,352 @@
+//! # Snapshot
+//!
+//! `snapshot` is the module providing the types used to export the items of a store, or only the
+//! items with a given prefix, and to import them into any other store.
+//!
+//! A snapshot file starts with `SNAPSHOT_MAGIC` and `SNAPSHOT_VERSION`, followed by the length of the
+//! header, encoded as big-endian `u32`, the header and the items, both serialized with
+//! `Serializable::to_bytes`. The header conveys the chain metadata of the snapshot, the prefix of the
+//! exported items, their number and the digest of the snapshot, computed over the header, with a
+//! default digest, and the serialized items.
+
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::Path;
+
+use base::Result;
+use base::Checkable;
+use base::Sizable;
+use base::ConstantSize;
+use base::Serializable;
+use base::Datable;
+use base::Meta;
+use crypto::Hash;
+use io::Permission;
+use io::store::{Store, Batch, StoreIter, Direction};
+
+/// Magic bytes starting a snapshot file.
+pub const SNAPSHOT_MAGIC: &[u8] = b"MTRDSNAP";
+
+/// Version of the snapshot file format.
+pub const SNAPSHOT_VERSION: u8 = 1;
+
+/// Type used to represent the header of a `Snapshot`.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default, Hash, Serialize, Deserialize)]
+pub struct SnapshotHeader<D>
+    where   D: Datable + ConstantSize
+{
+    /// Metadata of the chain the snapshot belongs to.
+    pub meta: Meta,
+    /// Prefix of the snapshot items.
+    pub prefix: Vec<u8>,
+    /// Snapshot items length.
+    pub items_len: u64,
+    /// Digest of the snapshot header and items.
+    pub digest: D,
+}
+
+impl<D> Sizable for SnapshotHeader<D>
+    where   D: Datable + ConstantSize
+{
+    fn size(&self) -> u64 {
+        self.meta.size() +
+            self.prefix.size() +
+            self.items_len.size() +
+            self.digest.size()
+    }
+}
+
+impl<D> Checkable for SnapshotHeader<D>
+    where   D: Datable + ConstantSize
+{
+    fn check(&self) -> Result<()> {
+        self.meta.check()?;
+        self.prefix.check()?;
+        self.items_len.check()?;
+        self.digest.check()?;
+        self.digest.check_size()
+    }
+}
+
+impl<D> Serializable for SnapshotHeader<D>
+    where   D: Datable + ConstantSize + Serializable
+{}
+
+impl<D> Datable for SnapshotHeader<D>
+    where   D: Datable + ConstantSize
+{}
+
+/// Type used to represent a snapshot of the items of a store.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default, Hash, Serialize, Deserialize)]
+pub struct Snapshot<D>
+    where   D: Datable + ConstantSize
+{
+    /// Snapshot header.
+    pub header: SnapshotHeader<D>,
+    /// Snapshot items, as keys and values ordered by key.
+    pub items: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl<D> Snapshot<D>
+    where   D: Datable + ConstantSize + Serializable
+{
+    /// Exports the items of a store starting with a given prefix (all the items, if the prefix is empty)
+    /// into a new `Snapshot`.
+    pub fn export<St, S, H>(store: &mut St, meta: &Meta, prefix: &[u8], hasher: &mut H) -> Result<Self>
+        where   St: Store<S>,
+                S: Datable + Serializable,
+                H: Hash<D>
+    {
+        meta.check()?;
+
+        let session = store.session(&Permission::Read)?;
+
+        let mut items = Vec::new();
+
+        for item in StoreIter::new_prefix(store, &session, prefix, Direction::Forward)? {
+            items.push(item?);
+        }
+
+        let header = SnapshotHeader {
+            meta: meta.to_owned(),
+            prefix: prefix.to_owned(),
+            items_len: items.len() as u64,
+            digest: D::default(),
+        };
+
+        let mut snapshot = Snapshot {
+            header,
+            items,
+        };
+
+        snapshot.header.digest = snapshot.digest(hasher)?;
+
+        Ok(snapshot)
+    }
+
+    /// Returns the message hashed in the `Snapshot` digest: the header, with a default digest,
+    /// followed by the items.
+    fn digest_message(&self) -> Result<Vec<u8>> {
+        let mut header = self.header.clone();
+        header.digest = D::default();
+
+        let mut msg = header.to_bytes()?;
+        msg.extend(self.items.to_bytes()?);
+
+        Ok(msg)
+    }
+
+    /// Computes the `Snapshot` digest.
+    pub fn digest<H: Hash<D>>(&self, hasher: &mut H) -> Result<D> {
+        hasher.digest(&self.digest_message()?)
+    }
+
+    /// Verifies the `Snapshot` digest against its header and items.
+    pub fn verify_digest<H: Hash<D>>(&self, hasher: &mut H) -> Result<bool> {
+        self.check()?;
+
+        hasher.verify(&self.digest_message()?, &self.header.digest)
+    }
+
+    /// Checks the `Snapshot` digest against its header and items.
+    pub fn check_digest<H: Hash<D>>(&self, hasher: &mut H) -> Result<()> {
+        self.check()?;
+
+        hasher.check(&self.digest_message()?, &self.header.digest)
+    }
+
+    /// Imports the `Snapshot` items into a store, after checking that the snapshot belongs to the
+    /// chain described by a given `Meta` and verifying its digest. The items are upserted in a single
+    /// `Batch`, returning their number. The store items with the snapshot prefix that are not in the
+    /// snapshot are kept: use `Snapshot::restore` to remove them.
+    pub fn import<St, S, H>(&self, store: &mut St, meta: &Meta, hasher: &mut H) -> Result<u64>
+        where   St: Store<S>,
+                S: Datable + Serializable,
+                H: Hash<D>
+    {
+        self.write_items(store, meta, hasher, false)
+    }
+
+    /// Restores the `Snapshot` items into a store, as `Snapshot::import` does, deleting in the same
+    /// `Batch` the store items with the snapshot prefix that are not in the snapshot.
+    pub fn restore<St, S, H>(&self, store: &mut St, meta: &Meta, hasher: &mut H) -> Result<u64>
+        where   St: Store<S>,
+                S: Datable + Serializable,
+                H: Hash<D>
+    {
+        self.write_items(store, meta, hasher, true)
+    }
+
+    /// Writes the `Snapshot` items into a store, deleting the stale items with the snapshot prefix
+    /// if required.
+    fn write_items<St, S, H>(&self, store: &mut St, meta: &Meta, hasher: &mut H, clear: bool) -> Result<u64>
+        where   St: Store<S>,
+                S: Datable + Serializable,
+                H: Hash<D>
+    {
+        meta.check()?;
+
+        if self.header.meta.chain != meta.chain {
+            return Err(String::from("invalid chain"));
+        }
+
+        if self.header.meta.stage != meta.stage {
+            return Err(String::from("invalid stage"));
+        }
+
+        if !self.header.meta.version.is_compatible(&meta.version)? {
+            return Err(String::from("invalid version"));
+        }
+
+        self.check_digest(hasher)?;
+
+        let mut batch = Batch::new();
+
+        if clear {
+            let session = store.session(&Permission::Read)?;
+
+            let mut stale = Vec::new();
+
+            for item in StoreIter::new_prefix(store, &session, &self.header.prefix, Direction::Forward)? {
+                let (key, _) = item?;
+
+                if self.items.binary_search_by(|(item_key, _)| item_key.cmp(&key)).is_err() {
+                    stale.push(key);
+                }
+            }
+
+            for key in stale.iter() {
+                batch.delete(key)?;
+            }
+        }
+
+        for (key, value) in self.items.iter() {
+            batch.upsert(key, value)?;
+        }
+
+        let session = store.session(&Permission::Write)?;
+
+        if !batch.is_empty() {
+            store.commit(&session, &batch)?;
+        }
+
+        Ok(self.header.items_len)
+    }
+
+    /// Reads a `Snapshot` from a file.
+    pub fn read_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mut buf = Vec::new();
+
+        File::open(path)
+            .and_then(|mut file| file.read_to_end(&mut buf))
+            .map_err(|e| format!("{}", e))?;
+
+        let header_start = SNAPSHOT_MAGIC.len() + 5;
+
+        if buf.len() < header_start {
+            return Err(String::from("invalid length"));
+        }
+
+        if &buf[..SNAPSHOT_MAGIC.len()] != SNAPSHOT_MAGIC {
+            return Err(String::from("invalid magic"));
+        }
+
+        if buf[SNAPSHOT_MAGIC.len()] != SNAPSHOT_VERSION {
+            return Err(String::from("invalid version"));
+        }
+
+        let mut header_len = [0u8; 4];
+        header_len.copy_from_slice(&buf[SNAPSHOT_MAGIC.len() + 1..header_start]);
+        let header_len = u32::from_be_bytes(header_len) as usize;
+
+        if buf.len() - header_start < header_len {
+            return Err(String::from("invalid length"));
+        }
+
+        let items_start = header_start + header_len;
+
+        let header = SnapshotHeader::from_bytes(&buf[header_start..items_start])?;
+        let items = Vec::from_bytes(&buf[items_start..])?;
+
+        let snapshot = Snapshot {
+            header,
+            items,
+        };
+
+        snapshot.check()?;
+
+        Ok(snapshot)
+    }
+
+    /// Writes the `Snapshot` to a file.
+    pub fn write_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.check()?;
+
+        let header = self.header.to_bytes()?;
+
+        if header.len() > u32::MAX as usize {
+            return Err(String::from("invalid length"));
+        }
+
+        let mut buf = Vec::new();
+
+        buf.extend_from_slice(SNAPSHOT_MAGIC);
+        buf.push(SNAPSHOT_VERSION);
+        buf.extend_from_slice(&(header.len() as u32).to_be_bytes());
+        buf.extend(header);
+        buf.extend(self.items.to_bytes()?);
+
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)
+            .map_err(|e| format!("{}", e))?;
+
+        file.write_all(&buf)
+            .and_then(|_| file.sync_all())
+            .map_err(|e| format!("{}", e))
+    }
+}
+
+impl<D> Sizable for Snapshot<D>
+    where   D: Datable + ConstantSize
+{
+    fn size(&self) -> u64 {
+        self.header.size() +
+            self.items.size()
+    }
+}
+
+impl<D> Checkable for Snapshot<D>
+    where   D: Datable + ConstantSize
+{
+    fn check(&self) -> Result<()> {
+        self.header.check()?;
+        self.items.check()?;
+
+        if self.items.len() as u64 != self.header.items_len {
+            return Err(String::from("invalid items length"));
+        }
+
+        for (idx, (key, _)) in self.items.iter().enumerate() {
+            if !key.starts_with(&self.header.prefix) {
+                return Err(String::from("invalid prefix"));
+            }
+
+            if idx > 0 && &self.items[idx - 1].0 >= key {
+                return Err(String::from("invalid order"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<D> Serializable for Snapshot<D>
+    where   D: Datable + ConstantSize + Serializable
+{}
+
+impl<D> Datable for Snapshot<D>
+    where   D: Datable + ConstantSize
+{}
\ No newline at end of file
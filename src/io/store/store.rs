@@ -0,0 +1,950 @@
+//! # Store
+//!
+//! `store` is the module providing the traits implemented by stores and by types that
+//! can be stored in and retrieved from a store.
+
+use base::Result;
+use base::Checkable;
+use base::Datable;
+use base::Serializable;
+use base::{Eval, EvalMut};
+use io::Permission;
+use io::Session;
+use io::network::{Method, Resource};
+use io::store::{Batch, BatchOp};
+use io::store::{Direction, StoreIter, StorableIter};
+use io::store::iter::{prefix_range, DEFAULT_PAGE_SIZE};
+use io::store::prefix::{decode_prefix, encode_legacy_prefix};
+use io::store::{StoreKey, IndexKey};
+use io::store::{Query, QueryPlan, QueryFilter};
+use io::store::EventSender;
+use io::store::index::{index_prefix, index_key_prefix};
+
+/// Trait representing the operations implemented by a store.
+pub trait Store<S>
+    where   S: Datable + Serializable,
+            Self: 'static + Sized + Send + Sync + Checkable
+{
+    /// Retrieves a new `Session` from the store.
+    fn session(&mut self, permission: &Permission) -> Result<Session<S>>;
+    
+    /// Counts the store items starting from the `from` key until, not included, the `to` key.
+    fn count(&mut self, session: &Session<S>, from: Option<Vec<u8>>, to: Option<Vec<u8>>) -> Result<u64>;
+
+    /// Counts the store items starting with the given prefix.
+    fn count_prefix(&mut self,
+                    session: &Session<S>,
+                    prefix: &[u8])
+        -> Result<u64>;
+    
+    /// Lists the store items starting from the `from` key until, not included, the `to` key.
+    fn list(&mut self,
+            session: &Session<S>,
+            from: Option<Vec<u8>>,
+            to: Option<Vec<u8>>,
+            count: Option<u64>,
+            skip: u64)
+        -> Result<Vec<Vec<u8>>>;
+
+    /// Lists the store items starting with the given prefix.
+    fn list_prefix(&mut self,
+                   session: &Session<S>,
+                   prefix: &[u8],
+                   count: Option<u64>,
+                   skip: u64)
+        -> Result<Vec<Vec<u8>>>;
+    
+    /// Scans at most `count` store items, as keys and values, starting from the `from` key until, not
+    /// included, the `to` key. If a `cursor` key is given, the scan resumes right after it in the given
+    /// direction.
+    fn scan(&mut self,
+            session: &Session<S>,
+            from: Option<Vec<u8>>,
+            to: Option<Vec<u8>>,
+            cursor: Option<Vec<u8>>,
+            direction: Direction,
+            count: u64)
+        -> Result<Vec<(Vec<u8>, Vec<u8>)>>;
+
+    /// Scans the store items, as keys and values, in the range of a `QueryPlan`. Only the items accepted
+    /// by the `filter` callback are skipped, counted against the plan limit and returned. The default
+    /// implementation walks the range with `scan`. Stores able to evaluate queries natively can override it.
+    fn query(&mut self,
+             session: &Session<S>,
+             plan: &QueryPlan,
+             filter: &mut QueryFilter)
+        -> Result<Vec<(Vec<u8>, Vec<u8>)>>
+    {
+        session.check()?;
+        plan.check()?;
+
+        let mut skipped = 0;
+        let mut items = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let page = self.scan(session,
+                                 plan.from.clone(),
+                                 plan.to.clone(),
+                                 cursor.clone(),
+                                 plan.direction,
+                                 DEFAULT_PAGE_SIZE)?;
+
+            if page.is_empty() {
+                return Ok(items);
+            }
+
+            cursor = page.last().map(|(key, _)| key.to_owned());
+
+            for (key, value) in page.into_iter() {
+                if !filter(&key, &value)? {
+                    continue;
+                }
+
+                if skipped < plan.skip {
+                    skipped += 1;
+                    continue;
+                }
+
+                items.push((key, value));
+
+                if Some(items.len() as u64) == plan.limit {
+                    return Ok(items);
+                }
+            }
+        }
+    }
+
+    /// Lookups an item from its key.
+    fn lookup(&mut self, session: &Session<S>, key: &[u8]) -> Result<bool>;
+    
+    /// Retrieves an item from its key. The item should already exist in the store before the operation.
+    fn get(&mut self, session: &Session<S>, key: &[u8]) -> Result<Vec<u8>>;
+    
+    /// Creates an item in the store. The item should not exist in the store before the operation.
+    fn create(&mut self, session: &Session<S>, key: &[u8], value: &[u8]) -> Result<()>;
+    
+    /// Updates an item in the store. The item should already exist in the store before the operation.
+    fn update(&mut self, session: &Session<S>, key: &[u8], value: &[u8]) -> Result<()>;
+    
+    /// Creates an item in the store if absent, update it if present.
+    fn upsert(&mut self, session: &Session<S>, key: &[u8], value: &[u8]) -> Result<()>;
+    
+    /// Deletes an item from the store. The item should already exist in the store before the operation.
+    fn delete(&mut self, session: &Session<S>, key: &[u8]) -> Result<()>;
+
+    /// Begins a new `Batch` of write operations. The operations of a session with an `Acl` are
+    /// checked when the batch is committed.
+    fn begin(&mut self, session: &Session<S>) -> Result<Batch> {
+        session.check()?;
+
+        if session.is_expired()? {
+            return Err(String::from("expired session"));
+        }
+
+        if session.acl.is_empty() && !session.permission.can_write() {
+            return Err(String::from("invalid permission"));
+        }
+
+        Ok(Batch::new())
+    }
+
+    /// Commits a `Batch` of write operations atomically: either all the operations are applied or none is.
+    /// The default implementation applies the operations in order and, on failure, restores the items
+    /// already written. It is not atomic if restoring the items fails too: the `Batch` is left partially
+    /// applied and the returned error reports both failures. Implementors that can apply writes
+    /// atomically should override it.
+    fn commit(&mut self, session: &Session<S>, batch: &Batch) -> Result<()> {
+        session.check()?;
+        batch.check()?;
+
+        if session.is_expired()? {
+            return Err(String::from("expired session"));
+        }
+
+        if !session.permission.can_write() {
+            return Err(String::from("invalid permission"));
+        }
+
+        let read_session = self.session(&Permission::Read)?;
+
+        let mut undo_log = Vec::new();
+
+        for op in batch.ops.iter() {
+            let key = op.key();
+
+            let previous = if self.lookup(&read_session, key)? {
+                Some(self.get(&read_session, key)?)
+            } else {
+                None
+            };
+
+            let res = match op {
+                BatchOp::Create(key, value) => self.create(session, key, value),
+                BatchOp::Update(key, value) => self.update(session, key, value),
+                BatchOp::Upsert(key, value) => self.upsert(session, key, value),
+                BatchOp::Delete(key) => self.delete(session, key),
+            };
+
+            if let Err(err) = res {
+                let undo = undo(self, session, undo_log);
+
+                return Err(commit_error(err, undo));
+            }
+
+            undo_log.push((key.to_owned(), previous));
+        }
+
+        Ok(())
+    }
+
+    /// Rollbacks a `Batch` of write operations, discarding all its staged operations.
+    fn rollback(&mut self, session: &Session<S>, batch: &mut Batch) -> Result<()> {
+        session.check()?;
+
+        if session.is_expired()? {
+            return Err(String::from("expired session"));
+        }
+
+        if !session.permission.can_write() {
+            return Err(String::from("invalid permission"));
+        }
+
+        batch.clear();
+
+        Ok(())
+    }
+    
+    /// Subscribes to the changes of the items starting with a given prefix, returning the id of the
+    /// subscription. Every committed change is sent to `sender` as a `StoreEvent`. The default
+    /// implementation returns an error, as only stores observing their writes can support it.
+    fn watch(&mut self, session: &Session<S>, _prefix: &[u8], _sender: &EventSender) -> Result<u64> {
+        session.check()?;
+
+        Err(String::from("not implemented"))
+    }
+
+    /// Removes a subscription to the changes of the items.
+    fn unwatch(&mut self, session: &Session<S>, id: u64) -> Result<()> {
+        session.check()?;
+        id.check()?;
+
+        Err(String::from("not implemented"))
+    }
+
+    /// Eval operation in the store.
+    fn eval<E, P, R>(&mut self, session: &Session<S>, params: &P, evaluator: &E) -> Result<R>
+        where   E: Eval<Self, P, R>,
+                P: Datable,
+                R: Datable
+    {
+        session.check()?;
+        params.check()?;
+
+        if session.is_expired()? {
+            return Err(String::from("expired session"));
+        }
+
+        if !session.acl.is_empty() {
+            session.acl.check_access(&Method::Eval, &Resource::EvalParams)?;
+        } else if !session.permission.can_read() {
+            return Err(String::from("invalid permission"));
+        }
+
+        evaluator.eval(self, params)
+    }
+    
+    /// Evals mutably in the store.
+    fn eval_mut<E, P, R>(&mut self, session: &Session<S>, params: &P, evaluator: &mut E) -> Result<R>
+        where   E: EvalMut<Self, P, R>,
+                P: Datable,
+                R: Datable
+    {
+        session.check()?;
+        params.check()?;
+
+        if session.is_expired()? {
+            return Err(String::from("expired session"));
+        }
+
+        if !session.acl.is_empty() {
+            session.acl.check_access(&Method::EvalMut, &Resource::EvalMutParams)?;
+        } else if !session.permission.can_write() {
+            return Err(String::from("invalid permission"));
+        }
+
+        evaluator.eval_mut(self, params)
+    }
+}
+
+/// Trait implemented by types that can be stored and retrieved from a store.
+pub trait Storable<St, S, K, V>
+    where   St: Store<S>,
+            S: Datable + Serializable,
+            K: Ord + Datable + Serializable + StoreKey,
+            V: Datable + Serializable,
+            Self: Datable + Serializable
+{
+    /// Returns the store prefix of the implementor.
+    fn store_prefix() -> Vec<u8>;
+
+    /// Returns the store key of the implementor.
+    fn store_key(&self) -> Result<K>;
+
+    /// Retrieves the store value of the implementor.
+    fn store_value(&self) -> Result<V>;
+
+    /// Retrieves an instance of the implementor from a store value.
+    fn from_store_value(value: &[u8]) -> Result<Self> {
+        Self::from_bytes(value)
+    }
+
+    /// Returns the keys of the implementor in its secondary indexes. The index entries are created,
+    /// updated and deleted together with the implementor's store item.
+    fn store_indexes(&self) -> Result<Vec<IndexKey>> {
+        Ok(Vec::new())
+    }
+    
+    /// Counts the store items starting from the `from` key until, not included, the `to` key.
+    fn store_count(store: &mut St, from: Option<K>, to: Option<K>) -> Result<u64> {
+        let permission = Permission::Read;
+
+        let session = store.session(&permission)?;
+
+        from.check()?;
+        to.check()?;
+
+        let prefix = Self::store_prefix();
+
+        if from.is_none() && to.is_none() {
+            return store.count_prefix(&session, &prefix);
+        }
+
+        if let Some(ref from) = from {
+            if let Some(ref to) = to {
+                if from >= to {
+                    return Err(String::from("invalid range"));
+                } 
+            }
+        }
+
+        let (prefix_from, prefix_to) = prefix_range(&prefix);
+
+        let store_from = if let Some(k) = from {
+            let mut from_key = Vec::new();
+            from_key.extend_from_slice(&prefix);
+            from_key.extend(&k.to_store_key()?);
+
+            Some(from_key)
+        } else {
+            prefix_from
+        };
+
+        let store_to = if let Some(k) = to {
+            let mut to_key = Vec::new();
+            to_key.extend_from_slice(&prefix);
+            to_key.extend(&k.to_store_key()?);
+
+            Some(to_key)
+        } else {
+            prefix_to
+        };
+
+        store.count(&session, store_from, store_to)
+    }
+    
+    /// Lists the store items starting from the `from` key until, not included, the `to` key.
+    fn store_list(store: &mut St,
+                  from: Option<K>,
+                  to: Option<K>,
+                  count: Option<u64>,
+                  skip: u64)
+        -> Result<Vec<Self>>
+    {
+        let permission = Permission::Read;
+
+        let session = store.session(&permission)?;
+
+        from.check()?;
+        to.check()?;
+
+        if let Some(count) = count {
+            if count == 0 {
+                return Err(String::from("invalid count"));
+            }
+        }
+
+        let mut list = Vec::new();
+
+        let prefix = Self::store_prefix();
+
+        if from.is_none() && to.is_none() {
+            for value in store.list_prefix(&session, &prefix, count, skip)?.iter() {
+                list.push(Self::from_store_value(&value)?);
+            }
+
+            return Ok(list)
+        }
+
+        if let Some(from) = from.clone() {
+            if let Some(to) = to.clone() {
+                if from >= to {
+                    return Err(String::from("invalid range"));
+                } 
+            }
+        }
+
+        let (prefix_from, prefix_to) = prefix_range(&prefix);
+
+        let store_from = if let Some(k) = from {
+            let mut from_key = Vec::new();
+            from_key.extend_from_slice(&prefix);
+            from_key.extend(&k.to_store_key()?);
+
+            Some(from_key)
+        } else {
+            prefix_from
+        };
+
+        let store_to = if let Some(k) = to {
+            let mut to_key = Vec::new();
+            to_key.extend_from_slice(&prefix);
+            to_key.extend(&k.to_store_key()?);
+
+            Some(to_key)
+        } else {
+            prefix_to
+        };
+
+        for value in store.list(&session, store_from, store_to, count, skip)?.iter() {
+            list.push(Self::from_store_value(&value)?);
+        }
+
+        Ok(list)
+    }
+    
+    /// Subscribes to the changes of the implementor items, returning the id of the subscription.
+    fn store_watch(store: &mut St, sender: &EventSender) -> Result<u64> {
+        let permission = Permission::Read;
+
+        let session = store.session(&permission)?;
+
+        store.watch(&session, &Self::store_prefix(), sender)
+    }
+
+    /// Retrieves the items matched by a `Query`.
+    fn store_query(store: &mut St, query: &Query<K, Self>) -> Result<Vec<Self>> {
+        query.execute(store)
+    }
+
+    /// Iterates lazily over the store items starting from the `from` key until, not included, the `to` key.
+    fn store_iter<'a>(store: &'a mut St, from: Option<K>, to: Option<K>, direction: Direction)
+        -> Result<StorableIter<'a, St, S, K, V, Self>>
+    {
+        let permission = Permission::Read;
+
+        let session = store.session(&permission)?;
+
+        from.check()?;
+        to.check()?;
+
+        if let Some(ref from) = from {
+            if let Some(ref to) = to {
+                if from >= to {
+                    return Err(String::from("invalid range"));
+                }
+            }
+        }
+
+        let prefix = Self::store_prefix();
+
+        let (prefix_from, prefix_to) = prefix_range(&prefix);
+
+        let store_from = if let Some(k) = from {
+            let mut from_key = Vec::new();
+            from_key.extend_from_slice(&prefix);
+            from_key.extend(&k.to_store_key()?);
+
+            Some(from_key)
+        } else {
+            prefix_from
+        };
+
+        let store_to = if let Some(k) = to {
+            let mut to_key = Vec::new();
+            to_key.extend_from_slice(&prefix);
+            to_key.extend(&k.to_store_key()?);
+
+            Some(to_key)
+        } else {
+            prefix_to
+        };
+
+        let iter = StoreIter::new(store, &session, store_from, store_to, direction)?;
+
+        Ok(StorableIter::new(iter))
+    }
+
+    /// Lookups an item from its key.
+    fn store_lookup(store: &mut St, key: &K) -> Result<bool> {
+        let permission = Permission::Read;
+
+        let session = store.session(&permission)?;
+
+        key.check()?;
+
+        let mut store_key = Vec::new();
+
+        let prefix = Self::store_prefix();
+        
+        store_key.extend_from_slice(&prefix);
+        store_key.extend_from_slice(&key.to_store_key()?);
+
+        store.lookup(&session, &store_key)
+    }
+    
+    /// Retrieves an item from its key. The item should already exist in the store before the operation.
+    fn store_get(store: &mut St, key: &K) -> Result<Self> {
+        let permission = Permission::Read;
+
+        let session = store.session(&permission)?;
+
+        key.check()?;
+        
+        let mut store_key = Vec::new();
+
+        let prefix = Self::store_prefix();
+        
+        store_key.extend_from_slice(&prefix);
+        store_key.extend_from_slice(&key.to_store_key()?);
+
+        let value = store.get(&session, &store_key)?;
+        Self::from_store_value(&value)
+    }
+    
+    /// Creates an item in the store. The item should not exist in the store before the operation.
+    fn store_create(&self, store: &mut St) -> Result<()> {
+        let permission = Permission::Write;
+
+        let session = store.session(&permission)?;
+
+        let mut batch = store.begin(&session)?;
+
+        self.store_batch_create(store, &mut batch)?;
+
+        store.commit(&session, &batch)
+    }
+    
+    /// Updates the item in the store. The item should already exist in the store before the operation.
+    fn store_update(&self, store: &mut St) -> Result<()> {
+        let permission = Permission::Write;
+
+        let session = store.session(&permission)?;
+
+        let mut batch = store.begin(&session)?;
+
+        self.store_batch_update(store, &mut batch)?;
+
+        store.commit(&session, &batch)
+    }
+    
+    /// Creates the item in the store if absent, update it if present.
+    fn store_upsert(&self, store: &mut St) -> Result<()> {
+        let permission = Permission::Write;
+
+        let session = store.session(&permission)?;
+
+        let mut batch = store.begin(&session)?;
+
+        self.store_batch_upsert(store, &mut batch)?;
+
+        store.commit(&session, &batch)
+    }
+    
+    /// Deletes the item from the store. The item should already exist in the store before the operation.
+    fn store_delete(&self, store: &mut St) -> Result<()> {
+        let permission = Permission::Write;
+
+        let session = store.session(&permission)?;
+
+        let mut batch = store.begin(&session)?;
+
+        self.store_batch_delete(store, &mut batch)?;
+
+        store.commit(&session, &batch)
+    }
+
+    /// Stages the creation of the item, and of its index entries, in a `Batch`.
+    fn store_batch_create(&self, store: &mut St, batch: &mut Batch) -> Result<()> {
+        store.check()?;
+
+        let key = self.store_key()?;
+
+        let value = self.store_value()?;
+
+        let mut store_key = Vec::new();
+
+        let prefix = Self::store_prefix();
+
+        store_key.extend_from_slice(&prefix);
+        store_key.extend_from_slice(&key.to_store_key()?);
+
+        let store_value = value.to_bytes()?;
+
+        let indexes = self.store_indexes()?;
+
+        batch.create(&store_key, &store_value)?;
+
+        stage_indexes(batch, &prefix, &store_key, &[], &indexes)
+    }
+
+    /// Stages the update of the item, and of its index entries, in a `Batch`. The stale index entries
+    /// are found from the item written by the `Batch`, if any, or else from the item in the store.
+    fn store_batch_update(&self, store: &mut St, batch: &mut Batch) -> Result<()> {
+        store.check()?;
+
+        let key = self.store_key()?;
+
+        let value = self.store_value()?;
+
+        let mut store_key = Vec::new();
+
+        let prefix = Self::store_prefix();
+
+        store_key.extend_from_slice(&prefix);
+        store_key.extend_from_slice(&key.to_store_key()?);
+
+        let store_value = value.to_bytes()?;
+
+        let old_value = match previous_value(store, batch, &store_key)? {
+            Some(value) => value,
+            None => return Err(String::from("not found")),
+        };
+
+        let old_indexes = Self::from_store_value(&old_value)?.store_indexes()?;
+
+        let indexes = self.store_indexes()?;
+
+        batch.update(&store_key, &store_value)?;
+
+        stage_indexes(batch, &prefix, &store_key, &old_indexes, &indexes)
+    }
+
+    /// Stages the upsert of the item, and of its index entries, in a `Batch`. The stale index entries
+    /// are found from the item written by the `Batch`, if any, or else from the item in the store.
+    fn store_batch_upsert(&self, store: &mut St, batch: &mut Batch) -> Result<()> {
+        store.check()?;
+
+        let key = self.store_key()?;
+
+        let value = self.store_value()?;
+
+        let mut store_key = Vec::new();
+
+        let prefix = Self::store_prefix();
+
+        store_key.extend_from_slice(&prefix);
+        store_key.extend_from_slice(&key.to_store_key()?);
+
+        let store_value = value.to_bytes()?;
+
+        let old_indexes = match previous_value(store, batch, &store_key)? {
+            Some(old_value) => Self::from_store_value(&old_value)?.store_indexes()?,
+            None => Vec::new(),
+        };
+
+        let indexes = self.store_indexes()?;
+
+        batch.upsert(&store_key, &store_value)?;
+
+        stage_indexes(batch, &prefix, &store_key, &old_indexes, &indexes)
+    }
+
+    /// Stages the deletion of the item, and of its index entries, in a `Batch`. The index entries
+    /// are found from the item written by the `Batch`, if any, or else from the item in the store.
+    fn store_batch_delete(&self, store: &mut St, batch: &mut Batch) -> Result<()> {
+        store.check()?;
+
+        let key = self.store_key()?;
+
+        let mut store_key = Vec::new();
+
+        let prefix = Self::store_prefix();
+
+        store_key.extend_from_slice(&prefix);
+        store_key.extend_from_slice(&key.to_store_key()?);
+
+        let old_value = match previous_value(store, batch, &store_key)? {
+            Some(value) => value,
+            None => return Err(String::from("not found")),
+        };
+
+        let old_indexes = Self::from_store_value(&old_value)?.store_indexes()?;
+
+        batch.delete(&store_key)?;
+
+        stage_indexes(batch, &prefix, &store_key, &old_indexes, &[])
+    }
+
+    /// Retrieves the items with a given key in a secondary index.
+    fn store_get_by_index(store: &mut St, index_key: &IndexKey) -> Result<Vec<Self>> {
+        let permission = Permission::Read;
+
+        let session = store.session(&permission)?;
+
+        index_key.check()?;
+
+        let prefix = Self::store_prefix();
+
+        let entries_prefix = index_key_prefix(&prefix, &index_key.index, &index_key.key)?;
+
+        let mut list = Vec::new();
+
+        for store_key in store.list_prefix(&session, &entries_prefix, None, 0)?.iter() {
+            let value = store.get(&session, store_key)?;
+            list.push(Self::from_store_value(&value)?);
+        }
+
+        Ok(list)
+    }
+
+    /// Counts the items with a key in a secondary index starting from the `from` key until, not
+    /// included, the `to` key.
+    fn store_count_by_index(store: &mut St,
+                            index: &str,
+                            from: Option<Vec<u8>>,
+                            to: Option<Vec<u8>>)
+        -> Result<u64>
+    {
+        let permission = Permission::Read;
+
+        let session = store.session(&permission)?;
+
+        let (store_from, store_to) = index_range(&Self::store_prefix(), index, from, to)?;
+
+        store.count(&session, store_from, store_to)
+    }
+
+    /// Lists the items with a key in a secondary index starting from the `from` key until, not
+    /// included, the `to` key. The items are ordered by index key.
+    fn store_list_by_index(store: &mut St,
+                           index: &str,
+                           from: Option<Vec<u8>>,
+                           to: Option<Vec<u8>>,
+                           count: Option<u64>,
+                           skip: u64)
+        -> Result<Vec<Self>>
+    {
+        let permission = Permission::Read;
+
+        let session = store.session(&permission)?;
+
+        if let Some(count) = count {
+            if count == 0 {
+                return Err(String::from("invalid count"));
+            }
+        }
+
+        let (store_from, store_to) = index_range(&Self::store_prefix(), index, from, to)?;
+
+        let mut list = Vec::new();
+
+        for store_key in store.list(&session, store_from, store_to, count, skip)?.iter() {
+            let value = store.get(&session, store_key)?;
+            list.push(Self::from_store_value(&value)?);
+        }
+
+        Ok(list)
+    }
+
+    /// Migrates the implementor items stored with a legacy store prefix and key encoding, returning the
+    /// number of migrated items. The items are re-created with the current store key encoding, together
+    /// with their index entries, and the legacy items are deleted in the same `Batch`.
+    fn store_migrate(store: &mut St) -> Result<u64> {
+        let code = decode_prefix(&Self::store_prefix())?;
+
+        let (from, to) = prefix_range(&encode_legacy_prefix(code));
+
+        let read_session = store.session(&Permission::Read)?;
+
+        let mut items = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let page = store.scan(&read_session,
+                                  from.clone(),
+                                  to.clone(),
+                                  cursor.clone(),
+                                  Direction::Forward,
+                                  DEFAULT_PAGE_SIZE)?;
+
+            if page.is_empty() {
+                break;
+            }
+
+            cursor = page.last().map(|(key, _)| key.to_owned());
+
+            items.extend(page);
+        }
+
+        let mut batch = Batch::new();
+
+        for (key, value) in items.iter() {
+            let item = Self::from_store_value(value)?;
+
+            batch.delete(key)?;
+            item.store_batch_create(store, &mut batch)?;
+        }
+
+        if batch.is_empty() {
+            return Ok(0);
+        }
+
+        let write_session = store.session(&Permission::Write)?;
+
+        store.commit(&write_session, &batch)?;
+
+        Ok(items.len() as u64)
+    }
+
+    /// Eval operation in the store.
+    fn store_eval<E, P, R>(store: &mut St, session: &Session<S>, params: &P, evaluator: &E)
+        -> Result<R>
+        where   E: Eval<St, P, R>,
+                P: Datable,
+                R: Datable
+    {
+        params.check()?;
+        session.check()?;
+
+        if session.is_expired()? {
+            return Err(String::from("expired session"));
+        }
+
+        if !session.acl.is_empty() {
+            session.acl.check_access(&Method::Eval, &Resource::EvalParams)?;
+        } else if !session.permission.can_read() {
+            return Err(String::from("invalid permission"));
+        }
+
+        store.eval(session, params, evaluator)
+    }
+
+    /// Evals mutably in the store.
+    fn store_eval_mut<E, P, R>(store: &mut St, session: &Session<S>, params: &P, evaluator: &mut E)
+        -> Result<R>
+        where   E: EvalMut<St, P, R>,
+                P: Datable,
+                R: Datable
+    {
+        params.check()?;
+        session.check()?;
+
+        if session.is_expired()? {
+            return Err(String::from("expired session"));
+        }
+
+        if !session.acl.is_empty() {
+            session.acl.check_access(&Method::EvalMut, &Resource::EvalMutParams)?;
+        } else if !session.permission.can_write() {
+            return Err(String::from("invalid permission"));
+        }
+
+        store.eval_mut(session, params, evaluator)
+    }
+}
+
+/// Restores the items written by a partially applied `Batch`, given their previous values.
+fn undo<St, S>(store: &mut St, session: &Session<S>, undo_log: Vec<(Vec<u8>, Option<Vec<u8>>)>) -> Result<()>
+    where   St: Store<S>,
+            S: Datable + Serializable
+{
+    for (key, previous) in undo_log.into_iter().rev() {
+        match previous {
+            Some(value) => store.upsert(session, &key, &value)?,
+            None => store.delete(session, &key)?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the error of a failed `Batch` commit, keeping the error of its undo, if any.
+fn commit_error(err: String, undo: Result<()>) -> String {
+    match undo {
+        Ok(()) => err,
+        Err(undo_err) => format!("{}: rollback failed: {}", err, undo_err),
+    }
+}
+
+/// Returns the value of an item before a new operation on it is staged in a `Batch`: the value written
+/// by the operations already staged, if any, or else the value in the store.
+fn previous_value<St, S>(store: &mut St, batch: &Batch, store_key: &[u8]) -> Result<Option<Vec<u8>>>
+    where   St: Store<S>,
+            S: Datable + Serializable
+{
+    if let Some(state) = batch.get(store_key) {
+        return Ok(state.map(|value| value.to_owned()));
+    }
+
+    let session = store.session(&Permission::Read)?;
+
+    if store.lookup(&session, store_key)? {
+        Ok(Some(store.get(&session, store_key)?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Stages in a `Batch` the index entries changes of an item, given its old and new index keys.
+fn stage_indexes(batch: &mut Batch,
+                 prefix: &[u8],
+                 store_key: &[u8],
+                 old_indexes: &[IndexKey],
+                 indexes: &[IndexKey])
+    -> Result<()>
+{
+    for index_key in old_indexes.iter() {
+        if !indexes.contains(index_key) {
+            batch.delete(&index_key.entry_key(prefix, store_key)?)?;
+        }
+    }
+
+    for index_key in indexes.iter() {
+        if !old_indexes.contains(index_key) {
+            batch.create(&index_key.entry_key(prefix, store_key)?, store_key)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the store range of the entries of an index with a key starting from the `from` key until,
+/// not included, the `to` key.
+fn index_range(prefix: &[u8], index: &str, from: Option<Vec<u8>>, to: Option<Vec<u8>>)
+    -> Result<(Option<Vec<u8>>, Option<Vec<u8>>)>
+{
+    if let Some(ref from) = from {
+        if let Some(ref to) = to {
+            if from >= to {
+                return Err(String::from("invalid range"));
+            }
+        }
+    }
+
+    let (prefix_from, prefix_to) = prefix_range(&index_prefix(prefix, index)?);
+
+    let store_from = if let Some(key) = from {
+        Some(index_key_prefix(prefix, index, &key)?)
+    } else {
+        prefix_from
+    };
+
+    let store_to = if let Some(key) = to {
+        Some(index_key_prefix(prefix, index, &key)?)
+    } else {
+        prefix_to
+    };
+
+    Ok((store_from, store_to))
+}
\ No newline at end of file
@@ -0,0 +1,654 @@
+//! # Wal
+//!
+//! `wal` is the module providing `WalStore`, a durable implementation of the `Store` trait backed by
+//! a write-ahead log on the file system. Every write is appended to the log as a checksummed record
+//! before being applied to an in-memory ordered index, so reads never touch the disk.
+//!
+//! The log starts with a header (`WAL_MAGIC` and `WAL_VERSION`), followed by records made of the
+//! payload length and its CRC-32 checksum, both encoded as big-endian `u32`, and the payload, a `Batch`
+//! of upserts and deletes serialized with `Serializable::to_bytes`. A committed `Batch` is a single
+//! record, so it is recovered entirely or not at all. On open, the log is replayed and truncated right
+//! after the last valid record, discarding the writes torn by a crash.
+//!
+//! The log is compacted, rewriting only the live items, when the overwritten or deleted records are
+//! more than the compaction threshold and than the live items. A failed automatic compaction does not
+//! fail the write triggering it, and is retried on the next write. Its error is kept until a compaction
+//! succeeds, and returned by `WalStore::last_compaction_error`.
+//!
+//! The log file is locked exclusively while a `WalStore` is open, so opening the same log twice fails
+//! instead of interleaving the writes of the two stores.
+
+use std::fs::{File, OpenOptions, TryLockError, rename};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::ffi::OsString;
+
+use base::Result;
+use base::Checkable;
+use base::Sizable;
+use base::Serializable;
+use base::Datable;
+use util::TimestampDiff;
+use io::Permission;
+use io::Session;
+use io::SessionManager;
+use io::store::{Store, Batch, BatchOp, Direction, EventSender};
+use io::store::memory::{MemoryStore, lock};
+
+/// Magic bytes starting a `WalStore` log.
+pub const WAL_MAGIC: &[u8] = b"MTRDWAL";
+
+/// Version of the `WalStore` log format.
+pub const WAL_VERSION: u8 = 1;
+
+/// Length of the `WalStore` log header.
+pub const WAL_HEADER_LEN: usize = 8;
+
+/// Length of the `WalStore` log records header.
+pub const WAL_RECORD_HEADER_LEN: usize = 8;
+
+/// Default number of overwritten or deleted records triggering a compaction.
+pub const DEFAULT_COMPACTION_THRESHOLD: u64 = 4096;
+
+/// Maximum number of operations written in a single record during a compaction.
+const COMPACTION_RECORD_LEN: usize = 1024;
+
+/// Type representing when a `WalStore` flushes its log to the disk.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Default, Hash, Serialize, Deserialize)]
+pub enum SyncPolicy {
+    /// Syncs the log after every write. Committed writes survive an operating system crash.
+    #[default]
+    Always,
+    /// Syncs the log every given number of writes.
+    Every(u64),
+    /// Leaves the log syncing to the operating system. Committed writes survive a process crash.
+    Never,
+}
+
+impl Sizable for SyncPolicy {
+    fn size(&self) -> u64 {
+        match self {
+            SyncPolicy::Every(writes) => 0u8.size() + writes.size(),
+            _ => 0u8.size(),
+        }
+    }
+}
+
+impl Checkable for SyncPolicy {
+    fn check(&self) -> Result<()> {
+        if let SyncPolicy::Every(0) = self {
+            return Err(String::from("invalid sync policy"));
+        }
+
+        Ok(())
+    }
+}
+
+impl Serializable for SyncPolicy {}
+
+impl Datable for SyncPolicy {}
+
+/// Append-only log of a `WalStore`.
+#[derive(Debug)]
+struct Log {
+    path: PathBuf,
+    file: File,
+    records_len: u64,
+    ops_len: u64,
+    unsynced: u64,
+    compaction_error: Option<String>,
+}
+
+impl Log {
+    /// Opens the log at a given path, creating it if missing, and returns it together with the
+    /// batches recovered from it. The log file is locked exclusively until the log is dropped.
+    fn open(path: &Path) -> Result<(Log, Vec<Batch>)> {
+        let created = !path.exists();
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)
+            .map_err(|e| format!("{}", e))?;
+
+        lock_file(&file)?;
+
+        let mut buf = Vec::new();
+
+        file.read_to_end(&mut buf)
+            .map_err(|e| format!("{}", e))?;
+
+        let (batches, valid_len) = if buf.len() < WAL_HEADER_LEN {
+            (Vec::new(), 0)
+        } else {
+            check_header(&buf)?;
+            read_records(&buf)
+        };
+
+        if valid_len < buf.len() {
+            file.set_len(valid_len as u64)
+                .map_err(|e| format!("{}", e))?;
+        }
+
+        if valid_len == 0 {
+            file.write_all(&header())
+                .map_err(|e| format!("{}", e))?;
+        }
+
+        file.sync_all()
+            .map_err(|e| format!("{}", e))?;
+
+        if created {
+            sync_dir(path)?;
+        }
+
+        let ops_len = batches.iter().map(|batch| batch.ops_len).sum();
+
+        let log = Log {
+            path: path.to_owned(),
+            file,
+            records_len: batches.len() as u64,
+            ops_len,
+            unsynced: 0,
+            compaction_error: None,
+        };
+
+        Ok((log, batches))
+    }
+
+    /// Appends a batch to the log, as a single record. On failure, the log is restored to its length
+    /// before the operation.
+    fn append(&mut self, batch: &Batch, policy: SyncPolicy) -> Result<()> {
+        let record = record(batch)?;
+
+        let len = self.file.metadata()
+            .map_err(|e| format!("{}", e))?
+            .len();
+
+        if let Err(e) = self.file.write_all(&record) {
+            let _ = self.file.set_len(len);
+            return Err(format!("{}", e));
+        }
+
+        self.records_len += 1;
+        self.ops_len += batch.ops_len;
+        self.unsynced += 1;
+
+        let sync = match policy {
+            SyncPolicy::Always => true,
+            SyncPolicy::Every(writes) => self.unsynced >= writes,
+            SyncPolicy::Never => false,
+        };
+
+        if sync {
+            self.sync()?;
+        }
+
+        Ok(())
+    }
+
+    /// Flushes the log to the disk.
+    fn sync(&mut self) -> Result<()> {
+        self.file.sync_data()
+            .map_err(|e| format!("{}", e))?;
+
+        self.unsynced = 0;
+
+        Ok(())
+    }
+
+    /// Rewrites the log with a given set of items. The new log is written to a temporary file, synced
+    /// and then renamed over the old one, so a crash leaves either the old or the new log. On failure,
+    /// the log is either left untouched or already replaced by the new one.
+    fn compact(&mut self, items: Vec<(Vec<u8>, Vec<u8>)>) -> Result<()> {
+        let mut tmp_path = OsString::from(self.path.as_os_str());
+        tmp_path.push(".compact");
+        let tmp_path = PathBuf::from(tmp_path);
+
+        let mut tmp_file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&tmp_path)
+            .map_err(|e| format!("{}", e))?;
+
+        lock_file(&tmp_file)?;
+
+        tmp_file.set_len(0)
+            .map_err(|e| format!("{}", e))?;
+
+        let mut buf = header();
+
+        let mut records_len = 0;
+        let ops_len = items.len() as u64;
+
+        for chunk in items.chunks(COMPACTION_RECORD_LEN) {
+            let mut batch = Batch::new();
+
+            for (key, value) in chunk.iter() {
+                batch.upsert(key, value)?;
+            }
+
+            buf.extend(record(&batch)?);
+            records_len += 1;
+        }
+
+        tmp_file.write_all(&buf)
+            .map_err(|e| format!("{}", e))?;
+
+        tmp_file.sync_all()
+            .map_err(|e| format!("{}", e))?;
+
+        rename(&tmp_path, &self.path)
+            .map_err(|e| format!("{}", e))?;
+
+        self.file = tmp_file;
+        self.records_len = records_len;
+        self.ops_len = ops_len;
+        self.unsynced = 0;
+
+        sync_dir(&self.path)
+    }
+
+    /// Compacts the log, keeping the error of a failed compaction until a compaction succeeds.
+    fn compact_or_keep_error(&mut self, items: Vec<(Vec<u8>, Vec<u8>)>) -> Result<()> {
+        let res = self.compact(items);
+
+        self.compaction_error = res.clone().err();
+
+        res
+    }
+}
+
+/// Type used to represent a durable store backed by a write-ahead log.
+#[derive(Clone, Debug)]
+pub struct WalStore<S>
+    where   S: Datable
+{
+    memory: MemoryStore<S>,
+    log: Arc<Mutex<Log>>,
+    sync_policy: SyncPolicy,
+    compaction_threshold: u64,
+}
+
+impl<S> WalStore<S>
+    where   S: Datable
+{
+    /// Opens the `WalStore` with the log at a given path, creating it if missing. The log is
+    /// replayed, and truncated after its last valid record.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let (log, batches) = Log::open(path.as_ref())?;
+
+        let memory = MemoryStore::new();
+
+        for batch in batches.iter() {
+            memory.commit_with(batch, |_| Ok(()))?;
+        }
+
+        let store = WalStore {
+            memory,
+            log: Arc::new(Mutex::new(log)),
+            sync_policy: SyncPolicy::default(),
+            compaction_threshold: DEFAULT_COMPACTION_THRESHOLD,
+        };
+
+        Ok(store)
+    }
+
+    /// Sets the duration of the sessions issued by the `WalStore`.
+    pub fn session_duration(mut self, duration: &TimestampDiff) -> Result<Self> {
+        self.memory = self.memory.session_duration(duration)?;
+
+        Ok(self)
+    }
+
+    /// Sets the `SessionManager` issuing and validating the sessions of the `WalStore`.
+    pub fn session_manager(mut self, manager: &SessionManager<S>) -> Self {
+        self.memory = self.memory.session_manager(manager);
+        self
+    }
+
+    /// Sets the `SyncPolicy` of the `WalStore` log.
+    pub fn sync_policy(mut self, policy: SyncPolicy) -> Result<Self> {
+        policy.check()?;
+
+        self.sync_policy = policy;
+
+        Ok(self)
+    }
+
+    /// Sets the number of overwritten or deleted records triggering a compaction of the log.
+    pub fn compaction_threshold(mut self, threshold: u64) -> Result<Self> {
+        if threshold == 0 {
+            return Err(String::from("invalid threshold"));
+        }
+
+        self.compaction_threshold = threshold;
+
+        Ok(self)
+    }
+
+    /// Returns the path of the `WalStore` log.
+    pub fn path(&self) -> PathBuf {
+        lock(&self.log).path.clone()
+    }
+
+    /// Returns the number of records in the `WalStore` log.
+    pub fn records_len(&self) -> u64 {
+        lock(&self.log).records_len
+    }
+
+    /// Returns the `SessionManager` of the `WalStore`, which can be used to renew and revoke its
+    /// sessions.
+    pub fn sessions(&self) -> SessionManager<S> {
+        self.memory.sessions()
+    }
+
+    /// Returns the number of live sessions.
+    pub fn sessions_len(&self) -> u64 {
+        self.memory.sessions_len()
+    }
+
+    /// Returns the number of items.
+    pub fn items_len(&self) -> u64 {
+        self.memory.items_len()
+    }
+
+    /// Returns the number of subscriptions to the items changes.
+    pub fn watchers_len(&self) -> u64 {
+        self.memory.watchers_len()
+    }
+
+    /// Removes the expired sessions.
+    pub fn clear_expired_sessions(&mut self) -> Result<()> {
+        self.memory.clear_expired_sessions()
+    }
+
+    /// Flushes the `WalStore` log to the disk.
+    pub fn sync(&mut self) -> Result<()> {
+        lock(&self.log).sync()
+    }
+
+    /// Returns the error of the last failed compaction of the `WalStore` log, if no compaction
+    /// succeeded since.
+    pub fn last_compaction_error(&self) -> Option<String> {
+        lock(&self.log).compaction_error.clone()
+    }
+
+    /// Compacts the `WalStore` log, rewriting only the live items.
+    pub fn compact(&mut self) -> Result<()> {
+        let log = &mut *lock(&self.log);
+
+        log.compact_or_keep_error(self.memory.items())
+    }
+}
+
+impl<S> Sizable for WalStore<S>
+    where   S: Datable
+{
+    fn size(&self) -> u64 {
+        self.memory.size()
+    }
+}
+
+impl<S> Checkable for WalStore<S>
+    where   S: Datable
+{
+    fn check(&self) -> Result<()> {
+        self.sync_policy.check()?;
+        self.memory.check()
+    }
+}
+
+impl<S> Store<S> for WalStore<S>
+    where   S: Datable + Serializable
+{
+    fn session(&mut self, permission: &Permission) -> Result<Session<S>> {
+        self.memory.session(permission)
+    }
+
+    fn count(&mut self,
+             session: &Session<S>,
+             from: Option<Vec<u8>>,
+             to: Option<Vec<u8>>)
+        -> Result<u64>
+    {
+        self.memory.count(session, from, to)
+    }
+
+    fn count_prefix(&mut self,
+                    session: &Session<S>,
+                    prefix: &[u8])
+        -> Result<u64>
+    {
+        self.memory.count_prefix(session, prefix)
+    }
+
+    fn list(&mut self,
+            session: &Session<S>,
+            from: Option<Vec<u8>>,
+            to: Option<Vec<u8>>,
+            count: Option<u64>,
+            skip: u64)
+        -> Result<Vec<Vec<u8>>>
+    {
+        self.memory.list(session, from, to, count, skip)
+    }
+
+    fn list_prefix(&mut self,
+                   session: &Session<S>,
+                   prefix: &[u8],
+                   count: Option<u64>,
+                   skip: u64)
+        -> Result<Vec<Vec<u8>>>
+    {
+        self.memory.list_prefix(session, prefix, count, skip)
+    }
+
+    fn scan(&mut self,
+            session: &Session<S>,
+            from: Option<Vec<u8>>,
+            to: Option<Vec<u8>>,
+            cursor: Option<Vec<u8>>,
+            direction: Direction,
+            count: u64)
+        -> Result<Vec<(Vec<u8>, Vec<u8>)>>
+    {
+        self.memory.scan(session, from, to, cursor, direction, count)
+    }
+
+    fn lookup(&mut self, session: &Session<S>, key: &[u8]) -> Result<bool> {
+        self.memory.lookup(session, key)
+    }
+
+    fn get(&mut self, session: &Session<S>, key: &[u8]) -> Result<Vec<u8>> {
+        self.memory.get(session, key)
+    }
+
+    fn create(&mut self, session: &Session<S>, key: &[u8], value: &[u8]) -> Result<()> {
+        let mut batch = Batch::new();
+        batch.create(key, value)?;
+
+        self.commit(session, &batch)
+    }
+
+    fn update(&mut self, session: &Session<S>, key: &[u8], value: &[u8]) -> Result<()> {
+        let mut batch = Batch::new();
+        batch.update(key, value)?;
+
+        self.commit(session, &batch)
+    }
+
+    fn upsert(&mut self, session: &Session<S>, key: &[u8], value: &[u8]) -> Result<()> {
+        let mut batch = Batch::new();
+        batch.upsert(key, value)?;
+
+        self.commit(session, &batch)
+    }
+
+    fn delete(&mut self, session: &Session<S>, key: &[u8]) -> Result<()> {
+        let mut batch = Batch::new();
+        batch.delete(key)?;
+
+        self.commit(session, &batch)
+    }
+
+    fn commit(&mut self, session: &Session<S>, batch: &Batch) -> Result<()> {
+        self.memory.check_write_session(session, batch)?;
+        batch.check()?;
+
+        let log = &mut *lock(&self.log);
+
+        let sync_policy = self.sync_policy;
+
+        self.memory.commit_with(batch, |staged| {
+            let mut record_batch = Batch::new();
+
+            for (key, state) in staged.iter() {
+                match state {
+                    Some(value) => record_batch.push(BatchOp::Upsert(key.to_owned(), value.to_owned()))?,
+                    None => record_batch.push(BatchOp::Delete(key.to_owned()))?,
+                }
+            }
+
+            log.append(&record_batch, sync_policy)
+        })?;
+
+        let garbage = log.ops_len.saturating_sub(self.memory.items_len());
+
+        if garbage >= self.compaction_threshold && garbage >= self.memory.items_len() {
+            // The batch is already durable and applied, so a failed compaction does not fail the
+            // commit: the log is left valid, the error is kept in the log and the compaction is
+            // retried on the next commit.
+            let _ = log.compact_or_keep_error(self.memory.items());
+        }
+
+        Ok(())
+    }
+
+    fn watch(&mut self, session: &Session<S>, prefix: &[u8], sender: &EventSender) -> Result<u64> {
+        self.memory.watch(session, prefix, sender)
+    }
+
+    fn unwatch(&mut self, session: &Session<S>, id: u64) -> Result<()> {
+        self.memory.unwatch(session, id)
+    }
+}
+
+/// Locks a `WalStore` log file exclusively, failing if it is already locked.
+fn lock_file(file: &File) -> Result<()> {
+    file.try_lock()
+        .map_err(|e| match e {
+            TryLockError::WouldBlock => String::from("log already open"),
+            TryLockError::Error(e) => format!("{}", e),
+        })
+}
+
+/// Syncs the directory containing a file, so that the file creation or renaming survives a crash.
+fn sync_dir(path: &Path) -> Result<()> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+
+    File::open(dir)
+        .and_then(|dir| dir.sync_all())
+        .map_err(|e| format!("{}", e))
+}
+
+/// Returns the header of a `WalStore` log.
+fn header() -> Vec<u8> {
+    let mut header = Vec::with_capacity(WAL_HEADER_LEN);
+
+    header.extend_from_slice(WAL_MAGIC);
+    header.push(WAL_VERSION);
+
+    header
+}
+
+/// Checks the header of a `WalStore` log.
+fn check_header(buf: &[u8]) -> Result<()> {
+    if &buf[..WAL_MAGIC.len()] != WAL_MAGIC {
+        return Err(String::from("invalid magic"));
+    }
+
+    if buf[WAL_MAGIC.len()] != WAL_VERSION {
+        return Err(String::from("invalid version"));
+    }
+
+    Ok(())
+}
+
+/// Encodes a batch as a `WalStore` log record.
+fn record(batch: &Batch) -> Result<Vec<u8>> {
+    let payload = batch.to_bytes()?;
+
+    if payload.len() > u32::MAX as usize {
+        return Err(String::from("invalid length"));
+    }
+
+    let mut record = Vec::with_capacity(WAL_RECORD_HEADER_LEN + payload.len());
+
+    record.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    record.extend_from_slice(&crc32(&payload).to_be_bytes());
+    record.extend(payload);
+
+    Ok(record)
+}
+
+/// Reads the valid records of a `WalStore` log, returning their batches and the length of the log up
+/// to the end of the last valid record.
+fn read_records(buf: &[u8]) -> (Vec<Batch>, usize) {
+    let mut batches = Vec::new();
+
+    let mut offset = WAL_HEADER_LEN;
+
+    while buf.len() - offset >= WAL_RECORD_HEADER_LEN {
+        let mut len = [0u8; 4];
+        len.copy_from_slice(&buf[offset..offset + 4]);
+        let len = u32::from_be_bytes(len) as usize;
+
+        let mut checksum = [0u8; 4];
+        checksum.copy_from_slice(&buf[offset + 4..offset + 8]);
+        let checksum = u32::from_be_bytes(checksum);
+
+        let start = offset + WAL_RECORD_HEADER_LEN;
+
+        if buf.len() - start < len {
+            break;
+        }
+
+        let payload = &buf[start..start + len];
+
+        if crc32(payload) != checksum {
+            break;
+        }
+
+        match Batch::from_bytes(payload) {
+            Ok(ref batch) if batch.check().is_ok() => batches.push(batch.to_owned()),
+            _ => break,
+        }
+
+        offset = start + len;
+    }
+
+    (batches, offset)
+}
+
+/// Computes the CRC-32 (IEEE) checksum of a buffer.
+fn crc32(buf: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+
+    for byte in buf {
+        crc ^= u32::from(*byte);
+
+        for _ in 0..8 {
+            let mask = (!(crc & 1)).wrapping_add(1);
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+
+    !crc
+}
\ No newline at end of file
@@ -0,0 +1,169 @@
+//! # Watch
+//!
+//! `watch` is the module providing the types used to subscribe to the changes of the store items
+//! with a given prefix. Every committed write is sent to the matching subscriptions as a `StoreEvent`
+//! over an `std::sync::mpsc` channel, in the same fashion as the application channels.
+
+use std::sync::mpsc::{Sender, Receiver, channel};
+use std::collections::BTreeMap;
+
+use base::Result;
+use base::Checkable;
+use base::Sizable;
+use base::Serializable;
+use base::Datable;
+
+/// Type representing a change of a store item.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash, Serialize, Deserialize)]
+pub enum StoreEvent {
+    /// An item has been created, with the given key and value.
+    Create(Vec<u8>, Vec<u8>),
+    /// An item has been updated, with the given key and new value.
+    Update(Vec<u8>, Vec<u8>),
+    /// An item has been deleted, with the given key.
+    Delete(Vec<u8>),
+}
+
+impl StoreEvent {
+    /// Returns the key of the item the `StoreEvent` refers to.
+    pub fn key(&self) -> &[u8] {
+        match self {
+            StoreEvent::Create(key, _) => key,
+            StoreEvent::Update(key, _) => key,
+            StoreEvent::Delete(key) => key,
+        }
+    }
+
+    /// Returns the new value of the item the `StoreEvent` refers to, if any.
+    pub fn value(&self) -> Option<&[u8]> {
+        match self {
+            StoreEvent::Create(_, value) => Some(value),
+            StoreEvent::Update(_, value) => Some(value),
+            StoreEvent::Delete(_) => None,
+        }
+    }
+}
+
+impl Default for StoreEvent {
+    fn default() -> StoreEvent {
+        StoreEvent::Delete(Vec::new())
+    }
+}
+
+impl Sizable for StoreEvent {
+    fn size(&self) -> u64 {
+        match self {
+            StoreEvent::Create(key, value) => 0u8.size() + key.size() + value.size(),
+            StoreEvent::Update(key, value) => 0u8.size() + key.size() + value.size(),
+            StoreEvent::Delete(key) => 0u8.size() + key.size(),
+        }
+    }
+}
+
+impl Checkable for StoreEvent {
+    fn check(&self) -> Result<()> {
+        if self.key().is_empty() {
+            return Err(String::from("invalid key"));
+        }
+
+        Ok(())
+    }
+}
+
+impl Serializable for StoreEvent {}
+
+impl Datable for StoreEvent {}
+
+/// Type used to send the `StoreEvent`s to a subscription.
+pub type EventSender = Sender<StoreEvent>;
+
+/// Type used to receive the `StoreEvent`s of a subscription.
+pub type EventReceiver = Receiver<StoreEvent>;
+
+/// Type used to represent a `StoreEvent` channel.
+#[derive(Debug)]
+pub struct EventChannel {
+    /// Event channel sender.
+    pub sender: EventSender,
+    /// Event channel receiver.
+    pub receiver: EventReceiver,
+}
+
+impl EventChannel {
+    /// Creates a new `EventChannel`.
+    pub fn new() -> Self {
+        let (sender, receiver) = channel();
+
+        EventChannel {
+            sender,
+            receiver,
+        }
+    }
+}
+
+impl Default for EventChannel {
+    fn default() -> Self {
+        EventChannel::new()
+    }
+}
+
+/// Type used to keep track of the subscriptions to the changes of the store items.
+#[derive(Debug, Default)]
+pub struct Watchers {
+    next_id: u64,
+    watches: BTreeMap<u64, (Vec<u8>, EventSender)>,
+}
+
+impl Watchers {
+    /// Creates a new `Watchers`.
+    pub fn new() -> Self {
+        Watchers::default()
+    }
+
+    /// Subscribes a sender to the changes of the items starting with a given prefix, returning the
+    /// id of the subscription.
+    pub fn watch(&mut self, prefix: &[u8], sender: &EventSender) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        self.watches.insert(id, (prefix.to_owned(), sender.to_owned()));
+
+        id
+    }
+
+    /// Removes a subscription.
+    pub fn unwatch(&mut self, id: u64) -> Result<()> {
+        match self.watches.remove(&id) {
+            Some(_) => Ok(()),
+            None => Err(String::from("not found")),
+        }
+    }
+
+    /// Returns the number of subscriptions.
+    pub fn len(&self) -> u64 {
+        self.watches.len() as u64
+    }
+
+    /// Returns if there are no subscriptions.
+    pub fn is_empty(&self) -> bool {
+        self.watches.is_empty()
+    }
+
+    /// Sends the events to the subscriptions with a matching prefix. Subscriptions whose receiver
+    /// has been dropped are removed.
+    pub fn notify(&mut self, events: &[StoreEvent]) {
+        self.watches.retain(|_, (prefix, sender)| {
+            for event in events.iter() {
+                if !event.key().starts_with(prefix) {
+                    continue;
+                }
+
+                if sender.send(event.to_owned()).is_err() {
+                    return false;
+                }
+            }
+
+            true
+        });
+    }
+}
\ No newline at end of file
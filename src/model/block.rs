@@ -3,8 +3,24 @@
 //! `block` is the module providing the type used to represent the (non-cryptographical) commitment
 //! to one or more `Transaction`s in the `BlockGraph`. Put differently, a `Block` is a bundle of
 //! transactions confirmed by one or more nodes.
-
-use std::mem;
+//!
+//! The `Block` commits to its transactions with the root of the `MerkleTree` of their ids, computed
+//! when the `Block` is finalized, so that the inclusion of a single `Transaction` can be proved with
+//! a `MerkleProof` without the rest of the `Block`.
+//!
+//! The `Block` id and proof are built on the `Block` header fields, so that they can be verified
+//! against a `BlockHeader` alone: the transactions are committed to only through the transactions
+//! root, which is computed before proving the `Block`. For this reason, `Block::verify_digest` and
+//! `Block::check_digest` verify the transactions root against the `Block` transactions too.
+//!
+//! A genesis `Block`, without previous blocks, has height 0, and any other `Block` has a height above
+//! 0. This breaks with the previous versions, where a `Block` without previous blocks had height 1:
+//! `Block::prev_blocks` now sets height 0 when there are no previous blocks, so a genesis `Block` built
+//! again gets a different id, and `Block::check` rejects a `Block` with previous blocks and height 0,
+//! or without previous blocks and a height above 1. The genesis `Block`s built by the previous
+//! versions, without previous blocks and with height 1, are still accepted as legacy genesis blocks
+//! (see `Block::is_legacy_genesis`), so that the stored `Block`s keep loading and connecting: their
+//! ids cover their height, so they cannot be migrated without building and proving their chain again.
 
 use base::Result;
 use base::Checkable;
@@ -15,13 +31,24 @@ use base::Numerical;
 use base::{Eval, EvalMut};
 use base::Meta;
 use crypto::{Hash, Prove};
+use crypto::{MerkleTree, MerkleProof};
 use io::{Store, Storable};
+use io::store::prefix::encode_prefix;
+use io::StoreKey;
+use io::IndexKey;
 use model::Transaction;
 use model::BlockNode;
+use model::BlockHeader;
 
 /// Code of the `Block` type.
 pub const BLOCK_CODE: u64 = 5;
 
+/// Name of the `Block` height index.
+pub const BLOCK_HEIGHT_INDEX: &str = "height";
+
+/// Name of the `Block` previous blocks index.
+pub const BLOCK_PREV_BLOCK_INDEX: &str = "prev_block";
+
 /// Type used to represent a bundle of confirmed `Transaction`s.
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default, Hash, Serialize, Deserialize)]
 pub struct Block<D, A, IP, OP, TP, P, Pr>
@@ -33,7 +60,7 @@ pub struct Block<D, A, IP, OP, TP, P, Pr>
             P: Datable,
             Pr: Datable
 {
-    /// Block id. It is the digest of the same coin, but with a default `D` id.
+    /// Block id. It is the digest of the `Block` header fields, with default `D` ids.
     pub id: D,
     /// Block metadata.
     pub meta: Meta,
@@ -47,6 +74,8 @@ pub struct Block<D, A, IP, OP, TP, P, Pr>
     pub transactions_len: u64,
     /// Block's transactions.
     pub transactions: Vec<Transaction<D, A, IP, OP, TP>>,
+    /// Root of the `MerkleTree` of the ids of the block's transactions.
+    pub transactions_root: D,
     /// Custom payload.
     pub payload: P,
     /// Proof of the block.
@@ -87,6 +116,17 @@ impl<D, A, IP, OP, TP, P, Pr> Block<D, A, IP, OP, TP, P, Pr>
         Ok(self)
     }
 
+    /// Returns if the `Block` is a genesis block, a block without previous blocks and with height 0.
+    pub fn is_genesis(&self) -> bool {
+        self.prev_blocks.is_empty() && self.height == 0
+    }
+
+    /// Returns if the `Block` is a legacy genesis block, a block without previous blocks and with
+    /// height 1, as built by the previous versions.
+    pub fn is_legacy_genesis(&self) -> bool {
+        self.prev_blocks.is_empty() && self.height == 1
+    }
+
     /// Sets the `Block`s set of previous blocks and its lenght.
     pub fn prev_blocks(mut self, prev_blocks: &Vec<BlockNode<D>>) -> Result<Self> {
         prev_blocks.check()?;
@@ -106,7 +146,7 @@ impl<D, A, IP, OP, TP, P, Pr> Block<D, A, IP, OP, TP, P, Pr>
             }
         }
 
-        self.height = prev_height + 1;
+        self.height = if prev_blocks.is_empty() { 0 } else { prev_height + 1 };
         self.prev_blocks_len = prev_blocks.len() as u64;
         self.prev_blocks = prev_blocks.clone();
 
@@ -145,14 +185,17 @@ impl<D, A, IP, OP, TP, P, Pr> Block<D, A, IP, OP, TP, P, Pr>
         Ok(self)
     }
 
-    /// Proves cryptographically the `Block`.
-    pub fn prove<Prv: Prove<Pr>>(mut self, prover: &mut Prv) -> Result<Self> {
-        let mut block = self.clone();
-        block.proof = Pr::default();
-        block.id = D::default();
-        block.update_size();
+    /// Proves cryptographically the `Block`, building its transactions root first.
+    pub fn prove<H, Prv>(mut self, hasher: &mut H, prover: &mut Prv) -> Result<Self>
+        where   H: Hash<D>,
+                Prv: Prove<Pr>,
+                D: Serializable,
+                P: Serializable,
+                Pr: Serializable
+    {
+        self.transactions_root = self.transactions_tree(hasher)?.root()?;
 
-        let msg = block.to_bytes()?;
+        let msg = self.header_data().block_message(false)?;
         self.proof = prover.prove(&msg)?;
 
         self.update_size();
@@ -161,37 +204,154 @@ impl<D, A, IP, OP, TP, P, Pr> Block<D, A, IP, OP, TP, P, Pr>
     }
 
     /// Verifies the cryptographic proof against the `Block`.
-    pub fn verify_proof<Prv: Prove<Pr>>(&self, prover: &mut Prv) -> Result<bool> {
-        let proof = self.proof.clone();
-        proof.check()?;
+    pub fn verify_proof<Prv: Prove<Pr>>(&self, prover: &mut Prv) -> Result<bool>
+        where   D: Serializable,
+                P: Serializable,
+                Pr: Serializable
+    {
+        self.header_data().verify_proof(prover)
+    }
 
-        let mut block = self.clone();
-        block.proof = Pr::default();
-        block.id = D::default();
-        block.update_size();
+    /// Checks the cryptographic proof against the `Block`.
+    pub fn check_proof<Prv: Prove<Pr>>(&self, prover: &mut Prv) -> Result<()>
+        where   D: Serializable,
+                P: Serializable,
+                Pr: Serializable
+    {
+        self.header_data().check_proof(prover)
+    }
+
+    /// Builds the `MerkleTree` of the ids of the `Block` transactions.
+    pub fn transactions_tree<H: Hash<D>>(&self, hasher: &mut H) -> Result<MerkleTree<D>>
+        where   D: Serializable
+    {
+        let ids: Vec<D> = self.transactions
+                            .iter()
+                            .map(|transaction| transaction.id.clone())
+                            .collect();
 
-        let msg = block.to_bytes()?;
-        prover.verify(&msg, &proof)
+        MerkleTree::new(hasher, &ids)
     }
 
-    /// Checks the cryptographic proof against the `Block`.
-    pub fn check_proof<Prv: Prove<Pr>>(&self, prover: &mut Prv) -> Result<()> {
-        let proof = self.proof.clone();
-        proof.check()?;
+    /// Verifies the transactions root against the `Block` transactions.
+    pub fn verify_transactions_root<H: Hash<D>>(&self, hasher: &mut H) -> Result<bool>
+        where   D: Serializable
+    {
+        let root = self.transactions_tree(hasher)?.root()?;
 
-        let mut block = self.clone();
-        block.proof = Pr::default();
-        block.id = D::default();
-        block.update_size();
+        Ok(root == self.transactions_root)
+    }
+
+    /// Checks the transactions root against the `Block` transactions.
+    pub fn check_transactions_root<H: Hash<D>>(&self, hasher: &mut H) -> Result<()>
+        where   D: Serializable
+    {
+        if !self.verify_transactions_root(hasher)? {
+            return Err(String::from("invalid transactions root"));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the `MerkleProof` of inclusion of a `Transaction` in the `Block`, given its id.
+    pub fn transaction_proof<H: Hash<D>>(&self, hasher: &mut H, transaction_id: &D) -> Result<MerkleProof<D>>
+        where   D: Serializable
+    {
+        let idx = self.transactions
+                    .iter()
+                    .position(|transaction| &transaction.id == transaction_id)
+                    .ok_or_else(|| String::from("not found"))?;
+
+        self.transactions_tree(hasher)?.proof(idx as u64)
+    }
+
+    /// Verifies the `MerkleProof` of inclusion of a `Transaction` in the `Block`, given its id.
+    pub fn verify_transaction_proof<H: Hash<D>>(&self,
+                                                hasher: &mut H,
+                                                transaction_id: &D,
+                                                proof: &MerkleProof<D>)
+        -> Result<bool>
+        where   D: Serializable
+    {
+        if proof.leaves_len != self.transactions_len {
+            return Ok(false);
+        }
+
+        proof.verify(hasher, transaction_id, &self.transactions_root)
+    }
+
+    /// Returns the finalized `BlockNode` referencing the `Block`.
+    pub fn node<H: Hash<D>>(&self, hasher: &mut H) -> Result<BlockNode<D>>
+        where   D: Serializable
+    {
+        BlockNode::new()
+            .meta(&self.meta)?
+            .block_data(&self.id, self.height)?
+            .finalize(hasher)
+    }
+
+    /// Returns the finalized `BlockHeader` of the `Block`.
+    pub fn header<H: Hash<D>>(&self, hasher: &mut H) -> Result<BlockHeader<D, P, Pr>>
+        where   D: Serializable,
+                P: Serializable,
+                Pr: Serializable
+    {
+        BlockHeader::new()
+            .meta(&self.meta)?
+            .block(self)?
+            .finalize(hasher)
+    }
+
+    /// Verifies the `Block` against a `BlockHeader`: the header digest, the `Block` id built on the
+    /// header, the `Block` transactions root, and the data shared by the `Block` and the header.
+    pub fn verify_header<H: Hash<D>>(&self, hasher: &mut H, header: &BlockHeader<D, P, Pr>) -> Result<bool>
+        where   D: Serializable,
+                P: Serializable,
+                Pr: Serializable
+    {
+        self.check()?;
+        header.check()?;
+
+        if !header.verify_digest(hasher)? || !header.verify_block_id(hasher)? {
+            return Ok(false);
+        }
 
-        let msg = block.to_bytes()?;
-        prover.check(&msg, &proof)
+        if !self.verify_transactions_root(hasher)? {
+            return Ok(false);
+        }
+
+        let matches = header.block_id == self.id &&
+            header.height == self.height &&
+            header.prev_blocks == self.prev_blocks &&
+            header.transactions_len == self.transactions_len &&
+            header.transactions_root == self.transactions_root &&
+            header.payload == self.payload &&
+            header.proof == self.proof;
+
+        Ok(matches)
+    }
+
+    /// Checks the `Block` against a `BlockHeader`.
+    pub fn check_header<H: Hash<D>>(&self, hasher: &mut H, header: &BlockHeader<D, P, Pr>) -> Result<()>
+        where   D: Serializable,
+                P: Serializable,
+                Pr: Serializable
+    {
+        if !self.verify_header(hasher, header)? {
+            return Err(String::from("invalid header"));
+        }
+
+        Ok(())
     }
 
-    /// Finalizes the `Block`, building its id and returning it's complete form.
-    pub fn finalize<H: Hash<D>>(mut self, hasher: &mut H) -> Result<Self> {
-        let msg = self.to_bytes()?;
-        self.id = hasher.digest(&msg)?;
+    /// Finalizes the `Block`, building its transactions root and its id and returning it's complete form.
+    pub fn finalize<H: Hash<D>>(mut self, hasher: &mut H) -> Result<Self>
+        where   D: Serializable,
+                P: Serializable,
+                Pr: Serializable
+    {
+        self.transactions_root = self.transactions_tree(hasher)?.root()?;
+        self.id = self.digest(hasher)?;
 
         self.update_size();
 
@@ -200,40 +360,69 @@ impl<D, A, IP, OP, TP, P, Pr> Block<D, A, IP, OP, TP, P, Pr>
         Ok(self)
     }
 
-    /// Hashes cryptographically the `Block`.
-    pub fn digest<H: Hash<D>>(&self, hasher: &mut H) -> Result<D> {
-        let mut block = self.clone();
-        block.id = D::default();
-        block.update_size();
+    /// Returns the `BlockHeader` data of the `Block`, without checks and ids.
+    fn header_data(&self) -> BlockHeader<D, P, Pr>
+        where   D: Serializable,
+                P: Serializable,
+                Pr: Serializable
+    {
+        let mut header = BlockHeader {
+            id: D::default(),
+            meta: self.meta.clone(),
+            block_id: D::default(),
+            height: self.height,
+            prev_blocks_len: self.prev_blocks_len,
+            prev_blocks: self.prev_blocks.clone(),
+            transactions_len: self.transactions_len,
+            transactions_root: self.transactions_root.clone(),
+            payload: self.payload.clone(),
+            proof: self.proof.clone(),
+        };
+
+        header.update_size();
+        header
+    }
 
-        let msg = block.to_bytes()?;
+    /// Hashes cryptographically the `Block` header fields.
+    pub fn digest<H: Hash<D>>(&self, hasher: &mut H) -> Result<D>
+        where   D: Serializable,
+                P: Serializable,
+                Pr: Serializable
+    {
+        let msg = self.header_data().block_message(true)?;
         hasher.digest(&msg)
     }
 
-    /// Verifies the cryptographic digest against the `Block`'s digest.
-    pub fn verify_digest<H: Hash<D>>(&self, hasher: &mut H) -> Result<bool> {
-        let digest = self.id.clone();
-        digest.check()?;
+    /// Verifies the cryptographic digest against the `Block`'s digest, and the transactions root
+    /// against the `Block` transactions.
+    pub fn verify_digest<H: Hash<D>>(&self, hasher: &mut H) -> Result<bool>
+        where   D: Serializable,
+                P: Serializable,
+                Pr: Serializable
+    {
+        let mut header = self.header_data();
+        header.block_id = self.id.clone();
 
-        let mut block = self.clone();
-        block.id = D::default();
-        block.update_size();
+        if !header.verify_block_id(hasher)? {
+            return Ok(false);
+        }
 
-        let msg = block.to_bytes()?;
-        hasher.verify(&msg, &digest)
+        self.verify_transactions_root(hasher)
     }
 
-    /// Checks the cryptographic digest against the `Block`'s digest.
-    pub fn check_digest<H: Hash<D>>(&self, hasher: &mut H) -> Result<()> {
-        let digest = self.id.clone();
-        digest.check()?;
+    /// Checks the cryptographic digest against the `Block`'s digest, and the transactions root
+    /// against the `Block` transactions.
+    pub fn check_digest<H: Hash<D>>(&self, hasher: &mut H) -> Result<()>
+        where   D: Serializable,
+                P: Serializable,
+                Pr: Serializable
+    {
+        let mut header = self.header_data();
+        header.block_id = self.id.clone();
 
-        let mut block = self.clone();
-        block.id = D::default();
-        block.update_size();
+        header.check_block_id(hasher)?;
 
-        let msg = block.to_bytes()?;
-        hasher.check(&msg, &digest)
+        self.check_transactions_root(hasher)
     }
 
     /// Evals the `Block`.
@@ -285,6 +474,7 @@ impl<D, A, IP, OP, TP, P, Pr> Sizable for Block<D, A, IP, OP, TP, P, Pr>
             self.prev_blocks.size() +
             self.transactions_len.size() +
             self.transactions.size() +
+            self.transactions_root.size() +
             self.payload.size() +
             self.proof.size()
     }
@@ -316,6 +506,14 @@ impl<D, A, IP, OP, TP, P, Pr> Checkable for Block<D, A, IP, OP, TP, P, Pr>
             return Err(String::from("invalid previous blocks length"));
         }
 
+        if self.prev_blocks.is_empty() {
+            if self.height > 1 {
+                return Err(String::from("invalid height"));
+            }
+        } else if self.height == 0 {
+            return Err(String::from("invalid height"));
+        }
+
         let mut unique_prev_blocks = self.prev_blocks.clone();
         unique_prev_blocks.dedup_by(|a, b| { a == b });
 
@@ -330,6 +528,9 @@ impl<D, A, IP, OP, TP, P, Pr> Checkable for Block<D, A, IP, OP, TP, P, Pr>
             return Err(String::from("invalid transactions length"));
         }
 
+        self.transactions_root.check()?;
+        self.transactions_root.check_size()?;
+
         let mut unique_transactions = self.transactions.clone();
         unique_transactions.dedup_by(|a, b| { a == b });
 
@@ -369,7 +570,7 @@ impl<St, S, D, A, IP, OP, TP, P, Pr>
     for Block<D, A, IP, OP, TP, P, Pr>
     where   St: Store<S>,
             S: Datable + Serializable,
-            D: Ord + Datable + ConstantSize + Serializable,
+            D: Ord + Datable + ConstantSize + Serializable + StoreKey,
             A: Numerical + Serializable,
             IP: Datable + Serializable,
             OP: Datable + Serializable,
@@ -378,12 +579,7 @@ impl<St, S, D, A, IP, OP, TP, P, Pr>
             Pr: Datable + Serializable
 {
     fn store_prefix() -> Vec<u8> {
-        let mut prefix = Vec::new();
-
-        let _prefix: [u8; 8] = unsafe { mem::transmute(BLOCK_CODE) };
-        prefix.extend_from_slice(&_prefix[..]);
-
-        prefix
+        encode_prefix(BLOCK_CODE)
     }
 
     fn store_key(&self) -> Result<D> {
@@ -397,4 +593,14 @@ impl<St, S, D, A, IP, OP, TP, P, Pr>
 
         Ok(self.clone())
     }
+
+    fn store_indexes(&self) -> Result<Vec<IndexKey>> {
+        let mut keys = vec![IndexKey::from_u64(BLOCK_HEIGHT_INDEX, self.height)?];
+
+        for prev_block in self.prev_blocks.iter() {
+            keys.push(IndexKey::from_key(BLOCK_PREV_BLOCK_INDEX, &prev_block.block_id)?);
+        }
+
+        Ok(keys)
+    }
 }
\ No newline at end of file
@@ -5,8 +5,28 @@
 //! referenced by the graph `BlockNode`s.
 //! An authenticated graph allows to represent different authenticated data structures
 //! (linked lists, trees, sets, etc), so it is a natural choice to keep the framework generic.
+//!
+//! The `BlockGraphEngine` maintains a `BlockGraph` backed by a `Store`. Ingested `Block`s are connected
+//! to the graph as `BlockNode`s once all their previous blocks are connected, and are kept as orphans
+//! until then. The frontier, its height and its tip, selected with a `TipRule`, are updated as blocks
+//! are connected, and the `BlockGraph` is stored in the same batch as the `Block`s and `BlockNode`s it
+//! references. Orphans that turn out to be invalid once their previous blocks are connected are
+//! deleted, together with their orphan descendants. The number of orphans is capped, by default at
+//! `BLOCKGRAPH_MAX_ORPHANS`: when the cap is reached, the orphans with the lowest height are evicted,
+//! with their orphan descendants, to make room for a new orphan.
+//!
+//! With `TipRule::Heaviest`, the subtree weights of the connected `Block`s are kept as `BlockWeight`s.
+//! Connecting a `Block` increases the weights of its ancestors, following the parent path as long as
+//! the `Block`s have a single previous block, and the updated `BlockWeight`s are stored in the same
+//! batch, so that opening a `BlockGraphEngine` loads them instead of walking the `Block`s.
+//!
+//! When the tip changes branch, the `Reorg` between the old and the new tip is applied to the states
+//! derived from the `Block`s through a `StateTransition`. If a `Block` cannot be applied, the state is
+//! moved back to the old tip, and the returned error reports if moving it back failed too.
 
-use std::mem;
+use std::marker::PhantomData;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 
 use base::Result;
 use base::Checkable;
@@ -16,12 +36,28 @@ use base::{Sizable, ConstantSize};
 use base::{Eval, EvalMut};
 use base::Meta;
 use crypto::Hash;
+use base::Numerical;
+use io::Permission;
+use io::Batch;
 use io::{Store, Storable};
+use io::store::prefix::encode_prefix;
+use io::StoreKey;
+use io::IndexKey;
 use model::BlockNode;
+use model::Block;
+use model::{Reorg, StateTransition};
+use model::blocknode::{BLOCKNODE_BLOCK_HEIGHT_INDEX, BLOCKNODE_BLOCK_ID_INDEX};
+use model::block::BLOCK_PREV_BLOCK_INDEX;
 
 /// Code of the `BlockGraph` type.
 pub const BLOCKGRAPH_CODE: u64 = 6;
 
+/// Code of the `BlockWeight` type.
+pub const BLOCKWEIGHT_CODE: u64 = 13;
+
+/// Default maximum number of orphan `Block`s kept by a `BlockGraphEngine`.
+pub const BLOCKGRAPH_MAX_ORPHANS: u64 = 1024;
+
 /// Type representing a graph of `BlockNodes`. It just expose the graph frontier, from which
 /// one can span the entire graph after following the `BlockNode`s' `Block`s `prev_block_id` links.
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default, Hash, Serialize, Deserialize)]
@@ -274,16 +310,11 @@ impl<St, S, D, P>
     for BlockGraph<D, P>
     where   St: Store<S>,
             S: Datable + Serializable,
-            D: Ord + Datable + ConstantSize + Serializable,
+            D: Ord + Datable + ConstantSize + Serializable + StoreKey,
             P: Datable + Serializable
 {
     fn store_prefix() -> Vec<u8> {
-        let mut prefix = Vec::new();
-
-        let _prefix: [u8; 8] = unsafe { mem::transmute(BLOCKGRAPH_CODE) };
-        prefix.extend_from_slice(&_prefix[..]);
-
-        prefix
+        encode_prefix(BLOCKGRAPH_CODE)
     }
 
     fn store_key(&self) -> Result<D> {
@@ -297,4 +328,1131 @@ impl<St, S, D, P>
 
         Ok(self.clone())
     }
+}
+
+/// Rule used to select the tip of the `BlockGraph` frontier.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Default, Hash, Serialize, Deserialize)]
+pub enum TipRule {
+    /// The tip is the highest `BlockNode` of the frontier.
+    #[default]
+    Longest,
+    /// The tip is the `BlockNode` reached from the genesis following the heaviest subtrees (GHOST).
+    Heaviest,
+    /// The tip is selected with an evaluator through `BlockGraphEngine::select_tip_eval`, and it is
+    /// kept as long as it is in the frontier.
+    Custom,
+}
+
+impl Sizable for TipRule {
+    fn size(&self) -> u64 {
+        0u8.size()
+    }
+}
+
+impl Checkable for TipRule {}
+
+impl Serializable for TipRule {}
+
+impl Datable for TipRule {}
+
+/// Type representing the subtree weight of a connected `Block`, used by `TipRule::Heaviest`: the
+/// number of its connected descendants plus one.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default, Hash, Serialize, Deserialize)]
+pub struct BlockWeight<D>
+    where   D: Ord + Datable + ConstantSize
+{
+    /// `BlockNode` of the weighted `Block`.
+    pub node: BlockNode<D>,
+    /// Ids of the previous blocks of the weighted `Block`.
+    pub parents: Vec<D>,
+    /// Subtree weight of the weighted `Block`.
+    pub weight: u64,
+}
+
+impl<D> BlockWeight<D>
+    where   D: Ord + Datable + ConstantSize
+{
+    /// Creates a new `BlockWeight` of a `Block` without connected descendants.
+    pub fn new(node: &BlockNode<D>, parents: &[D]) -> Self {
+        BlockWeight {
+            node: node.to_owned(),
+            parents: parents.to_owned(),
+            weight: 1,
+        }
+    }
+}
+
+impl<D> Sizable for BlockWeight<D>
+    where   D: Ord + Datable + ConstantSize
+{
+    fn size(&self) -> u64 {
+        self.node.size() +
+            self.parents.size() +
+            self.weight.size()
+    }
+}
+
+impl<D> Checkable for BlockWeight<D>
+    where   D: Ord + Datable + ConstantSize
+{
+    fn check(&self) -> Result<()> {
+        self.node.check()?;
+
+        for parent in self.parents.iter() {
+            parent.check()?;
+            parent.check_size()?;
+        }
+
+        if self.weight == 0 {
+            return Err(String::from("invalid weight"));
+        }
+
+        Ok(())
+    }
+}
+
+impl<D> Serializable for BlockWeight<D>
+    where   D: Ord + Datable + ConstantSize + Serializable
+{}
+
+impl<D> Datable for BlockWeight<D>
+    where   D: Ord + Datable + ConstantSize
+{}
+
+impl<St, S, D>
+    Storable<St, S, D, BlockWeight<D>>
+    for BlockWeight<D>
+    where   St: Store<S>,
+            S: Datable + Serializable,
+            D: Ord + Datable + ConstantSize + Serializable + StoreKey
+{
+    fn store_prefix() -> Vec<u8> {
+        encode_prefix(BLOCKWEIGHT_CODE)
+    }
+
+    fn store_key(&self) -> Result<D> {
+        self.node.block_id.check()?;
+
+        Ok(self.node.block_id.clone())
+    }
+
+    fn store_value(&self) -> Result<Self> {
+        self.check()?;
+
+        Ok(self.clone())
+    }
+}
+
+/// Type used to maintain a `BlockGraph` of `Block`s stored in a `Store`.
+pub struct BlockGraphEngine<St, S, D, A, IP, OP, TP, BP, BPr, P>
+    where   St: Store<S>,
+            S: Datable + Serializable,
+            D: Ord + Datable + ConstantSize + Serializable + StoreKey,
+            A: Numerical + Serializable,
+            IP: Datable + Serializable,
+            OP: Datable + Serializable,
+            TP: Datable + Serializable,
+            BP: Datable + Serializable,
+            BPr: Datable + Serializable,
+            P: Datable + Serializable
+{
+    store: St,
+    graph: BlockGraph<D, P>,
+    tip_rule: TipRule,
+    max_orphans: u64,
+    orphans: Option<BTreeSet<(u64, D)>>,
+    weights: Weights<D>,
+    _marker: PhantomData<(S, A, IP, OP, TP, BP, BPr)>,
+}
+
+impl<St, S, D, A, IP, OP, TP, BP, BPr, P> BlockGraphEngine<St, S, D, A, IP, OP, TP, BP, BPr, P>
+    where   St: Store<S>,
+            S: Datable + Serializable,
+            D: Ord + Datable + ConstantSize + Serializable + StoreKey,
+            A: Numerical + Serializable,
+            IP: Datable + Serializable,
+            OP: Datable + Serializable,
+            TP: Datable + Serializable,
+            BP: Datable + Serializable,
+            BPr: Datable + Serializable,
+            P: Datable + Serializable
+{
+    /// Creates a new `BlockGraphEngine` with an empty `BlockGraph`, backed by a `Store`.
+    pub fn new(store: St, tip_rule: &TipRule) -> Self {
+        BlockGraphEngine {
+            store,
+            graph: BlockGraph::new(),
+            tip_rule: *tip_rule,
+            max_orphans: BLOCKGRAPH_MAX_ORPHANS,
+            orphans: None,
+            weights: Weights::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Opens a `BlockGraphEngine` on a `BlockGraph` stored in a `Store`.
+    pub fn open(mut store: St, tip_rule: &TipRule, graph_id: &D) -> Result<Self> {
+        let graph = BlockGraph::store_get(&mut store, graph_id)?;
+
+        let mut engine = BlockGraphEngine {
+            store,
+            graph,
+            tip_rule: *tip_rule,
+            max_orphans: BLOCKGRAPH_MAX_ORPHANS,
+            orphans: None,
+            weights: Weights::new(),
+            _marker: PhantomData,
+        };
+
+        if engine.tip_rule == TipRule::Heaviest {
+            engine.load_weights()?;
+        }
+
+        Ok(engine)
+    }
+
+    /// Returns the `Store` backing the `BlockGraphEngine`.
+    pub fn store(&mut self) -> &mut St {
+        &mut self.store
+    }
+
+    /// Consumes the `BlockGraphEngine`, returning its `Store`.
+    pub fn into_store(self) -> St {
+        self.store
+    }
+
+    /// Returns the `BlockGraph` maintained by the `BlockGraphEngine`.
+    pub fn graph(&self) -> &BlockGraph<D, P> {
+        &self.graph
+    }
+
+    /// Returns the `TipRule` of the `BlockGraphEngine`.
+    pub fn tip_rule(&self) -> TipRule {
+        self.tip_rule
+    }
+
+    /// Returns the maximum number of orphan `Block`s of the `BlockGraphEngine`.
+    pub fn max_orphans(&self) -> u64 {
+        self.max_orphans
+    }
+
+    /// Sets the maximum number of orphan `Block`s of the `BlockGraphEngine`.
+    pub fn set_max_orphans(&mut self, max_orphans: u64) {
+        self.max_orphans = max_orphans;
+    }
+
+    /// Returns the tip of the `BlockGraph`, if any.
+    pub fn tip(&self) -> Option<BlockNode<D>> {
+        self.graph.tip_idx.map(|idx| self.graph.frontier[idx as usize].clone())
+    }
+
+    /// Returns the `BlockNode` of the genesis `Block`, the only connected block without previous blocks.
+    /// A legacy genesis `Block`, with height 1, is looked up if there is no `Block` with height 0.
+    pub fn genesis(&mut self) -> Result<BlockNode<D>> {
+        let index_key = IndexKey::from_u64(BLOCKNODE_BLOCK_HEIGHT_INDEX, 0)?;
+
+        let mut nodes = BlockNode::store_get_by_index(&mut self.store, &index_key)?;
+
+        if nodes.is_empty() {
+            let index_key = IndexKey::from_u64(BLOCKNODE_BLOCK_HEIGHT_INDEX, 1)?;
+
+            for node in BlockNode::store_get_by_index(&mut self.store, &index_key)?.into_iter() {
+                if self.block(&node.block_id)?.is_legacy_genesis() {
+                    nodes.push(node);
+                }
+            }
+        }
+
+        if nodes.len() != 1 {
+            return Err(String::from("not found"));
+        }
+
+        Ok(nodes.remove(0))
+    }
+
+    /// Lookups a `Block` connected to the `BlockGraph` from its id.
+    pub fn lookup(&mut self, block_id: &D) -> Result<bool> {
+        let index_key = IndexKey::from_key(BLOCKNODE_BLOCK_ID_INDEX, block_id)?;
+
+        let nodes = BlockNode::<D>::store_get_by_index(&mut self.store, &index_key)?;
+
+        Ok(!nodes.is_empty())
+    }
+
+    /// Retrieves the `BlockNode` of a `Block` connected to the `BlockGraph`.
+    pub fn node(&mut self, block_id: &D) -> Result<BlockNode<D>> {
+        let index_key = IndexKey::from_key(BLOCKNODE_BLOCK_ID_INDEX, block_id)?;
+
+        let mut nodes = BlockNode::store_get_by_index(&mut self.store, &index_key)?;
+
+        if nodes.is_empty() {
+            return Err(String::from("not found"));
+        }
+
+        Ok(nodes.remove(0))
+    }
+
+    /// Retrieves a `Block`, connected or orphan, from its id.
+    pub fn block(&mut self, block_id: &D) -> Result<Block<D, A, IP, OP, TP, BP, BPr>> {
+        Block::store_get(&mut self.store, block_id)
+    }
+
+    /// Returns if a `Block` is stored, but not connected to the `BlockGraph`.
+    pub fn is_orphan(&mut self, block_id: &D) -> Result<bool> {
+        if !Block::<D, A, IP, OP, TP, BP, BPr>::store_lookup(&mut self.store, block_id)? {
+            return Ok(false);
+        }
+
+        Ok(!self.lookup(block_id)?)
+    }
+
+    /// Returns the ids of the orphan `Block`s, waiting for their previous blocks to be connected,
+    /// ordered by height.
+    pub fn orphans(&mut self) -> Result<Vec<D>> {
+        let orphans = self.orphans_set()?;
+
+        Ok(orphans.iter().map(|(_, id)| id.clone()).collect())
+    }
+
+    /// Returns the `BlockNode`s of the previous blocks of a connected `Block`.
+    pub fn parents(&mut self, block_id: &D) -> Result<Vec<BlockNode<D>>> {
+        if !self.lookup(block_id)? {
+            return Err(String::from("not found"));
+        }
+
+        Ok(self.block(block_id)?.prev_blocks)
+    }
+
+    /// Returns the `BlockNode`s of the connected `Block`s having a connected `Block` as previous block.
+    pub fn children(&mut self, block_id: &D) -> Result<Vec<BlockNode<D>>> {
+        if !self.lookup(block_id)? {
+            return Err(String::from("not found"));
+        }
+
+        let index_key = IndexKey::from_key(BLOCK_PREV_BLOCK_INDEX, block_id)?;
+
+        let blocks = Block::<D, A, IP, OP, TP, BP, BPr>::store_get_by_index(&mut self.store, &index_key)?;
+
+        let mut children = Vec::new();
+
+        for block in blocks.iter() {
+            if self.lookup(&block.id)? {
+                children.push(self.node(&block.id)?);
+            }
+        }
+
+        sort_nodes(&mut children);
+
+        Ok(children)
+    }
+
+    /// Returns the `BlockNode`s of all the ancestors of a connected `Block`, ordered by height.
+    pub fn ancestors(&mut self, block_id: &D) -> Result<Vec<BlockNode<D>>> {
+        self.traverse(block_id, |engine, id| engine.parents(id))
+    }
+
+    /// Returns the `BlockNode`s of all the descendants of a connected `Block`, ordered by height.
+    pub fn descendants(&mut self, block_id: &D) -> Result<Vec<BlockNode<D>>> {
+        self.traverse(block_id, |engine, id| engine.children(id))
+    }
+
+    /// Adds a `Block` to the `BlockGraph`, returning the `BlockNode`s of the connected blocks: the
+    /// `Block` and the orphans it connects, or none if the `Block` is an orphan.
+    pub fn add_block<H: Hash<D>>(&mut self, hasher: &mut H, block: &Block<D, A, IP, OP, TP, BP, BPr>)
+        -> Result<Vec<BlockNode<D>>>
+    {
+        block.check()?;
+        block.check_digest(hasher)?;
+        block.check_transactions_root(hasher)?;
+
+        if Block::<D, A, IP, OP, TP, BP, BPr>::store_lookup(&mut self.store, &block.id)? {
+            return Err(String::from("already found"));
+        }
+
+        let mut staged = BTreeMap::new();
+        let mut connected = Vec::new();
+        let mut invalid = BTreeMap::new();
+        let mut queue = VecDeque::new();
+
+        match self.connection(&staged, block)? {
+            Connection::Connectable => queue.push_back(block.clone()),
+            Connection::Missing => {},
+            Connection::Invalid(err) => return Err(err),
+        }
+
+        while let Some(next) = queue.pop_front() {
+            let node = next.node(hasher)?;
+
+            staged.insert(next.id.clone(), node.clone());
+            connected.push((node, next.prev_blocks.clone()));
+
+            let index_key = IndexKey::from_key(BLOCK_PREV_BLOCK_INDEX, &next.id)?;
+
+            let children = Block::<D, A, IP, OP, TP, BP, BPr>::store_get_by_index(&mut self.store, &index_key)?;
+
+            for child in children.into_iter() {
+                if staged.contains_key(&child.id) || invalid.contains_key(&child.id) || self.lookup(&child.id)? {
+                    continue;
+                }
+
+                match self.connection(&staged, &child)? {
+                    Connection::Connectable => queue.push_back(child),
+                    Connection::Missing => {},
+                    Connection::Invalid(_) => self.orphan_subtree(child, &mut invalid)?,
+                }
+            }
+        }
+
+        if connected.is_empty() {
+            self.add_orphan(block)?;
+
+            return Ok(Vec::new());
+        }
+
+        let mut weights = Vec::new();
+
+        if self.tip_rule == TipRule::Heaviest {
+            let mut updated = BTreeSet::new();
+
+            for (node, prev_blocks) in connected.iter() {
+                updated.extend(self.weights.link(node, prev_blocks));
+                updated.insert(node.block_id.clone());
+            }
+
+            weights = updated.iter().filter_map(|id| self.weights.get(id).cloned()).collect();
+        }
+
+        let invalid: Vec<Block<D, A, IP, OP, TP, BP, BPr>> = invalid.into_values().collect();
+
+        if let Err(err) = self.connect(hasher, block, &connected, &weights, &invalid) {
+            if self.tip_rule == TipRule::Heaviest {
+                for (node, _) in connected.iter().rev() {
+                    self.weights.unlink(node);
+                }
+            }
+
+            return Err(err);
+        }
+
+        if let Some(ref mut orphans) = self.orphans {
+            for (node, _) in connected.iter() {
+                orphans.remove(&(node.block_height, node.block_id.clone()));
+            }
+
+            for orphan in invalid.iter() {
+                orphans.remove(&(orphan.height, orphan.id.clone()));
+            }
+        }
+
+        Ok(connected.into_iter().map(|(node, _)| node).collect())
+    }
+
+    /// Selects the tip of the `BlockGraph` with an evaluator returning the tip index in the frontier.
+    /// The tip is kept until it leaves the frontier if the `TipRule` is `TipRule::Custom`.
+    pub fn select_tip_eval<H, Ev, EP>(&mut self, hasher: &mut H, params: &EP, evaluator: &Ev)
+        -> Result<Option<BlockNode<D>>>
+        where   H: Hash<D>,
+                Ev: Eval<BlockGraph<D, P>, EP, Option<u64>>,
+                EP: Datable
+    {
+        let tip_idx = self.graph.eval(params, evaluator)?;
+
+        let mut graph = self.graph.clone().frontier(tip_idx, &self.graph.frontier)?;
+        graph.id = D::default();
+        graph = graph.finalize(hasher)?;
+
+        let session = self.store.session(&Permission::Write)?;
+
+        let mut batch = self.store.begin(&session)?;
+
+        self.stage_graph(&mut batch, &graph)?;
+
+        self.store.commit(&session, &batch)?;
+
+        self.graph = graph;
+
+        Ok(self.tip())
+    }
+
+    /// Computes the `Reorg` moving the state derived from the `Block`s from a tip to another.
+    /// A missing tip has no `Block`s. The `BlockGraph` is walked back from both tips by height until
+    /// the walks meet, so only the `Block`s above the fork are visited.
+    pub fn reorg(&mut self, from: Option<&BlockNode<D>>, to: Option<&BlockNode<D>>) -> Result<Reorg<D>> {
+        let mut walk = Walk::new();
+
+        if let Some(node) = from {
+            self.check_node(node)?;
+            walk.reach(node, true, false);
+        }
+
+        if let Some(node) = to {
+            self.check_node(node)?;
+            walk.reach(node, false, true);
+        }
+
+        let mut from_nodes = Vec::new();
+        let mut to_nodes = Vec::new();
+        let mut fork = None;
+
+        while walk.pending > 0 {
+            let (node, is_from, is_to) = match walk.next() {
+                Some(next) => next,
+                None => break,
+            };
+
+            if is_from && is_to {
+                if fork.is_none() {
+                    fork = Some(node.clone());
+                }
+            } else if is_from {
+                from_nodes.push(node.clone());
+            } else {
+                to_nodes.push(node.clone());
+            }
+
+            for parent in self.parents(&node.block_id)?.iter() {
+                walk.reach(parent, is_from, is_to);
+            }
+        }
+
+        if fork.is_none() {
+            fork = walk.next().map(|(node, _, _)| node);
+        }
+
+        if let Some(fork) = fork {
+            from_nodes.push(fork.clone());
+            to_nodes.push(fork);
+        }
+
+        Reorg::new(&from_nodes, &to_nodes)
+    }
+
+    /// Applies a `Reorg` to a state, reverting the `Block`s to undo and applying the ones to redo.
+    /// If a `Block` cannot be reverted or applied, the `Block`s already reverted or applied are
+    /// rolled back before returning the error. If the rollback fails too, the state is left between
+    /// the two tips and the error reports both failures.
+    pub fn apply_reorg<H, T>(&mut self, hasher: &mut H, reorg: &Reorg<D>, state: &mut T) -> Result<()>
+        where   H: Hash<D>,
+                T: StateTransition<D, A, IP, OP, TP, BP, BPr>
+    {
+        reorg.check()?;
+
+        let mut undo = Vec::new();
+
+        for node in reorg.undo.iter() {
+            undo.push(self.block(&node.block_id)?);
+        }
+
+        let mut redo = Vec::new();
+
+        for node in reorg.redo.iter() {
+            redo.push(self.block(&node.block_id)?);
+        }
+
+        for (idx, block) in undo.iter().enumerate() {
+            if let Err(err) = state.revert_block(hasher, block) {
+                let rollback = rollback(hasher, state, &[], &undo[..idx]);
+
+                return Err(reorg_error(err, rollback));
+            }
+        }
+
+        for (idx, block) in redo.iter().enumerate() {
+            if let Err(err) = state.apply_block(hasher, block) {
+                let rollback = rollback(hasher, state, &redo[..idx], &undo);
+
+                return Err(reorg_error(err, rollback));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Moves a state from a previous tip to the current tip of the `BlockGraph`, returning the
+    /// applied `Reorg`.
+    pub fn transition<H, T>(&mut self, hasher: &mut H, from: Option<&BlockNode<D>>, state: &mut T)
+        -> Result<Reorg<D>>
+        where   H: Hash<D>,
+                T: StateTransition<D, A, IP, OP, TP, BP, BPr>
+    {
+        let tip = self.tip();
+
+        let reorg = self.reorg(from, tip.as_ref())?;
+        self.apply_reorg(hasher, &reorg, state)?;
+
+        Ok(reorg)
+    }
+
+    /// Checks that a `BlockNode` is the one of a connected `Block`.
+    fn check_node(&mut self, node: &BlockNode<D>) -> Result<()> {
+        node.check()?;
+
+        if &self.node(&node.block_id)? != node {
+            return Err(String::from("invalid node"));
+        }
+
+        Ok(())
+    }
+
+    /// Visits the `BlockNode`s reachable from a connected `Block` through a neighbours function.
+    fn traverse<F>(&mut self, block_id: &D, neighbours: F) -> Result<Vec<BlockNode<D>>>
+        where   F: Fn(&mut Self, &D) -> Result<Vec<BlockNode<D>>>
+    {
+        let mut visited = BTreeSet::new();
+        let mut nodes = Vec::new();
+        let mut queue = VecDeque::new();
+
+        queue.push_back(block_id.clone());
+
+        while let Some(id) = queue.pop_front() {
+            for node in neighbours(self, &id)?.into_iter() {
+                if visited.insert(node.block_id.clone()) {
+                    queue.push_back(node.block_id.clone());
+                    nodes.push(node);
+                }
+            }
+        }
+
+        sort_nodes(&mut nodes);
+
+        Ok(nodes)
+    }
+
+    /// Returns the heights and the ids of the orphan `Block`s, loading them from the `Store` on
+    /// first use.
+    fn orphans_set(&mut self) -> Result<&BTreeSet<(u64, D)>> {
+        if self.orphans.is_none() {
+            let blocks = Block::<D, A, IP, OP, TP, BP, BPr>::store_list(&mut self.store, None, None, None, 0)?;
+
+            let mut orphans = BTreeSet::new();
+
+            for block in blocks.into_iter() {
+                if !self.lookup(&block.id)? {
+                    orphans.insert((block.height, block.id));
+                }
+            }
+
+            self.orphans = Some(orphans);
+        }
+
+        Ok(self.orphans.get_or_insert_with(BTreeSet::new))
+    }
+
+    /// Stores an orphan `Block`. If the orphans cap is reached, the orphans with the lowest height are
+    /// evicted, together with their orphan descendants, in the same batch.
+    fn add_orphan(&mut self, block: &Block<D, A, IP, OP, TP, BP, BPr>) -> Result<()> {
+        if self.max_orphans == 0 {
+            return Err(String::from("too many orphans"));
+        }
+
+        let max_orphans = self.max_orphans;
+        let mut evicted = BTreeMap::new();
+
+        loop {
+            let oldest = {
+                let orphans = self.orphans_set()?;
+
+                if (orphans.len().saturating_sub(evicted.len()) as u64) < max_orphans {
+                    break;
+                }
+
+                orphans.iter()
+                    .find(|(_, id)| !evicted.contains_key(id))
+                    .map(|(_, id)| id.clone())
+            };
+
+            match oldest {
+                Some(id) => {
+                    let orphan = self.block(&id)?;
+                    self.orphan_subtree(orphan, &mut evicted)?;
+                },
+                None => break,
+            }
+        }
+
+        let session = self.store.session(&Permission::Write)?;
+
+        let mut batch = self.store.begin(&session)?;
+
+        for orphan in evicted.values() {
+            orphan.store_batch_delete(&mut self.store, &mut batch)?;
+        }
+
+        block.store_batch_create(&mut self.store, &mut batch)?;
+
+        self.store.commit(&session, &batch)?;
+
+        if let Some(ref mut orphans) = self.orphans {
+            for orphan in evicted.values() {
+                orphans.remove(&(orphan.height, orphan.id.clone()));
+            }
+
+            orphans.insert((block.height, block.id.clone()));
+        }
+
+        Ok(())
+    }
+
+    /// Collects an orphan `Block` together with its orphan descendants, which cannot be connected
+    /// without it.
+    fn orphan_subtree(&mut self,
+                      orphan: Block<D, A, IP, OP, TP, BP, BPr>,
+                      subtree: &mut BlocksById<D, A, IP, OP, TP, BP, BPr>)
+        -> Result<()>
+    {
+        let mut queue = VecDeque::new();
+        queue.push_back(orphan);
+
+        while let Some(next) = queue.pop_front() {
+            if subtree.contains_key(&next.id) {
+                continue;
+            }
+
+            let index_key = IndexKey::from_key(BLOCK_PREV_BLOCK_INDEX, &next.id)?;
+
+            let children = Block::<D, A, IP, OP, TP, BP, BPr>::store_get_by_index(&mut self.store, &index_key)?;
+
+            queue.extend(children);
+
+            subtree.insert(next.id.clone(), next);
+        }
+
+        Ok(())
+    }
+
+    /// Returns if a `Block` can be connected, given the `BlockNode`s staged to be connected.
+    fn connection(&mut self,
+                  staged: &BTreeMap<D, BlockNode<D>>,
+                  block: &Block<D, A, IP, OP, TP, BP, BPr>)
+        -> Result<Connection>
+    {
+        if block.is_genesis() || block.is_legacy_genesis() {
+            if !self.graph.frontier.is_empty() || !staged.is_empty() {
+                return Ok(Connection::Invalid(String::from("invalid previous blocks")));
+            }
+
+            return Ok(Connection::Connectable);
+        }
+
+        let mut height = 0;
+
+        for prev_block in block.prev_blocks.iter() {
+            let node = if let Some(node) = staged.get(&prev_block.block_id) {
+                node.clone()
+            } else if self.lookup(&prev_block.block_id)? {
+                self.node(&prev_block.block_id)?
+            } else {
+                return Ok(Connection::Missing);
+            };
+
+            if &node != prev_block {
+                return Ok(Connection::Invalid(String::from("invalid previous blocks")));
+            }
+
+            if node.block_height > height {
+                height = node.block_height;
+            }
+        }
+
+        if block.height != height + 1 {
+            return Ok(Connection::Invalid(String::from("invalid height")));
+        }
+
+        Ok(Connection::Connectable)
+    }
+
+    /// Stores a `Block`, the `BlockNode`s it connects, the updated `BlockWeight`s and the updated
+    /// `BlockGraph` in a single batch, deleting the orphans that cannot be connected.
+    fn connect<H: Hash<D>>(&mut self,
+                           hasher: &mut H,
+                           block: &Block<D, A, IP, OP, TP, BP, BPr>,
+                           connected: &[(BlockNode<D>, Vec<BlockNode<D>>)],
+                           weights: &[BlockWeight<D>],
+                           invalid: &[Block<D, A, IP, OP, TP, BP, BPr>])
+        -> Result<()>
+    {
+        let mut frontier = self.graph.frontier.clone();
+
+        for (node, prev_blocks) in connected.iter() {
+            frontier.retain(|frontier_node| !prev_blocks.contains(frontier_node));
+            frontier.push(node.clone());
+        }
+
+        let graph = self.next_graph(hasher, &frontier)?;
+
+        let session = self.store.session(&Permission::Write)?;
+
+        let mut batch = self.store.begin(&session)?;
+
+        block.store_batch_create(&mut self.store, &mut batch)?;
+
+        for (node, _) in connected.iter() {
+            node.store_batch_create(&mut self.store, &mut batch)?;
+        }
+
+        for weight in weights.iter() {
+            weight.store_batch_upsert(&mut self.store, &mut batch)?;
+        }
+
+        for orphan in invalid.iter() {
+            orphan.store_batch_delete(&mut self.store, &mut batch)?;
+        }
+
+        self.stage_graph(&mut batch, &graph)?;
+
+        self.store.commit(&session, &batch)?;
+
+        self.graph = graph;
+
+        Ok(())
+    }
+
+    /// Builds the finalized `BlockGraph` with a new frontier, selecting its tip with the `TipRule`.
+    fn next_graph<H: Hash<D>>(&self, hasher: &mut H, frontier: &Vec<BlockNode<D>>) -> Result<BlockGraph<D, P>> {
+        let tip_idx = match self.tip_rule {
+            TipRule::Longest => {
+                let mut tip: Option<&BlockNode<D>> = None;
+
+                for node in frontier.iter() {
+                    let is_higher = match tip {
+                        Some(tip) => node.block_height > tip.block_height ||
+                            (node.block_height == tip.block_height && node.block_id < tip.block_id),
+                        None => true,
+                    };
+
+                    if is_higher {
+                        tip = Some(node);
+                    }
+                }
+
+                tip.and_then(|tip| frontier.iter().position(|node| node == tip))
+            },
+            TipRule::Heaviest => {
+                self.weights.tip().and_then(|tip| frontier.iter().position(|node| node == &tip))
+            },
+            TipRule::Custom => {
+                self.tip().and_then(|tip| frontier.iter().position(|node| node == &tip))
+            },
+        };
+
+        let mut graph = self.graph.clone().frontier(tip_idx.map(|idx| idx as u64), frontier)?;
+        graph.id = D::default();
+
+        graph.finalize(hasher)
+    }
+
+    /// Stages in a batch the replacement of the stored `BlockGraph` with a new version.
+    fn stage_graph(&mut self, batch: &mut Batch, graph: &BlockGraph<D, P>) -> Result<()> {
+        if BlockGraph::<D, P>::store_lookup(&mut self.store, &self.graph.id)? {
+            self.graph.store_batch_delete(&mut self.store, batch)?;
+        }
+
+        graph.store_batch_create(&mut self.store, batch)
+    }
+
+    /// Loads the `BlockWeight`s of the connected `Block`s from the `Store`. If some connected `Block`s
+    /// have no `BlockWeight`, as in the `Store`s written before they were stored or by another
+    /// `TipRule`, the `BlockWeight`s are rebuilt from the connected `Block`s and stored.
+    fn load_weights(&mut self) -> Result<()> {
+        let stored = BlockWeight::<D>::store_list(&mut self.store, None, None, None, 0)?;
+
+        if stored.len() as u64 == BlockNode::<D>::store_count(&mut self.store, None, None)? {
+            let mut weights = Weights::new();
+
+            for weight in stored.into_iter() {
+                weights.insert(weight);
+            }
+
+            self.weights = weights;
+
+            return Ok(());
+        }
+
+        let mut nodes = BlockNode::<D>::store_list(&mut self.store, None, None, None, 0)?;
+
+        sort_nodes(&mut nodes);
+
+        let mut weights = Weights::new();
+
+        for node in nodes.iter() {
+            let prev_blocks = self.block(&node.block_id)?.prev_blocks;
+
+            weights.link(node, &prev_blocks);
+        }
+
+        let session = self.store.session(&Permission::Write)?;
+
+        let mut batch = self.store.begin(&session)?;
+
+        for weight in weights.weights.values() {
+            weight.store_batch_upsert(&mut self.store, &mut batch)?;
+        }
+
+        self.store.commit(&session, &batch)?;
+
+        self.weights = weights;
+
+        Ok(())
+    }
+}
+
+/// Moves a state back after a failed `Reorg`, reverting the applied `Block`s and applying the
+/// reverted ones, from the last to the first.
+fn rollback<H, T, D, A, IP, OP, TP, BP, BPr>(hasher: &mut H,
+                                             state: &mut T,
+                                             applied: &[Block<D, A, IP, OP, TP, BP, BPr>],
+                                             reverted: &[Block<D, A, IP, OP, TP, BP, BPr>])
+    -> Result<()>
+    where   H: Hash<D>,
+            T: StateTransition<D, A, IP, OP, TP, BP, BPr>,
+            D: Ord + Datable + ConstantSize,
+            A: Numerical,
+            IP: Datable,
+            OP: Datable,
+            TP: Datable,
+            BP: Datable,
+            BPr: Datable
+{
+    for block in applied.iter().rev() {
+        state.revert_block(hasher, block)?;
+    }
+
+    for block in reverted.iter().rev() {
+        state.apply_block(hasher, block)?;
+    }
+
+    Ok(())
+}
+
+/// Returns the error of a failed `Reorg`, keeping the error of its rollback, if any.
+fn reorg_error(err: String, rollback: Result<()>) -> String {
+    match rollback {
+        Ok(()) => err,
+        Err(rollback_err) => format!("{}: rollback failed: {}", err, rollback_err),
+    }
+}
+
+/// Type used to walk back a `BlockGraph` from two tips by height, highest first, keeping track of
+/// the tips each visited `BlockNode` is reachable from.
+struct Walk<D>
+    where   D: Ord + Datable + ConstantSize
+{
+    queue: BTreeSet<(Reverse<u64>, D)>,
+    reached: BTreeMap<D, (BlockNode<D>, bool, bool)>,
+    pending: u64,
+}
+
+impl<D> Walk<D>
+    where   D: Ord + Datable + ConstantSize
+{
+    /// Creates a new `Walk`.
+    fn new() -> Self {
+        Walk {
+            queue: BTreeSet::new(),
+            reached: BTreeMap::new(),
+            pending: 0,
+        }
+    }
+
+    /// Marks a `BlockNode` as reachable from the old tip, the new tip or both, queueing it if new.
+    /// `pending` counts the queued `BlockNode`s not reachable from both tips.
+    fn reach(&mut self, node: &BlockNode<D>, from: bool, to: bool) {
+        if !self.reached.contains_key(&node.block_id) {
+            self.queue.insert((Reverse(node.block_height), node.block_id.clone()));
+            self.reached.insert(node.block_id.clone(), (node.clone(), false, false));
+            self.pending += 1;
+        }
+
+        if let Some(entry) = self.reached.get_mut(&node.block_id) {
+            let was_common = entry.1 && entry.2;
+
+            entry.1 |= from;
+            entry.2 |= to;
+
+            if !was_common && entry.1 && entry.2 {
+                self.pending -= 1;
+            }
+        }
+    }
+
+    /// Pops the highest queued `BlockNode`, with the tips it is reachable from.
+    fn next(&mut self) -> Option<(BlockNode<D>, bool, bool)> {
+        let key = self.queue.iter().next().cloned()?;
+        self.queue.remove(&key);
+
+        let entry = self.reached.get(&key.1)?.clone();
+
+        if !(entry.1 && entry.2) {
+            self.pending -= 1;
+        }
+
+        Some(entry)
+    }
+}
+
+/// Map of `Block`s by id.
+type BlocksById<D, A, IP, OP, TP, BP, BPr> = BTreeMap<D, Block<D, A, IP, OP, TP, BP, BPr>>;
+
+/// Type used to represent if a `Block` can be connected to the `BlockGraph`.
+enum Connection {
+    /// All the previous blocks are connected.
+    Connectable,
+    /// Some of the previous blocks are not connected yet.
+    Missing,
+    /// The previous blocks or the height of the `Block` are invalid.
+    Invalid(String),
+}
+
+/// Type used to keep the links and the subtree weights of the connected `Block`s, where the weight
+/// of a `Block` is the number of its descendants plus one. The weights are updated as the `Block`s
+/// are connected, so that `TipRule::Heaviest` does not walk the descendants at each update.
+#[derive(Clone, Debug, Default)]
+struct Weights<D>
+    where   D: Ord + Datable + ConstantSize
+{
+    genesis: Option<BlockNode<D>>,
+    children: BTreeMap<D, Vec<BlockNode<D>>>,
+    weights: BTreeMap<D, BlockWeight<D>>,
+}
+
+impl<D> Weights<D>
+    where   D: Ord + Datable + ConstantSize
+{
+    /// Creates a new `Weights`.
+    fn new() -> Self {
+        Weights {
+            genesis: None,
+            children: BTreeMap::new(),
+            weights: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the `BlockWeight` of a connected `Block`, if any.
+    fn get(&self, block_id: &D) -> Option<&BlockWeight<D>> {
+        self.weights.get(block_id)
+    }
+
+    /// Adds a `BlockWeight` as it is, without updating the weights of its ancestors.
+    fn insert(&mut self, weight: BlockWeight<D>) {
+        if weight.parents.is_empty() {
+            self.genesis = Some(weight.node.clone());
+        }
+
+        for parent in weight.parents.iter() {
+            self.children.entry(parent.clone()).or_default().push(weight.node.clone());
+        }
+
+        self.weights.insert(weight.node.block_id.clone(), weight);
+    }
+
+    /// Adds a connected `BlockNode`, increasing the weights of its ancestors, and returns the ids
+    /// of the ancestors.
+    fn link(&mut self, node: &BlockNode<D>, prev_blocks: &[BlockNode<D>]) -> Vec<D> {
+        let parents: Vec<D> = prev_blocks.iter().map(|prev_block| prev_block.block_id.clone()).collect();
+
+        let ancestors = self.ancestors(&parents);
+
+        for ancestor in ancestors.iter() {
+            if let Some(weight) = self.weights.get_mut(ancestor) {
+                weight.weight += 1;
+            }
+        }
+
+        self.insert(BlockWeight::new(node, &parents));
+
+        ancestors
+    }
+
+    /// Removes the last added `BlockNode`, decreasing the weights of its ancestors.
+    fn unlink(&mut self, node: &BlockNode<D>) {
+        let parents = match self.weights.remove(&node.block_id) {
+            Some(weight) => weight.parents,
+            None => return,
+        };
+
+        for ancestor in self.ancestors(&parents).iter() {
+            if let Some(weight) = self.weights.get_mut(ancestor) {
+                weight.weight -= 1;
+            }
+        }
+
+        for parent in parents.iter() {
+            if let Some(children) = self.children.get_mut(parent) {
+                children.retain(|child| child != node);
+            }
+        }
+
+        if self.genesis.as_ref() == Some(node) {
+            self.genesis = None;
+        }
+    }
+
+    /// Returns the ids of the ancestors of a `Block` from the ids of its previous blocks, included.
+    /// The parent path is followed while the `Block`s have a single previous block, and only past a
+    /// `Block` with more previous blocks the ancestors are deduplicated.
+    fn ancestors(&self, parents: &[D]) -> Vec<D> {
+        let mut ancestors = Vec::new();
+        let mut parents = parents;
+
+        while parents.len() == 1 {
+            ancestors.push(parents[0].clone());
+
+            parents = match self.weights.get(&parents[0]) {
+                Some(weight) => &weight.parents,
+                None => &[],
+            };
+        }
+
+        let mut visited = BTreeSet::new();
+        let mut queue: VecDeque<D> = parents.iter().cloned().collect();
+
+        while let Some(id) = queue.pop_front() {
+            if !visited.insert(id.clone()) {
+                continue;
+            }
+
+            if let Some(weight) = self.weights.get(&id) {
+                queue.extend(weight.parents.iter().cloned());
+            }
+        }
+
+        ancestors.extend(visited);
+
+        ancestors
+    }
+
+    /// Returns the `BlockNode` reached from the genesis following the heaviest subtrees, if any.
+    fn tip(&self) -> Option<BlockNode<D>> {
+        let mut tip = self.genesis.clone()?;
+
+        loop {
+            let mut heaviest: Option<(u64, &BlockNode<D>)> = None;
+
+            for child in self.children.get(&tip.block_id).into_iter().flat_map(|children| children.iter()) {
+                let weight = self.weights.get(&child.block_id).map(|weight| weight.weight).unwrap_or(0);
+
+                let is_heavier = match heaviest {
+                    Some((max_weight, node)) => weight > max_weight ||
+                        (weight == max_weight && child.block_id < node.block_id),
+                    None => true,
+                };
+
+                if is_heavier {
+                    heaviest = Some((weight, child));
+                }
+            }
+
+            match heaviest {
+                Some((_, node)) => tip = node.clone(),
+                None => return Some(tip),
+            }
+        }
+    }
+}
+
+/// Sorts a list of `BlockNode`s by height and block id.
+fn sort_nodes<D>(nodes: &mut [BlockNode<D>])
+    where   D: Ord + Datable + ConstantSize
+{
+    nodes.sort_by(|a, b| (a.block_height, &a.block_id).cmp(&(b.block_height, &b.block_id)));
 }
\ No newline at end of file
@@ -0,0 +1,382 @@
+//! # BlockHeader
+//!
+//! `blockheader` is the module providing the type used to represent the header of a `Block`: all the
+//! `Block` data but its transactions, which are committed to by the transactions root.
+//!
+//! Nodes can sync the headers first, which are small, and fetch the `Block` bodies on demand through
+//! the `Block` id stored in the header. A `Block` is verified against its header with
+//! `Block::verify_header`, and the inclusion of a `Transaction` with a `MerkleProof` against the
+//! header transactions root.
+//!
+//! The `Block` id and proof are derived from the header fields alone, so a header can be checked
+//! against its `Block` id and proof with `BlockHeader::verify_block_id` and
+//! `BlockHeader::verify_proof` without the `Block` body.
+//!
+//! The height of a `BlockHeader` follows the `Block` rules: 0 for a genesis `Block`, 1 for a legacy
+//! genesis `Block`, and above 0 for the other `Block`s.
+
+use base::Result;
+use base::Checkable;
+use base::Datable;
+use base::Serializable;
+use base::{Sizable, ConstantSize};
+use base::Numerical;
+use base::{Eval, EvalMut};
+use base::Meta;
+use crypto::{Hash, Prove, MerkleProof};
+use io::{Store, Storable};
+use io::store::prefix::encode_prefix;
+use io::StoreKey;
+use io::IndexKey;
+use model::BlockNode;
+use model::Block;
+
+/// Code of the `BlockHeader` type.
+pub const BLOCKHEADER_CODE: u64 = 12;
+
+/// Name of the `BlockHeader` height index.
+pub const BLOCKHEADER_HEIGHT_INDEX: &str = "height";
+
+/// Name of the `BlockHeader` block id index.
+pub const BLOCKHEADER_BLOCK_ID_INDEX: &str = "block_id";
+
+/// Type used to represent the header of a `Block`.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default, Hash, Serialize, Deserialize)]
+pub struct BlockHeader<D, P, Pr>
+    where   D: Ord + Datable + ConstantSize,
+            P: Datable,
+            Pr: Datable
+{
+    /// BlockHeader id. It is the digest of the same blockheader, but with a default `D` id.
+    pub id: D,
+    /// BlockHeader metadata.
+    pub meta: Meta,
+    /// Block's id.
+    pub block_id: D,
+    /// Block's height.
+    pub height: u64,
+    /// Previous blocks length.
+    pub prev_blocks_len: u64,
+    /// Previous blocks.
+    pub prev_blocks: Vec<BlockNode<D>>,
+    /// Block's transactions length.
+    pub transactions_len: u64,
+    /// Root of the `MerkleTree` of the ids of the block's transactions.
+    pub transactions_root: D,
+    /// Block's custom payload.
+    pub payload: P,
+    /// Block's proof.
+    pub proof: Pr,
+}
+
+impl<D, P, Pr> BlockHeader<D, P, Pr>
+    where   D: Ord + Datable + ConstantSize,
+            P: Datable,
+            Pr: Datable,
+            Self: Serializable
+{
+    /// Creates a new `BlockHeader`.
+    pub fn new() -> Self {
+        let mut header = BlockHeader::default();
+        header.update_size();
+        header
+    }
+
+    /// Updates the `BlockHeader` size.
+    pub fn update_size(&mut self) {
+        let size = self.size();
+
+        self.meta.set_size(size);
+    }
+
+    /// Sets the `BlockHeader`'s metadata.
+    pub fn meta(mut self, meta: &Meta) -> Result<Self> {
+        meta.check()?;
+        self.meta = meta.clone();
+
+        self.update_size();
+
+        Ok(self)
+    }
+
+    /// Sets the `BlockHeader`'s block data, taking it from a finalized `Block`.
+    pub fn block<A, IP, OP, TP>(mut self, block: &Block<D, A, IP, OP, TP, P, Pr>) -> Result<Self>
+        where   A: Numerical,
+                IP: Datable,
+                OP: Datable,
+                TP: Datable
+    {
+        block.check()?;
+
+        self.block_id = block.id.clone();
+        self.height = block.height;
+        self.prev_blocks_len = block.prev_blocks_len;
+        self.prev_blocks = block.prev_blocks.clone();
+        self.transactions_len = block.transactions_len;
+        self.transactions_root = block.transactions_root.clone();
+        self.payload = block.payload.clone();
+        self.proof = block.proof.clone();
+
+        self.update_size();
+
+        Ok(self)
+    }
+
+    /// Returns the message the `Block` id is the digest of, or, without the proof, the message the
+    /// `Block` proof is built on: the header with default ids.
+    pub(crate) fn block_message(&self, with_proof: bool) -> Result<Vec<u8>> {
+        let mut header = self.clone();
+        header.id = D::default();
+        header.block_id = D::default();
+
+        if !with_proof {
+            header.proof = Pr::default();
+        }
+
+        header.update_size();
+
+        header.to_bytes()
+    }
+
+    /// Verifies the `Block` id against the `BlockHeader`.
+    pub fn verify_block_id<H: Hash<D>>(&self, hasher: &mut H) -> Result<bool> {
+        let block_id = self.block_id.clone();
+        block_id.check()?;
+
+        let msg = self.block_message(true)?;
+        hasher.verify(&msg, &block_id)
+    }
+
+    /// Checks the `Block` id against the `BlockHeader`.
+    pub fn check_block_id<H: Hash<D>>(&self, hasher: &mut H) -> Result<()> {
+        let block_id = self.block_id.clone();
+        block_id.check()?;
+
+        let msg = self.block_message(true)?;
+        hasher.check(&msg, &block_id)
+    }
+
+    /// Verifies the `Block` cryptographic proof against the `BlockHeader`.
+    pub fn verify_proof<Prv: Prove<Pr>>(&self, prover: &mut Prv) -> Result<bool> {
+        let proof = self.proof.clone();
+        proof.check()?;
+
+        let msg = self.block_message(false)?;
+        prover.verify(&msg, &proof)
+    }
+
+    /// Checks the `Block` cryptographic proof against the `BlockHeader`.
+    pub fn check_proof<Prv: Prove<Pr>>(&self, prover: &mut Prv) -> Result<()> {
+        let proof = self.proof.clone();
+        proof.check()?;
+
+        let msg = self.block_message(false)?;
+        prover.check(&msg, &proof)
+    }
+
+    /// Verifies the `MerkleProof` of inclusion of a `Transaction` in the `Block`, given its id.
+    pub fn verify_transaction_proof<H: Hash<D>>(&self,
+                                                hasher: &mut H,
+                                                transaction_id: &D,
+                                                proof: &MerkleProof<D>)
+        -> Result<bool>
+        where   D: Serializable
+    {
+        if proof.leaves_len != self.transactions_len {
+            return Ok(false);
+        }
+
+        proof.verify(hasher, transaction_id, &self.transactions_root)
+    }
+
+    /// Finalizes the `BlockHeader`, building its id and returning it's complete form.
+    pub fn finalize<H: Hash<D>>(mut self, hasher: &mut H) -> Result<Self> {
+        self.id = self.digest(hasher)?;
+
+        self.update_size();
+
+        self.check()?;
+
+        Ok(self)
+    }
+
+    /// Hashes cryptographically the `BlockHeader`.
+    pub fn digest<H: Hash<D>>(&self, hasher: &mut H) -> Result<D> {
+        let mut header = self.clone();
+        header.id = D::default();
+        header.update_size();
+
+        let msg = header.to_bytes()?;
+        hasher.digest(&msg)
+    }
+
+    /// Verifies the cryptographic digest against the `BlockHeader`'s digest.
+    pub fn verify_digest<H: Hash<D>>(&self, hasher: &mut H) -> Result<bool> {
+        let digest = self.id.clone();
+        digest.check()?;
+
+        let mut header = self.clone();
+        header.id = D::default();
+        header.update_size();
+
+        let msg = header.to_bytes()?;
+        hasher.verify(&msg, &digest)
+    }
+
+    /// Checks the cryptographic digest against the `BlockHeader`'s digest.
+    pub fn check_digest<H: Hash<D>>(&self, hasher: &mut H) -> Result<()> {
+        let digest = self.id.clone();
+        digest.check()?;
+
+        let mut header = self.clone();
+        header.id = D::default();
+        header.update_size();
+
+        let msg = header.to_bytes()?;
+        hasher.check(&msg, &digest)
+    }
+
+    /// Evals the `BlockHeader`.
+    pub fn eval<Ev, EP, ER>(&self, params: &EP, evaluator: &Ev)
+        -> Result<ER>
+        where   Ev: Eval<Self, EP, ER>,
+                EP: Datable,
+                ER: Datable
+    {
+        self.check()?;
+        params.check()?;
+
+        evaluator.eval(self, params)
+    }
+
+    /// Evals mutably the `BlockHeader`.
+    pub fn eval_mut<EvM, EP, ER>(&mut self, params: &EP, evaluator: &mut EvM)
+        -> Result<ER>
+        where   EvM: EvalMut<Self, EP, ER>,
+                EP: Datable,
+                ER: Datable
+    {
+        self.check()?;
+        params.check()?;
+
+        let result = evaluator.eval_mut(self, params)?;
+        self.update_size();
+
+        self.check()?;
+
+        Ok(result)
+    }
+}
+
+impl<D, P, Pr> Sizable for BlockHeader<D, P, Pr>
+    where   D: Ord + Datable + ConstantSize,
+            P: Datable,
+            Pr: Datable
+{
+    fn size(&self) -> u64 {
+        self.id.size() +
+            self.meta.size() +
+            self.block_id.size() +
+            self.height.size() +
+            self.prev_blocks_len.size() +
+            self.prev_blocks.size() +
+            self.transactions_len.size() +
+            self.transactions_root.size() +
+            self.payload.size() +
+            self.proof.size()
+    }
+}
+
+impl<D, P, Pr> Checkable for BlockHeader<D, P, Pr>
+    where   D: Ord + Datable + ConstantSize,
+            P: Datable,
+            Pr: Datable
+{
+    fn check(&self) -> Result<()> {
+        self.id.check()?;
+        self.id.check_size()?;
+        self.meta.check()?;
+
+        if self.meta.get_size() != self.size() {
+            return Err(String::from("invalid meta size"));
+        }
+
+        self.block_id.check()?;
+        self.block_id.check_size()?;
+        self.height.check()?;
+        self.prev_blocks_len.check()?;
+        self.prev_blocks.check()?;
+
+        if self.prev_blocks.len() != self.prev_blocks_len as usize {
+            return Err(String::from("invalid previous blocks length"));
+        }
+
+        if self.prev_blocks.is_empty() {
+            if self.height > 1 {
+                return Err(String::from("invalid height"));
+            }
+        } else if self.height == 0 {
+            return Err(String::from("invalid height"));
+        }
+
+        let mut unique_prev_blocks = self.prev_blocks.clone();
+        unique_prev_blocks.dedup_by(|a, b| { a == b });
+
+        if unique_prev_blocks.len() != self.prev_blocks.len() {
+            return Err(String::from("duplicates found"));
+        }
+
+        self.transactions_len.check()?;
+        self.transactions_root.check()?;
+        self.transactions_root.check_size()?;
+        self.payload.check()?;
+        self.proof.check()?;
+
+        Ok(())
+    }
+}
+
+impl<D, P, Pr> Serializable for BlockHeader<D, P, Pr>
+    where   D: Ord + Datable + ConstantSize + Serializable,
+            P: Datable + Serializable,
+            Pr: Datable + Serializable
+{}
+
+impl<D, P, Pr> Datable for BlockHeader<D, P, Pr>
+    where   D: Ord + Datable + ConstantSize,
+            P: Datable,
+            Pr: Datable
+{}
+
+impl<St, S, D, P, Pr>
+    Storable<St, S, D, BlockHeader<D, P, Pr>>
+    for BlockHeader<D, P, Pr>
+    where   St: Store<S>,
+            S: Datable + Serializable,
+            D: Ord + Datable + ConstantSize + Serializable + StoreKey,
+            P: Datable + Serializable,
+            Pr: Datable + Serializable
+{
+    fn store_prefix() -> Vec<u8> {
+        encode_prefix(BLOCKHEADER_CODE)
+    }
+
+    fn store_key(&self) -> Result<D> {
+        self.id.check()?;
+
+        Ok(self.id.clone())
+    }
+
+    fn store_value(&self) -> Result<Self> {
+        self.check()?;
+
+        Ok(self.clone())
+    }
+
+    fn store_indexes(&self) -> Result<Vec<IndexKey>> {
+        let height_key = IndexKey::from_u64(BLOCKHEADER_HEIGHT_INDEX, self.height)?;
+        let block_id_key = IndexKey::from_key(BLOCKHEADER_BLOCK_ID_INDEX, &self.block_id)?;
+
+        Ok(vec![height_key, block_id_key])
+    }
+}
\ No newline at end of file
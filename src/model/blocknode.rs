@@ -3,8 +3,6 @@
 //! `blocknode` is the module providing the type used to represent a node in the `BlockNode`.
 //! A `BlockNode` references a `Block`.
 
-use std::mem;
-
 use base::Result;
 use base::Checkable;
 use base::Datable;
@@ -14,10 +12,19 @@ use base::{Eval, EvalMut};
 use base::Meta;
 use crypto::Hash;
 use io::{Store, Storable};
+use io::store::prefix::encode_prefix;
+use io::StoreKey;
+use io::IndexKey;
 
 /// Code of the `BlockNode` type.
 pub const BLOCKNODE_CODE: u64 = 4;
 
+/// Name of the `BlockNode` block height index.
+pub const BLOCKNODE_BLOCK_HEIGHT_INDEX: &str = "block_height";
+
+/// Name of the `BlockNode` block id index.
+pub const BLOCKNODE_BLOCK_ID_INDEX: &str = "block_id";
+
 /// Type used to represent a node in the `BlockNode` and that references a `Block`.
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default, Hash, Serialize, Deserialize)]
 pub struct BlockNode<D>
@@ -198,15 +205,10 @@ impl<St, S, D>
     for BlockNode<D>
     where   St: Store<S>,
             S: Datable + Serializable,
-            D: Ord + Datable + ConstantSize + Serializable
+            D: Ord + Datable + ConstantSize + Serializable + StoreKey
 {
     fn store_prefix() -> Vec<u8> {
-        let mut prefix = Vec::new();
-
-        let _prefix: [u8; 8] = unsafe { mem::transmute(BLOCKNODE_CODE) };
-        prefix.extend_from_slice(&_prefix[..]);
-
-        prefix
+        encode_prefix(BLOCKNODE_CODE)
     }
 
     fn store_key(&self) -> Result<D> {
@@ -220,4 +222,11 @@ impl<St, S, D>
 
         Ok(self.clone())
     }
+
+    fn store_indexes(&self) -> Result<Vec<IndexKey>> {
+        let block_height_key = IndexKey::from_u64(BLOCKNODE_BLOCK_HEIGHT_INDEX, self.block_height)?;
+        let block_id_key = IndexKey::from_key(BLOCKNODE_BLOCK_ID_INDEX, &self.block_id)?;
+
+        Ok(vec![block_height_key, block_id_key])
+    }
 }
\ No newline at end of file
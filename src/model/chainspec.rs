@@ -0,0 +1,296 @@
+//! # ChainSpec
+//!
+//! `chainspec` is the module providing the type used to specify a chain: its name, `Version` and
+//! `Stage`, its consensus parameters, the outputs created at its start and its genesis `Block`.
+//!
+//! The genesis `Block` is built deterministically from the rest of the specification: its metadata
+//! are the chain metadata with the genesis timestamp, and its only `Transaction` creates the initial
+//! outputs. Any node can so rebuild the genesis `Block` and its id from a `ChainSpec` loaded from JSON
+//! or CBOR, and check that its `BlockGraph` is rooted at the same genesis.
+
+use base::Result;
+use base::Checkable;
+use base::Datable;
+use base::Serializable;
+use base::{Sizable, ConstantSize};
+use base::Numerical;
+use base::Meta;
+use base::Stage;
+use crypto::Hash;
+use io::Store;
+use io::StoreKey;
+use util::{Version, Timestamp};
+use model::Output;
+use model::Transaction;
+use model::Block;
+use model::BlockGraphEngine;
+
+/// Type used to specify a chain and its genesis `Block`.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default, Hash, Serialize, Deserialize)]
+pub struct ChainSpec<D, A, IP, OP, TP, BP, BPr, C>
+    where   D: Ord + Datable + ConstantSize,
+            A: Numerical,
+            IP: Datable,
+            OP: Datable,
+            TP: Datable,
+            BP: Datable,
+            BPr: Datable,
+            C: Datable
+{
+    /// Chain name.
+    pub chain: String,
+    /// Chain version.
+    pub version: Version,
+    /// Chain stage.
+    pub stage: Stage,
+    /// Chain consensus parameters.
+    pub consensus: C,
+    /// Initial outputs length.
+    pub outputs_len: u64,
+    /// Initial outputs, created by the genesis block.
+    pub outputs: Vec<Output<D, A, OP>>,
+    /// Genesis block.
+    pub genesis: Block<D, A, IP, OP, TP, BP, BPr>,
+}
+
+impl<D, A, IP, OP, TP, BP, BPr, C> ChainSpec<D, A, IP, OP, TP, BP, BPr, C>
+    where   D: Ord + Datable + ConstantSize + Serializable,
+            A: Numerical + Serializable,
+            IP: Datable + Serializable,
+            OP: Datable + Serializable,
+            TP: Datable + Serializable,
+            BP: Datable + Serializable,
+            BPr: Datable + Serializable,
+            C: Datable + Serializable
+{
+    /// Creates a new `ChainSpec`.
+    pub fn new() -> Self {
+        ChainSpec::default()
+    }
+
+    /// Sets the `ChainSpec`'s chain name, version and stage.
+    pub fn chain(mut self, chain: &str, version: &Version, stage: &Stage) -> Result<Self> {
+        version.check()?;
+        stage.check()?;
+
+        self.chain = chain.into();
+        self.version = version.clone();
+        self.stage = *stage;
+
+        Ok(self)
+    }
+
+    /// Sets the `ChainSpec`'s consensus parameters.
+    pub fn consensus(mut self, consensus: &C) -> Result<Self> {
+        consensus.check()?;
+
+        self.consensus = consensus.clone();
+
+        Ok(self)
+    }
+
+    /// Sets the `ChainSpec`'s initial outputs and their length.
+    pub fn outputs(mut self, outputs: &Vec<Output<D, A, OP>>) -> Result<Self> {
+        outputs.check()?;
+
+        self.outputs_len = outputs.len() as u64;
+        self.outputs = outputs.clone();
+
+        Ok(self)
+    }
+
+    /// Builds the `ChainSpec`'s genesis `Block` from the chain data and the initial outputs.
+    /// The chain data and the outputs have to be set before.
+    pub fn genesis<H: Hash<D>>(mut self,
+                               hasher: &mut H,
+                               timestamp: &Timestamp,
+                               payload: &BP,
+                               proof: &BPr)
+        -> Result<Self>
+    {
+        self.genesis = self.build_genesis(hasher, timestamp, payload, proof)?;
+
+        self.check()?;
+
+        Ok(self)
+    }
+
+    /// Builds a genesis `Block` from the `ChainSpec`'s chain data and initial outputs.
+    pub fn build_genesis<H: Hash<D>>(&self,
+                                     hasher: &mut H,
+                                     timestamp: &Timestamp,
+                                     payload: &BP,
+                                     proof: &BPr)
+        -> Result<Block<D, A, IP, OP, TP, BP, BPr>>
+    {
+        let mut meta = Meta::new(self.chain.clone(), self.version.clone(), self.stage)?;
+        meta.timestamp = *timestamp;
+
+        let mut transactions = Vec::new();
+
+        if !self.outputs.is_empty() {
+            let transaction = Transaction::new()
+                                .meta(&meta)?
+                                .outputs(&self.outputs)?
+                                .finalize(hasher)?;
+
+            transactions.push(transaction);
+        }
+
+        let mut genesis = Block::new()
+                            .meta(&meta)?
+                            .transactions(&transactions)?
+                            .payload(payload)?;
+
+        genesis.proof = proof.clone();
+        genesis.update_size();
+
+        genesis.finalize(hasher)
+    }
+
+    /// Verifies the `ChainSpec`'s genesis `Block` against the one built from the `ChainSpec`.
+    pub fn verify_genesis<H: Hash<D>>(&self, hasher: &mut H) -> Result<bool> {
+        self.check()?;
+
+        let genesis = self.build_genesis(hasher,
+                                         &self.genesis.meta.timestamp,
+                                         &self.genesis.payload,
+                                         &self.genesis.proof)?;
+
+        Ok(genesis == self.genesis)
+    }
+
+    /// Checks the `ChainSpec`'s genesis `Block` against the one built from the `ChainSpec`.
+    pub fn check_genesis<H: Hash<D>>(&self, hasher: &mut H) -> Result<()> {
+        if !self.verify_genesis(hasher)? {
+            return Err(String::from("invalid genesis"));
+        }
+
+        Ok(())
+    }
+
+    /// Verifies that a `BlockGraph` is rooted at the `ChainSpec`'s genesis `Block`.
+    pub fn verify_graph<St, S, P>(&self, engine: &mut BlockGraphEngine<St, S, D, A, IP, OP, TP, BP, BPr, P>)
+        -> Result<bool>
+        where   St: Store<S>,
+                S: Datable + Serializable,
+                D: StoreKey,
+                P: Datable + Serializable
+    {
+        self.check()?;
+
+        if !engine.lookup(&self.genesis.id)? {
+            return Ok(false);
+        }
+
+        let genesis = engine.node(&self.genesis.id)?;
+
+        if engine.genesis()? != genesis || engine.block(&self.genesis.id)? != self.genesis {
+            return Ok(false);
+        }
+
+        for node in engine.graph().frontier.clone().iter() {
+            if node != &genesis && !engine.ancestors(&node.block_id)?.contains(&genesis) {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Checks that a `BlockGraph` is rooted at the `ChainSpec`'s genesis `Block`.
+    pub fn check_graph<St, S, P>(&self, engine: &mut BlockGraphEngine<St, S, D, A, IP, OP, TP, BP, BPr, P>)
+        -> Result<()>
+        where   St: Store<S>,
+                S: Datable + Serializable,
+                D: StoreKey,
+                P: Datable + Serializable
+    {
+        if !self.verify_graph(engine)? {
+            return Err(String::from("invalid genesis"));
+        }
+
+        Ok(())
+    }
+}
+
+impl<D, A, IP, OP, TP, BP, BPr, C> Sizable for ChainSpec<D, A, IP, OP, TP, BP, BPr, C>
+    where   D: Ord + Datable + ConstantSize,
+            A: Numerical,
+            IP: Datable,
+            OP: Datable,
+            TP: Datable,
+            BP: Datable,
+            BPr: Datable,
+            C: Datable
+{
+    fn size(&self) -> u64 {
+        self.chain.size() +
+            self.version.size() +
+            self.stage.size() +
+            self.consensus.size() +
+            self.outputs_len.size() +
+            self.outputs.size() +
+            self.genesis.size()
+    }
+}
+
+impl<D, A, IP, OP, TP, BP, BPr, C> Checkable for ChainSpec<D, A, IP, OP, TP, BP, BPr, C>
+    where   D: Ord + Datable + ConstantSize,
+            A: Numerical,
+            IP: Datable,
+            OP: Datable,
+            TP: Datable,
+            BP: Datable,
+            BPr: Datable,
+            C: Datable
+{
+    fn check(&self) -> Result<()> {
+        self.chain.check()?;
+        self.version.check()?;
+        self.stage.check()?;
+        self.consensus.check()?;
+        self.outputs_len.check()?;
+        self.outputs.check()?;
+
+        if self.outputs.len() != self.outputs_len as usize {
+            return Err(String::from("invalid outputs length"));
+        }
+
+        self.genesis.check()?;
+
+        if !self.genesis.prev_blocks.is_empty() || self.genesis.height != 0 {
+            return Err(String::from("invalid genesis"));
+        }
+
+        let meta = &self.genesis.meta;
+
+        if meta.chain != self.chain || meta.version != self.version || meta.stage != self.stage {
+            return Err(String::from("invalid genesis meta"));
+        }
+
+        Ok(())
+    }
+}
+
+impl<D, A, IP, OP, TP, BP, BPr, C> Serializable for ChainSpec<D, A, IP, OP, TP, BP, BPr, C>
+    where   D: Ord + Datable + ConstantSize + Serializable,
+            A: Numerical + Serializable,
+            IP: Datable + Serializable,
+            OP: Datable + Serializable,
+            TP: Datable + Serializable,
+            BP: Datable + Serializable,
+            BPr: Datable + Serializable,
+            C: Datable + Serializable
+{}
+
+impl<D, A, IP, OP, TP, BP, BPr, C> Datable for ChainSpec<D, A, IP, OP, TP, BP, BPr, C>
+    where   D: Ord + Datable + ConstantSize,
+            A: Numerical,
+            IP: Datable,
+            OP: Datable,
+            TP: Datable,
+            BP: Datable,
+            BPr: Datable,
+            C: Datable
+{}
\ No newline at end of file
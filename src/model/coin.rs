@@ -3,8 +3,6 @@
 //! `coin` is the module providing the `Coin` type, an `Output` already registered or sent to the
 //! distributed ledger, or just past in time.
 
-use std::mem;
-
 use base::Result;
 use base::Checkable;
 use base::Datable;
@@ -15,10 +13,16 @@ use base::Numerical;
 use base::Meta;
 use crypto::Hash;
 use io::{Store, Storable};
+use io::store::prefix::encode_prefix;
+use io::StoreKey;
+use io::IndexKey;
 
 /// Code of the `Coin` type.
 pub const COIN_CODE: u64 = 0;
 
+/// Name of the `Coin` transaction id index.
+pub const COIN_TX_ID_INDEX: &str = "tx_id";
+
 /// Type used to represent a past `Output`.
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default, Hash, Serialize, Deserialize)]
 pub struct Coin<D, A>
@@ -211,16 +215,11 @@ impl<St, S, D, A>
     for Coin<D, A>
     where   St: Store<S>,
             S: Datable + Serializable,
-            D: Ord + Datable + ConstantSize + Serializable,
+            D: Ord + Datable + ConstantSize + Serializable + StoreKey,
             A: Numerical + Serializable
 {
     fn store_prefix() -> Vec<u8> {
-        let mut prefix = Vec::new();
-
-        let _prefix: [u8; 8] = unsafe { mem::transmute(COIN_CODE) };
-        prefix.extend_from_slice(&_prefix[..]);
-
-        prefix
+        encode_prefix(COIN_CODE)
     }
 
     fn store_key(&self) -> Result<D> {
@@ -234,4 +233,10 @@ impl<St, S, D, A>
 
         Ok(self.clone())
     }
+
+    fn store_indexes(&self) -> Result<Vec<IndexKey>> {
+        let tx_id_key = IndexKey::from_key(COIN_TX_ID_INDEX, &self.tx_id)?;
+
+        Ok(vec![tx_id_key])
+    }
 }
\ No newline at end of file
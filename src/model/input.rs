@@ -2,20 +2,25 @@
 //!
 //! `input` is the module providing the type used to bind as inputs one or more `Input`s
 //! in a `Input`.
-
-use std::mem;
+//!
+//! An `Input` spending a locked `Output` carries a `Witness` satisfying the `Output` lock, e.g. the
+//! `crypto::Sign` signatures of the spending `Transaction` sighash, or the preimages of a digest.
 
 use base::Result;
 use base::Checkable;
 use base::Datable;
 use base::Serializable;
-use base::{Sizable, ConstantSize};
+use base::{Sizable, ConstantSize, VariableSize};
 use base::{Eval, EvalMut};
 use base::Numerical;
 use base::Meta;
-use crypto::Hash;
+use crypto::{Hash, Sign};
 use io::{Store, Storable};
+use io::store::prefix::encode_prefix;
+use io::StoreKey;
 use model::Coin;
+use model::Witness;
+use model::SighashMode;
 
 /// Code of the `Input` type.
 pub const INPUT_CODE: u64 = 1;
@@ -35,6 +40,8 @@ pub struct Input<D, A, P>
     pub coin: Coin<D, A>,
     /// Custom payload.
     pub payload: P,
+    /// Witness authorizing the spending of the bound coin.
+    pub witness: Witness,
 }
 
 impl<D, A, P> Input<D, A, P>
@@ -89,6 +96,98 @@ impl<D, A, P> Input<D, A, P>
         Ok(self)
     }
 
+    /// Sets the `SighashMode` of the `Input` witness. The mode cannot be changed once the `Input` is
+    /// signed.
+    pub fn sighash_mode(mut self, mode: &SighashMode) -> Result<Self> {
+        if self.is_signed() && self.witness.mode != *mode {
+            return Err(String::from("invalid witness"));
+        }
+
+        self.witness.mode = *mode;
+
+        self.update_size();
+
+        Ok(self)
+    }
+
+    /// Signs the `Input` with a secret key, adding the signature to the `Input` witness. The message
+    /// is usually the sighash of the spending `Transaction`.
+    pub fn sign<Seed, Pk, Sk, Sig, Sg>(mut self, msg: &[u8], signer: &mut Sg, sk: &Sk) -> Result<Self>
+        where   Seed: Datable + ConstantSize,
+                Pk: Datable + ConstantSize,
+                Sk: Datable + ConstantSize,
+                Sig: Datable + ConstantSize + Serializable,
+                Sg: Sign<Seed, Pk, Sk, Sig>
+    {
+        sk.check()?;
+        sk.check_size()?;
+
+        let signature = signer.sign(msg, sk)?;
+        self.witness.signatures.push(signature.to_bytes()?);
+        self.witness.check_size()?;
+
+        self.update_size();
+
+        Ok(self)
+    }
+
+    /// Adds a digest preimage to the `Input` witness.
+    pub fn preimage(mut self, preimage: &[u8]) -> Result<Self> {
+        self.witness.preimages.push(preimage.to_owned());
+        self.witness.check_size()?;
+
+        self.update_size();
+
+        Ok(self)
+    }
+
+    /// Returns if the `Input` witness has a signature.
+    pub fn is_signed(&self) -> bool {
+        !self.witness.signatures.is_empty()
+    }
+
+    /// Verifies that the `Input` witness has a signature of a message by a public key.
+    pub fn verify_signature<Seed, Pk, Sk, Sig, Sg>(&self, msg: &[u8], signer: &mut Sg, pk: &Pk)
+        -> Result<bool>
+        where   Seed: Datable + ConstantSize,
+                Pk: Datable + ConstantSize,
+                Sk: Datable + ConstantSize,
+                Sig: Datable + ConstantSize + Serializable,
+                Sg: Sign<Seed, Pk, Sk, Sig>
+    {
+        pk.check()?;
+        pk.check_size()?;
+
+        for signature in self.witness.signatures.iter() {
+            let signature = match Sig::from_bytes(signature) {
+                Ok(signature) => signature,
+                Err(_) => continue,
+            };
+
+            if signer.verify(msg, pk, &signature)? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Checks that the `Input` witness has a signature of a message by a public key.
+    pub fn check_signature<Seed, Pk, Sk, Sig, Sg>(&self, msg: &[u8], signer: &mut Sg, pk: &Pk)
+        -> Result<()>
+        where   Seed: Datable + ConstantSize,
+                Pk: Datable + ConstantSize,
+                Sk: Datable + ConstantSize,
+                Sig: Datable + ConstantSize + Serializable,
+                Sg: Sign<Seed, Pk, Sk, Sig>
+    {
+        if !self.verify_signature(msg, signer, pk)? {
+            return Err(String::from("invalid signature"));
+        }
+
+        Ok(())
+    }
+
     /// Finalizes the `Input`, building its id and returning it's complete form.
     pub fn finalize<H: Hash<D>>(mut self, hasher: &mut H) -> Result<Self> {
         let msg = self.to_bytes()?;
@@ -178,7 +277,8 @@ impl<D, A, P> Sizable for Input<D, A, P>
         self.id.size() +
             self.meta.size() +
             self.coin.size() +
-            self.payload.size()
+            self.payload.size() +
+            self.witness.size()
     }
 }
 
@@ -200,6 +300,8 @@ impl<D, A, P> Checkable for Input<D, A, P>
         self.coin.check()?;
 
         self.payload.check()?;
+        self.witness.check()?;
+        self.witness.check_size()?;
 
         Ok(())
     }
@@ -222,17 +324,12 @@ impl<St, S, D, A, P>
     for Input<D, A, P>
     where   St: Store<S>,
             S: Datable + Serializable,
-            D: Ord + Datable + ConstantSize + Serializable,
+            D: Ord + Datable + ConstantSize + Serializable + StoreKey,
             A: Numerical + Serializable,
             P: Datable + Serializable
 {
     fn store_prefix() -> Vec<u8> {
-        let mut prefix = Vec::new();
-
-        let _prefix: [u8; 8] = unsafe { mem::transmute(INPUT_CODE) };
-        prefix.extend_from_slice(&_prefix[..]);
-
-        prefix
+        encode_prefix(INPUT_CODE)
     }
 
     fn store_key(&self) -> Result<D> {
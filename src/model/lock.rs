@@ -0,0 +1,389 @@
+//! # Lock
+//!
+//! `lock` is the module providing the predicate language used to lock `Output`s, and the witness
+//! type used by `Input`s to satisfy them.
+//!
+//! A `Lock` is a small, deterministic predicate over a `Witness`: it can require signatures of one
+//! or a threshold of keys, the preimage of a digest, or a time after or before a given `Timestamp`,
+//! and it can combine predicates with conjunctions and disjunctions. The evaluation time is supplied
+//! by the caller (e.g. the timestamp of the including block), so a `Lock` evaluates always to the same
+//! result for the same witness, message and time. Both locks and witnesses are size-bounded.
+
+use base::Result;
+use base::Checkable;
+use base::{Sizable, ConstantSize, VariableSize};
+use base::Serializable;
+use base::Datable;
+use crypto::{Hash, Sign};
+use util::Timestamp;
+use model::SighashMode;
+
+/// Maximum size of a `Lock`.
+pub const LOCK_MAX_SIZE: u64 = 4096;
+
+/// Maximum nesting depth of a `Lock`.
+pub const LOCK_MAX_DEPTH: u64 = 8;
+
+/// Maximum size of a `Witness`.
+pub const WITNESS_MAX_SIZE: u64 = 8192;
+
+/// Type representing the predicate locking an `Output`.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Default, Hash, Serialize, Deserialize)]
+pub enum Lock {
+    /// Always satisfied.
+    #[default]
+    Unlocked,
+    /// Satisfied by a signature of the given serialized public key.
+    Key(Vec<u8>),
+    /// Satisfied by signatures of at least a threshold of the given serialized public keys.
+    Multisig(u64, Vec<Vec<u8>>),
+    /// Satisfied by the preimage of the given serialized digest.
+    Preimage(Vec<u8>),
+    /// Satisfied from the given time on.
+    After(Timestamp),
+    /// Satisfied before the given time.
+    Before(Timestamp),
+    /// Satisfied if all the predicates are satisfied.
+    All(Vec<Lock>),
+    /// Satisfied if at least one of the predicates is satisfied.
+    Any(Vec<Lock>),
+}
+
+impl Lock {
+    /// Creates a `Lock` requiring a signature of a public key.
+    pub fn key<Pk>(pk: &Pk) -> Result<Lock>
+        where   Pk: Datable + ConstantSize + Serializable
+    {
+        pk.check()?;
+        pk.check_size()?;
+
+        let lock = Lock::Key(pk.to_bytes()?);
+        lock.check()?;
+
+        Ok(lock)
+    }
+
+    /// Creates a `Lock` requiring signatures of at least `threshold` of the public keys.
+    pub fn multisig<Pk>(threshold: u64, pks: &[Pk]) -> Result<Lock>
+        where   Pk: Datable + ConstantSize + Serializable
+    {
+        let mut keys = Vec::new();
+
+        for pk in pks.iter() {
+            pk.check()?;
+            pk.check_size()?;
+
+            keys.push(pk.to_bytes()?);
+        }
+
+        let lock = Lock::Multisig(threshold, keys);
+        lock.check()?;
+
+        Ok(lock)
+    }
+
+    /// Creates a `Lock` requiring the preimage of a digest.
+    pub fn preimage<D>(digest: &D) -> Result<Lock>
+        where   D: Datable + ConstantSize + Serializable
+    {
+        digest.check()?;
+        digest.check_size()?;
+
+        let lock = Lock::Preimage(digest.to_bytes()?);
+        lock.check()?;
+
+        Ok(lock)
+    }
+
+    /// Returns the nesting depth of the `Lock`.
+    pub fn depth(&self) -> u64 {
+        match self {
+            Lock::All(locks) | Lock::Any(locks) => {
+                1 + locks.iter().map(|lock| lock.depth()).max().unwrap_or(0)
+            },
+            _ => 1,
+        }
+    }
+
+    /// Verifies a `Witness` against the `Lock`. The signatures are verified against `msg`, usually
+    /// the sighash of the spending `Transaction`, and the timelocks against `time`.
+    pub fn verify<D, H, Seed, Pk, Sk, Sig, Sg>(&self,
+                                              witness: &Witness,
+                                              msg: &[u8],
+                                              time: &Timestamp,
+                                              hasher: &mut H,
+                                              signer: &mut Sg)
+        -> Result<bool>
+        where   D: Datable + ConstantSize + Serializable,
+                H: Hash<D>,
+                Seed: Datable + ConstantSize,
+                Pk: Datable + ConstantSize + Serializable,
+                Sk: Datable + ConstantSize,
+                Sig: Datable + ConstantSize + Serializable,
+                Sg: Sign<Seed, Pk, Sk, Sig>
+    {
+        self.check()?;
+        self.check_size()?;
+        witness.check()?;
+        witness.check_size()?;
+        time.check()?;
+
+        self.eval(witness, msg, time, hasher, signer)
+    }
+
+    /// Checks a `Witness` against the `Lock`.
+    pub fn check_witness<D, H, Seed, Pk, Sk, Sig, Sg>(&self,
+                                                     witness: &Witness,
+                                                     msg: &[u8],
+                                                     time: &Timestamp,
+                                                     hasher: &mut H,
+                                                     signer: &mut Sg)
+        -> Result<()>
+        where   D: Datable + ConstantSize + Serializable,
+                H: Hash<D>,
+                Seed: Datable + ConstantSize,
+                Pk: Datable + ConstantSize + Serializable,
+                Sk: Datable + ConstantSize,
+                Sig: Datable + ConstantSize + Serializable,
+                Sg: Sign<Seed, Pk, Sk, Sig>
+    {
+        if !self.verify(witness, msg, time, hasher, signer)? {
+            return Err(String::from("invalid witness"));
+        }
+
+        Ok(())
+    }
+
+    /// Evaluates the `Lock` against a `Witness`.
+    fn eval<D, H, Seed, Pk, Sk, Sig, Sg>(&self,
+                                        witness: &Witness,
+                                        msg: &[u8],
+                                        time: &Timestamp,
+                                        hasher: &mut H,
+                                        signer: &mut Sg)
+        -> Result<bool>
+        where   D: Datable + ConstantSize + Serializable,
+                H: Hash<D>,
+                Seed: Datable + ConstantSize,
+                Pk: Datable + ConstantSize + Serializable,
+                Sk: Datable + ConstantSize,
+                Sig: Datable + ConstantSize + Serializable,
+                Sg: Sign<Seed, Pk, Sk, Sig>
+    {
+        match self {
+            Lock::Unlocked => Ok(true),
+            Lock::Key(key) => {
+                let count = witness.count_signers(msg, signer, &[key.to_owned()])?;
+
+                Ok(count == 1)
+            },
+            Lock::Multisig(threshold, keys) => {
+                let count = witness.count_signers(msg, signer, keys)?;
+
+                Ok(count >= *threshold)
+            },
+            Lock::Preimage(digest) => {
+                for preimage in witness.preimages.iter() {
+                    if &hasher.digest(preimage)?.to_bytes()? == digest {
+                        return Ok(true);
+                    }
+                }
+
+                Ok(false)
+            },
+            Lock::After(after) => Ok(time >= after),
+            Lock::Before(before) => Ok(time < before),
+            Lock::All(locks) => {
+                for lock in locks.iter() {
+                    if !lock.eval(witness, msg, time, hasher, signer)? {
+                        return Ok(false);
+                    }
+                }
+
+                Ok(true)
+            },
+            Lock::Any(locks) => {
+                for lock in locks.iter() {
+                    if lock.eval(witness, msg, time, hasher, signer)? {
+                        return Ok(true);
+                    }
+                }
+
+                Ok(false)
+            },
+        }
+    }
+}
+
+impl Sizable for Lock {
+    fn size(&self) -> u64 {
+        let variant_size = 0u8.size();
+
+        match self {
+            Lock::Unlocked => variant_size,
+            Lock::Key(key) => variant_size + key.size(),
+            Lock::Multisig(threshold, keys) => variant_size + threshold.size() + keys.size(),
+            Lock::Preimage(digest) => variant_size + digest.size(),
+            Lock::After(time) | Lock::Before(time) => variant_size + time.size(),
+            Lock::All(locks) | Lock::Any(locks) => variant_size + locks.size(),
+        }
+    }
+}
+
+impl VariableSize for Lock {
+    fn min_size() -> u64 {
+        0u8.size()
+    }
+
+    fn max_size() -> Option<u64> {
+        Some(LOCK_MAX_SIZE)
+    }
+}
+
+impl Checkable for Lock {
+    fn check(&self) -> Result<()> {
+        if self.depth() > LOCK_MAX_DEPTH {
+            return Err(String::from("invalid depth"));
+        }
+
+        match self {
+            Lock::Unlocked => {},
+            Lock::Key(key) => {
+                if key.is_empty() {
+                    return Err(String::from("invalid key"));
+                }
+            },
+            Lock::Multisig(threshold, keys) => {
+                if *threshold == 0 || *threshold > keys.len() as u64 {
+                    return Err(String::from("invalid threshold"));
+                }
+
+                for (idx, key) in keys.iter().enumerate() {
+                    if key.is_empty() {
+                        return Err(String::from("invalid key"));
+                    }
+
+                    if keys[..idx].contains(key) {
+                        return Err(String::from("duplicates found"));
+                    }
+                }
+            },
+            Lock::Preimage(digest) => {
+                if digest.is_empty() {
+                    return Err(String::from("invalid digest"));
+                }
+            },
+            Lock::After(time) | Lock::Before(time) => {
+                time.check()?;
+            },
+            Lock::All(locks) | Lock::Any(locks) => {
+                if locks.is_empty() {
+                    return Err(String::from("invalid length"));
+                }
+
+                locks.check()?;
+            },
+        }
+
+        Ok(())
+    }
+}
+
+impl Serializable for Lock {}
+
+impl Datable for Lock {}
+
+/// Type representing the data used by an `Input` to satisfy the `Lock` of the spent `Output`.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Default, Hash, Serialize, Deserialize)]
+pub struct Witness {
+    /// Mode of the sighash signed by the signatures.
+    pub mode: SighashMode,
+    /// Serialized signatures, usually of the sighash of the spending `Transaction`.
+    pub signatures: Vec<Vec<u8>>,
+    /// Preimages of the digests required by the `Lock`.
+    pub preimages: Vec<Vec<u8>>,
+}
+
+impl Witness {
+    /// Creates a new empty `Witness`.
+    pub fn new() -> Self {
+        Witness::default()
+    }
+
+    /// Returns if the `Witness` is empty.
+    pub fn is_empty(&self) -> bool {
+        self.signatures.is_empty() && self.preimages.is_empty()
+    }
+
+    /// Counts the public keys with a distinct matching signature in the `Witness`. Public keys and
+    /// signatures that cannot be deserialized are not counted.
+    pub fn count_signers<Seed, Pk, Sk, Sig, Sg>(&self, msg: &[u8], signer: &mut Sg, keys: &[Vec<u8>])
+        -> Result<u64>
+        where   Seed: Datable + ConstantSize,
+                Pk: Datable + ConstantSize + Serializable,
+                Sk: Datable + ConstantSize,
+                Sig: Datable + ConstantSize + Serializable,
+                Sg: Sign<Seed, Pk, Sk, Sig>
+    {
+        let mut used = vec![false; self.signatures.len()];
+        let mut count = 0;
+
+        for key in keys.iter() {
+            let pk = match Pk::from_bytes(key) {
+                Ok(pk) => pk,
+                Err(_) => continue,
+            };
+
+            for (idx, signature) in self.signatures.iter().enumerate() {
+                if used[idx] {
+                    continue;
+                }
+
+                let signature = match Sig::from_bytes(signature) {
+                    Ok(signature) => signature,
+                    Err(_) => continue,
+                };
+
+                if signer.verify(msg, &pk, &signature)? {
+                    used[idx] = true;
+                    count += 1;
+                    break;
+                }
+            }
+        }
+
+        Ok(count)
+    }
+}
+
+impl Sizable for Witness {
+    fn size(&self) -> u64 {
+        self.mode.size() +
+            self.signatures.size() +
+            self.preimages.size()
+    }
+}
+
+impl VariableSize for Witness {
+    fn max_size() -> Option<u64> {
+        Some(WITNESS_MAX_SIZE)
+    }
+}
+
+impl Checkable for Witness {
+    fn check(&self) -> Result<()> {
+        self.mode.check()?;
+
+        for signature in self.signatures.iter() {
+            if signature.is_empty() {
+                return Err(String::from("invalid signature"));
+            }
+        }
+
+        self.preimages.check()
+    }
+}
+
+impl Serializable for Witness {}
+
+impl Datable for Witness {}
\ No newline at end of file
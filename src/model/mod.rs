@@ -12,9 +12,15 @@ pub mod input;
 /// Type used to represent the output of a `Transaction`.
 pub mod output;
 
+/// Types used to lock `Output`s with predicates and to unlock them with `Input` witnesses.
+pub mod lock;
+
 /// Type used to produce new `Output`s from one or more input `Coin`s.
 pub mod transaction;
 
+/// Types used to track the spent and unspent `Coin`s of a ledger in a `Store`.
+pub mod utxo;
+
 /// Type used to represent a node in the `BlockGraph`.
 pub mod blocknode;
 
@@ -22,17 +28,31 @@ pub mod blocknode;
 /// in the `BlockGraph`.
 pub mod block;
 
-/// Type used to represent a graph of authenticated `Block`s, represented as `BlockNode`s.
+/// Type used to represent the header of a `Block`, without its transactions.
+pub mod blockheader;
+
+/// Types used to represent and maintain a graph of authenticated `Block`s, represented as `BlockNode`s.
 pub mod blockgraph;
 
+/// Types used to move the state derived from the `Block`s of a `BlockGraph` between tips.
+pub mod reorg;
+
+/// Type used to specify a chain and build its genesis `Block`.
+pub mod chainspec;
+
 /// Type used to represent a wallet (account) in the protocol.
 pub mod wallet;
 
 pub use self::coin::Coin;
 pub use self::input::Input;
 pub use self::output::Output;
-pub use self::transaction::Transaction;
+pub use self::lock::{Lock, Witness};
+pub use self::transaction::{Transaction, FeePolicy, SighashMode};
+pub use self::utxo::{Utxo, UtxoSet};
 pub use self::blocknode::BlockNode;
 pub use self::block::Block;
-pub use self::blockgraph::BlockGraph;
+pub use self::blockheader::BlockHeader;
+pub use self::blockgraph::{BlockGraph, BlockGraphEngine, BlockWeight, TipRule};
+pub use self::reorg::{Reorg, StateTransition};
+pub use self::chainspec::ChainSpec;
 pub use self::wallet::Wallet;
\ No newline at end of file
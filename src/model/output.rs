@@ -1,19 +1,24 @@
 //! # Output
 //!
 //! `output` is the module providing the type used to represent the output of a `Transaction`.
-
-use std::mem;
+//!
+//! An `Output` is locked by a `Lock` predicate, e.g. to an owner represented by a `crypto::Sign`
+//! public key, so that only the inputs whose `Witness` satisfies the predicate can spend it. An
+//! unlocked `Output` can be spent by anyone.
 
 use base::Result;
 use base::Checkable;
 use base::Datable;
 use base::Serializable;
-use base::{Sizable, ConstantSize};
+use base::{Sizable, ConstantSize, VariableSize};
 use base::Numerical;
 use base::{Eval, EvalMut};
 use base::Meta;
 use crypto::Hash;
 use io::{Store, Storable};
+use io::store::prefix::encode_prefix;
+use io::StoreKey;
+use model::Lock;
 
 /// Code of the `Output` type.
 pub const OUTPUT_CODE: u64 = 2;
@@ -31,6 +36,8 @@ pub struct Output<D, A, P>
     pub meta: Meta,
     /// Output amount.
     pub amount: A,
+    /// Output lock.
+    pub lock: Lock,
     /// Custom payload.
     pub payload: P,
 }
@@ -75,6 +82,39 @@ impl<D, A, P> Output<D, A, P>
         Ok(self)
     }
 
+    /// Sets the `Output`'s lock.
+    pub fn lock(mut self, lock: &Lock) -> Result<Self> {
+        lock.check()?;
+        lock.check_size()?;
+
+        self.lock = lock.clone();
+
+        self.update_size();
+
+        Ok(self)
+    }
+
+    /// Returns if the `Output` is locked.
+    pub fn is_locked(&self) -> bool {
+        self.lock != Lock::Unlocked
+    }
+
+    /// Returns the public key of the `Output` owner, if the `Output` is locked to a single key.
+    pub fn owner<Pk>(&self) -> Result<Option<Pk>>
+        where   Pk: Datable + ConstantSize + Serializable
+    {
+        match self.lock {
+            Lock::Key(ref key) => {
+                let pk = Pk::from_bytes(key)?;
+                pk.check()?;
+                pk.check_size()?;
+
+                Ok(Some(pk))
+            },
+            _ => Ok(None),
+        }
+    }
+
     /// Sets the `Output`'s custom payload.
     pub fn payload(mut self, payload: &P) -> Result<Self> {
         payload.check()?;
@@ -175,6 +215,7 @@ impl<D, A, P> Sizable for Output<D, A, P>
         self.id.size() +
             self.meta.size() +
             self.amount.size() +
+            self.lock.size() +
             self.payload.size()
     }
 }
@@ -194,6 +235,8 @@ impl<D, A, P> Checkable for Output<D, A, P>
         }
 
         self.amount.check()?;
+        self.lock.check()?;
+        self.lock.check_size()?;
         self.payload.check()?;
 
         Ok(())
@@ -217,17 +260,12 @@ impl<St, S, D, A, P>
     for Output<D, A, P>
     where   St: Store<S>,
             S: Datable + Serializable,
-            D: Ord + Datable + ConstantSize + Serializable,
+            D: Ord + Datable + ConstantSize + Serializable + StoreKey,
             A: Numerical + Serializable,
             P: Datable + Serializable
 {
     fn store_prefix() -> Vec<u8> {
-        let mut prefix = Vec::new();
-
-        let _prefix: [u8; 8] = unsafe { mem::transmute(OUTPUT_CODE) };
-        prefix.extend_from_slice(&_prefix[..]);
-
-        prefix
+        encode_prefix(OUTPUT_CODE)
     }
 
     fn store_key(&self) -> Result<D> {
@@ -0,0 +1,147 @@
+//! # Reorg
+//!
+//! `reorg` is the module providing the types used to move the state derived from the `Block`s of a
+//! `BlockGraph` (e.g. an `UtxoSet`) from a tip to another when the tip changes branch.
+//!
+//! A `Reorg` lists the `BlockNode`s to undo, the ones reachable from the old tip but not from the new
+//! one, from the highest to the lowest, and the `BlockNode`s to redo, the ones reachable from the new
+//! tip but not from the old one, from the lowest to the highest. The `Block`s are reverted and applied
+//! through a `StateTransition`, so that any state can be kept in sync with the `BlockGraph` tip.
+
+use std::collections::BTreeSet;
+
+use base::Result;
+use base::Checkable;
+use base::Datable;
+use base::Serializable;
+use base::{Sizable, ConstantSize};
+use base::Numerical;
+use crypto::Hash;
+use model::BlockNode;
+use model::Block;
+
+/// Trait implemented by the states derived from `Block`s, applied and reverted during a `Reorg`.
+pub trait StateTransition<D, A, IP, OP, TP, BP, BPr>
+    where   D: Ord + Datable + ConstantSize,
+            A: Numerical,
+            IP: Datable,
+            OP: Datable,
+            TP: Datable,
+            BP: Datable,
+            BPr: Datable
+{
+    /// Applies a `Block` to the state.
+    fn apply_block<H: Hash<D>>(&mut self, hasher: &mut H, block: &Block<D, A, IP, OP, TP, BP, BPr>)
+        -> Result<()>;
+
+    /// Reverts a `Block` from the state. The `Block` is the last applied one.
+    fn revert_block<H: Hash<D>>(&mut self, hasher: &mut H, block: &Block<D, A, IP, OP, TP, BP, BPr>)
+        -> Result<()>;
+}
+
+/// Type representing the reorganization of the state derived from a `BlockGraph` between two tips.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default, Hash, Serialize, Deserialize)]
+pub struct Reorg<D>
+    where   D: Ord + Datable + ConstantSize
+{
+    /// Highest common ancestor of the two tips, if any.
+    pub fork: Option<BlockNode<D>>,
+    /// `BlockNode`s to undo, from the highest to the lowest.
+    pub undo: Vec<BlockNode<D>>,
+    /// `BlockNode`s to redo, from the lowest to the highest.
+    pub redo: Vec<BlockNode<D>>,
+}
+
+impl<D> Reorg<D>
+    where   D: Ord + Datable + ConstantSize
+{
+    /// Builds a `Reorg` from the `BlockNode`s reachable from the old tip and from the new tip,
+    /// tips included. The common `BlockNode`s below the highest common ancestor can be omitted.
+    pub fn new(from: &[BlockNode<D>], to: &[BlockNode<D>]) -> Result<Self> {
+        for node in from.iter().chain(to.iter()) {
+            node.check()?;
+        }
+
+        let from_set: BTreeSet<&BlockNode<D>> = from.iter().collect();
+        let to_set: BTreeSet<&BlockNode<D>> = to.iter().collect();
+
+        let mut fork: Option<BlockNode<D>> = None;
+        let mut undo = Vec::new();
+        let mut redo = Vec::new();
+
+        for node in from_set.iter() {
+            if !to_set.contains(node) {
+                undo.push((*node).clone());
+                continue;
+            }
+
+            let is_higher = match fork {
+                Some(ref fork) => node.block_height > fork.block_height ||
+                    (node.block_height == fork.block_height && node.block_id < fork.block_id),
+                None => true,
+            };
+
+            if is_higher {
+                fork = Some((*node).clone());
+            }
+        }
+
+        for node in to_set.iter() {
+            if !from_set.contains(node) {
+                redo.push((*node).clone());
+            }
+        }
+
+        undo.sort_by(|a, b| (b.block_height, &b.block_id).cmp(&(a.block_height, &a.block_id)));
+        redo.sort_by(|a, b| (a.block_height, &a.block_id).cmp(&(b.block_height, &b.block_id)));
+
+        let reorg = Reorg {
+            fork,
+            undo,
+            redo,
+        };
+
+        Ok(reorg)
+    }
+
+    /// Returns if the `Reorg` does not change the state.
+    pub fn is_empty(&self) -> bool {
+        self.undo.is_empty() && self.redo.is_empty()
+    }
+}
+
+impl<D> Sizable for Reorg<D>
+    where   D: Ord + Datable + ConstantSize
+{
+    fn size(&self) -> u64 {
+        self.fork.size() +
+            self.undo.size() +
+            self.redo.size()
+    }
+}
+
+impl<D> Checkable for Reorg<D>
+    where   D: Ord + Datable + ConstantSize
+{
+    fn check(&self) -> Result<()> {
+        self.fork.check()?;
+        self.undo.check()?;
+        self.redo.check()?;
+
+        for node in self.undo.iter() {
+            if self.redo.contains(node) {
+                return Err(String::from("duplicates found"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<D> Serializable for Reorg<D>
+    where   D: Ord + Datable + ConstantSize + Serializable
+{}
+
+impl<D> Datable for Reorg<D>
+    where   D: Ord + Datable + ConstantSize
+{}
\ No newline at end of file
@@ -1,8 +1,22 @@
 //! # Transaction
 //!
 //! `transaction` is the module providing the type used to produce new `Output`s from one or more input `Transaction`s.
-
-use std::mem;
+//!
+//! The balance of a `Transaction` amounts is validated against a `FeePolicy`: either the inputs amount
+//! must match exactly the outputs amount, or it must cover it, the difference being the transaction fee.
+//! Amounts are summed with checked arithmetic, so an overflow is reported as an error.
+//!
+//! The inputs spending locked `Output`s are authorized by witnesses satisfying the `Lock`s of the
+//! spent outputs, usually signatures of the `Transaction` sighash, which never covers the ids of the
+//! `Transaction` and of its inputs, nor the inputs witnesses. `Transaction::check` checks that the locks and
+//! witnesses are well-formed and size-bounded, while `Transaction::verify_inputs` evaluates them.
+//!
+//! Each input witness selects with a `SighashMode` the parts of the `Transaction` its signatures commit
+//! to, so that multi-party transactions can be assembled incrementally: an input signed with
+//! `SighashMode::AnyoneCanPay` stays valid when other inputs are added, one signed with
+//! `SighashMode::SingleInput` also when other outputs are added, and one signed with
+//! `SighashMode::OutputsOnly` when any input is added, removed or changed. The mode is itself covered
+//! by the sighash, so it cannot be changed without invalidating the signatures.
 
 use base::Result;
 use base::Checkable;
@@ -12,14 +26,67 @@ use base::{Sizable, ConstantSize};
 use base::{Eval, EvalMut};
 use base::Numerical;
 use base::Meta;
-use crypto::Hash;
+use crypto::{Hash, Sign};
 use io::{Store, Storable};
+use io::store::prefix::encode_prefix;
+use io::StoreKey;
+use model::Coin;
 use model::Input;
 use model::Output;
+use model::Witness;
+use util::Timestamp;
 
 /// Code of the `Transaction` type.
 pub const TRANSACTION_CODE: u64 = 3;
 
+/// Policy used to validate the balance of the amounts of a `Transaction`.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Default, Hash, Serialize, Deserialize)]
+pub enum FeePolicy {
+    /// The inputs amount must be equal to the outputs amount.
+    #[default]
+    Exact,
+    /// The inputs amount must be greater or equal to the outputs amount. The difference is the fee.
+    Fee,
+}
+
+impl Sizable for FeePolicy {
+    fn size(&self) -> u64 {
+        0u8.size()
+    }
+}
+
+impl Checkable for FeePolicy {}
+
+impl Serializable for FeePolicy {}
+
+impl Datable for FeePolicy {}
+
+/// Mode used to select the parts of a `Transaction` covered by the sighash signed by an `Input`.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Default, Hash, Serialize, Deserialize)]
+pub enum SighashMode {
+    /// Covers all the inputs and all the outputs.
+    #[default]
+    All,
+    /// Covers the signed input and the output with the same index.
+    SingleInput,
+    /// Covers the signed input and all the outputs.
+    AnyoneCanPay,
+    /// Covers all the outputs, but none of the inputs.
+    OutputsOnly,
+}
+
+impl Sizable for SighashMode {
+    fn size(&self) -> u64 {
+        0u8.size()
+    }
+}
+
+impl Checkable for SighashMode {}
+
+impl Serializable for SighashMode {}
+
+impl Datable for SighashMode {}
+
 /// Type used to produce one or more `Output`s from one or more `Input`s.
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default, Hash, Serialize, Deserialize)]
 pub struct Transaction<D, A, IP, OP, P>
@@ -174,6 +241,259 @@ impl<D, A, IP, OP, P> Transaction<D, A, IP, OP, P>
         hasher.check(&msg, &digest)
     }
 
+    /// Returns the sum of the amounts of the `Transaction` inputs.
+    pub fn inputs_amount(&self) -> Result<A> {
+        for input in self.inputs.iter() {
+            if input.coin.out_amount < A::zero() {
+                return Err(String::from("invalid amount"));
+            }
+        }
+
+        A::checked_sum(self.inputs.iter().map(|input| &input.coin.out_amount))
+            .ok_or_else(|| String::from("amount overflow"))
+    }
+
+    /// Returns the sum of the amounts of the `Transaction` outputs.
+    pub fn outputs_amount(&self) -> Result<A> {
+        for output in self.outputs.iter() {
+            if output.amount < A::zero() {
+                return Err(String::from("invalid amount"));
+            }
+        }
+
+        A::checked_sum(self.outputs.iter().map(|output| &output.amount))
+            .ok_or_else(|| String::from("amount overflow"))
+    }
+
+    /// Returns the fee of the `Transaction`, that is the difference between the inputs amount and
+    /// the outputs amount.
+    pub fn fee(&self) -> Result<A> {
+        let inputs_amount = self.inputs_amount()?;
+        let outputs_amount = self.outputs_amount()?;
+
+        if inputs_amount < outputs_amount {
+            return Err(String::from("insufficient inputs amount"));
+        }
+
+        inputs_amount.checked_sub(&outputs_amount)
+            .ok_or_else(|| String::from("amount overflow"))
+    }
+
+    /// Verifies the balance of the `Transaction` amounts against a `FeePolicy`.
+    pub fn verify_balance(&self, policy: &FeePolicy) -> Result<bool> {
+        let inputs_amount = self.inputs_amount()?;
+        let outputs_amount = self.outputs_amount()?;
+
+        let balanced = match *policy {
+            FeePolicy::Exact => inputs_amount == outputs_amount,
+            FeePolicy::Fee => inputs_amount >= outputs_amount,
+        };
+
+        Ok(balanced)
+    }
+
+    /// Checks the balance of the `Transaction` amounts against a `FeePolicy`.
+    pub fn check_balance(&self, policy: &FeePolicy) -> Result<()> {
+        if !self.verify_balance(policy)? {
+            return Err(String::from("invalid balance"));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the `Coin`s derived from the `Transaction` outputs, in the same order as the outputs.
+    /// Each `Coin` is identified by the `Transaction` id, the output index and the output amount, and
+    /// has the `Transaction` metadata, so that the same coins are derived every time.
+    pub fn output_coins<H: Hash<D>>(&self, hasher: &mut H) -> Result<Vec<Coin<D, A>>>
+        where   D: Serializable,
+                A: Serializable
+    {
+        self.id.check()?;
+        self.id.check_size()?;
+
+        let mut coins = Vec::new();
+
+        for (idx, output) in self.outputs.iter().enumerate() {
+            let coin = Coin::new()
+                        .meta(&self.meta)?
+                        .output_data(&self.id, idx as u64, &output.amount)?
+                        .finalize(hasher)?;
+
+            coins.push(coin);
+        }
+
+        Ok(coins)
+    }
+
+    /// Returns the sighash of the `Transaction` in `SighashMode::All`. It covers the whole `Transaction`
+    /// but its id and the ids and witnesses of its inputs.
+    pub fn sighash<H: Hash<D>>(&self, hasher: &mut H) -> Result<D> {
+        self.input_sighash(hasher, 0, &SighashMode::All)
+    }
+
+    /// Returns the sighash signed by the input at index `idx` in a given `SighashMode`. The ids of the
+    /// `Transaction` and of the inputs and the inputs witnesses are never covered, while the mode is.
+    pub fn input_sighash<H: Hash<D>>(&self, hasher: &mut H, idx: u64, mode: &SighashMode) -> Result<D> {
+        let idx = idx as usize;
+
+        let mut transaction = self.clone();
+        transaction.id = D::default();
+
+        match *mode {
+            SighashMode::All => {},
+            SighashMode::SingleInput => {
+                if idx >= self.inputs.len() || idx >= self.outputs.len() {
+                    return Err(String::from("invalid index"));
+                }
+
+                transaction.inputs = vec![self.inputs[idx].clone()];
+                transaction.outputs = vec![self.outputs[idx].clone()];
+            },
+            SighashMode::AnyoneCanPay => {
+                if idx >= self.inputs.len() {
+                    return Err(String::from("invalid index"));
+                }
+
+                transaction.inputs = vec![self.inputs[idx].clone()];
+            },
+            SighashMode::OutputsOnly => {
+                transaction.inputs = Vec::new();
+            },
+        }
+
+        transaction.inputs_len = transaction.inputs.len() as u64;
+        transaction.outputs_len = transaction.outputs.len() as u64;
+
+        for input in transaction.inputs.iter_mut() {
+            input.id = D::default();
+            input.witness = Witness::new();
+
+            let size = input.size();
+            input.meta.set_size(size);
+        }
+
+        transaction.update_size();
+
+        let mut msg = transaction.to_bytes()?;
+        msg.extend_from_slice(&mode.to_bytes()?);
+
+        hasher.digest(&msg)
+    }
+
+    /// Signs the `Transaction` inputs with secret keys, given in the same order as the inputs, adding
+    /// the signatures to the inputs witnesses. Each input signs the sighash in the `SighashMode` of its
+    /// witness. Inputs without a secret key are left unchanged, and multisig inputs can be signed by
+    /// calling the method once per signer. The ids of the inputs
+    /// and of the `Transaction` are updated accordingly.
+    pub fn sign_inputs<H, Seed, Pk, Sk, Sig, Sg>(mut self,
+                                                 hasher: &mut H,
+                                                 signer: &mut Sg,
+                                                 sks: &[Option<Sk>])
+        -> Result<Self>
+        where   H: Hash<D>,
+                D: Serializable,
+                Input<D, A, IP>: Serializable,
+                Seed: Datable + ConstantSize,
+                Pk: Datable + ConstantSize,
+                Sk: Datable + ConstantSize,
+                Sig: Datable + ConstantSize + Serializable,
+                Sg: Sign<Seed, Pk, Sk, Sig>
+    {
+        self.check()?;
+
+        if sks.len() != self.inputs.len() {
+            return Err(String::from("invalid length"));
+        }
+
+        let mut inputs = Vec::new();
+
+        for (idx, (input, sk)) in self.inputs.iter().zip(sks.iter()).enumerate() {
+            let mut input = input.to_owned();
+
+            if let Some(ref sk) = *sk {
+                let msg = self.input_sighash(hasher, idx as u64, &input.witness.mode)?.to_bytes()?;
+                input = input.sign(&msg, signer, sk)?;
+                input.id = input.digest(hasher)?;
+            }
+
+            inputs.push(input);
+        }
+
+        self.inputs = inputs;
+        self.update_size();
+
+        self.id = self.digest(hasher)?;
+
+        self.check()?;
+
+        Ok(self)
+    }
+
+    /// Verifies the `Transaction` inputs witnesses against the locks of the spent outputs, given in
+    /// the same order as the inputs, at a given time. Each witness is verified against the sighash in
+    /// its own `SighashMode`.
+    pub fn verify_inputs<H, Seed, Pk, Sk, Sig, Sg>(&self,
+                                                   hasher: &mut H,
+                                                   signer: &mut Sg,
+                                                   outputs: &[Output<D, A, OP>],
+                                                   time: &Timestamp)
+        -> Result<bool>
+        where   H: Hash<D>,
+                D: Serializable,
+                Seed: Datable + ConstantSize,
+                Pk: Datable + ConstantSize + Serializable,
+                Sk: Datable + ConstantSize,
+                Sig: Datable + ConstantSize + Serializable,
+                Sg: Sign<Seed, Pk, Sk, Sig>
+    {
+        self.check()?;
+
+        for output in outputs.iter() {
+            output.check()?;
+        }
+
+        if outputs.len() != self.inputs.len() {
+            return Err(String::from("invalid length"));
+        }
+
+        for (idx, (input, output)) in self.inputs.iter().zip(outputs.iter()).enumerate() {
+            if input.coin.out_amount != output.amount {
+                return Ok(false);
+            }
+
+            let msg = self.input_sighash(hasher, idx as u64, &input.witness.mode)?.to_bytes()?;
+
+            if !output.lock.verify(&input.witness, &msg, time, hasher, signer)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Checks the `Transaction` inputs witnesses against the locks of the spent outputs, given in
+    /// the same order as the inputs, at a given time.
+    pub fn check_inputs<H, Seed, Pk, Sk, Sig, Sg>(&self,
+                                                  hasher: &mut H,
+                                                  signer: &mut Sg,
+                                                  outputs: &[Output<D, A, OP>],
+                                                  time: &Timestamp)
+        -> Result<()>
+        where   H: Hash<D>,
+                D: Serializable,
+                Seed: Datable + ConstantSize,
+                Pk: Datable + ConstantSize + Serializable,
+                Sk: Datable + ConstantSize,
+                Sig: Datable + ConstantSize + Serializable,
+                Sg: Sign<Seed, Pk, Sk, Sig>
+    {
+        if !self.verify_inputs(hasher, signer, outputs, time)? {
+            return Err(String::from("invalid witness"));
+        }
+
+        Ok(())
+    }
+
     /// Evals the `Transaction`.
     pub fn eval<Ev, EP, ER>(&self, params: &EP, evaluator: &Ev)
         -> Result<ER>
@@ -295,19 +615,14 @@ impl<St, S, D, A, IP, OP, P>
     for Transaction<D, A, IP, OP, P>
     where   St: Store<S>,
             S: Datable + Serializable,
-            D: Ord + Datable + ConstantSize + Serializable,
+            D: Ord + Datable + ConstantSize + Serializable + StoreKey,
             A: Numerical + Serializable,
             IP: Datable + Serializable,
             OP: Datable + Serializable,
             P: Datable + Serializable
 {
     fn store_prefix() -> Vec<u8> {
-        let mut prefix = Vec::new();
-
-        let _prefix: [u8; 8] = unsafe { mem::transmute(TRANSACTION_CODE) };
-        prefix.extend_from_slice(&_prefix[..]);
-
-        prefix
+        encode_prefix(TRANSACTION_CODE)
     }
 
     fn store_key(&self) -> Result<D> {
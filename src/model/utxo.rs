@@ -0,0 +1,522 @@
+//! # UTXO
+//!
+//! `utxo` is the module providing the `UtxoSet` type, the ledger of the `Coin`s derived from the
+//! `Transaction` outputs, backed by a `Store`.
+//!
+//! Applying a `Transaction` spends the coins of its inputs and creates a `Coin` for each of its
+//! outputs, identified by the `Transaction` id, the output index and the output amount. Unknown coins
+//! and double spends are rejected. Spent coins are kept in the set, marked with the id of the spending
+//! `Transaction`, so that transactions and blocks can be reverted, in the reverse order they were
+//! applied. The changes of a `Transaction` or of a `Block` are committed atomically in a single `Batch`.
+//!
+//! The balance of the applied transactions is checked against the `FeePolicy` of the set, set with
+//! `UtxoSet::fee_policy`, except for the transactions without inputs, which issue new coins. The set does
+//! not verify the witnesses of the transactions, which are verified with `Transaction::verify_inputs`,
+//! given the outputs returned by `UtxoSet::spent_outputs`.
+
+use std::marker::PhantomData;
+use std::collections::BTreeMap;
+
+use base::Result;
+use base::Checkable;
+use base::Datable;
+use base::Serializable;
+use base::{Sizable, ConstantSize};
+use base::Numerical;
+use crypto::Hash;
+use io::Permission;
+use io::{Store, Storable};
+use io::store::prefix::encode_prefix;
+use io::StoreKey;
+use io::IndexKey;
+use model::Coin;
+use model::Output;
+use model::Lock;
+use model::{Transaction, FeePolicy};
+use model::Block;
+use model::StateTransition;
+
+/// Code of the `Utxo` type.
+pub const UTXO_CODE: u64 = 11;
+
+/// Name of the index of the unspent `Utxo`s by `Lock`.
+pub const UTXO_LOCK_INDEX: &str = "lock";
+
+/// Type representing an entry of the `UtxoSet`: a `Coin` and the `Output` it is derived from.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default, Hash, Serialize, Deserialize)]
+pub struct Utxo<D, A, P>
+    where   D: Ord + Datable + ConstantSize,
+            A: Numerical,
+            P: Datable
+{
+    /// Coin derived from the output.
+    pub coin: Coin<D, A>,
+    /// Output the coin is derived from.
+    pub output: Output<D, A, P>,
+    /// Id of the `Transaction` spending the coin, if spent.
+    pub spent_by: Option<D>,
+}
+
+impl<D, A, P> Utxo<D, A, P>
+    where   D: Ord + Datable + ConstantSize,
+            A: Numerical,
+            P: Datable
+{
+    /// Creates a new unspent `Utxo`.
+    pub fn new(coin: &Coin<D, A>, output: &Output<D, A, P>) -> Result<Self> {
+        let utxo = Utxo {
+            coin: coin.to_owned(),
+            output: output.to_owned(),
+            spent_by: None,
+        };
+
+        utxo.check()?;
+
+        Ok(utxo)
+    }
+
+    /// Returns if the `Utxo` is spent.
+    pub fn is_spent(&self) -> bool {
+        self.spent_by.is_some()
+    }
+}
+
+impl<D, A, P> Sizable for Utxo<D, A, P>
+    where   D: Ord + Datable + ConstantSize,
+            A: Numerical,
+            P: Datable
+{
+    fn size(&self) -> u64 {
+        self.coin.size() +
+            self.output.size() +
+            self.spent_by.size()
+    }
+}
+
+impl<D, A, P> Checkable for Utxo<D, A, P>
+    where   D: Ord + Datable + ConstantSize,
+            A: Numerical,
+            P: Datable
+{
+    fn check(&self) -> Result<()> {
+        self.coin.check()?;
+        self.output.check()?;
+
+        if self.coin.out_amount != self.output.amount {
+            return Err(String::from("invalid amount"));
+        }
+
+        if let Some(ref spent_by) = self.spent_by {
+            spent_by.check()?;
+            spent_by.check_size()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<D, A, P> Serializable for Utxo<D, A, P>
+    where   D: Ord + Datable + ConstantSize + Serializable,
+            A: Numerical + Serializable,
+            P: Datable + Serializable
+{}
+
+impl<D, A, P> Datable for Utxo<D, A, P>
+    where   D: Ord + Datable + ConstantSize,
+            A: Numerical,
+            P: Datable
+{}
+
+impl<St, S, D, A, P>
+    Storable<St, S, D, Utxo<D, A, P>>
+    for Utxo<D, A, P>
+    where   St: Store<S>,
+            S: Datable + Serializable,
+            D: Ord + Datable + ConstantSize + Serializable + StoreKey,
+            A: Numerical + Serializable,
+            P: Datable + Serializable
+{
+    fn store_prefix() -> Vec<u8> {
+        encode_prefix(UTXO_CODE)
+    }
+
+    fn store_key(&self) -> Result<D> {
+        self.coin.id.check()?;
+
+        Ok(self.coin.id.clone())
+    }
+
+    fn store_value(&self) -> Result<Self> {
+        self.check()?;
+
+        Ok(self.clone())
+    }
+
+    fn store_indexes(&self) -> Result<Vec<IndexKey>> {
+        if self.is_spent() {
+            return Ok(Vec::new());
+        }
+
+        let lock_key = IndexKey::from_key(UTXO_LOCK_INDEX, &self.output.lock.to_bytes()?)?;
+
+        Ok(vec![lock_key])
+    }
+}
+
+/// Type used to track the spent and unspent `Coin`s of a ledger in a `Store`.
+pub struct UtxoSet<St, S, D, A, P>
+    where   St: Store<S>,
+            S: Datable + Serializable,
+            D: Ord + Datable + ConstantSize + Serializable + StoreKey,
+            A: Numerical + Serializable,
+            P: Datable + Serializable
+{
+    store: St,
+    fee_policy: FeePolicy,
+    _marker: PhantomData<(S, D, A, P)>,
+}
+
+impl<St, S, D, A, P> UtxoSet<St, S, D, A, P>
+    where   St: Store<S>,
+            S: Datable + Serializable,
+            D: Ord + Datable + ConstantSize + Serializable + StoreKey,
+            A: Numerical + Serializable,
+            P: Datable + Serializable
+{
+    /// Creates a new `UtxoSet` backed by a `Store`.
+    pub fn new(store: St) -> Self {
+        UtxoSet {
+            store,
+            fee_policy: FeePolicy::default(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets the `FeePolicy` checked on the balance of the applied transactions.
+    pub fn fee_policy(mut self, policy: &FeePolicy) -> Result<Self> {
+        policy.check()?;
+
+        self.fee_policy = policy.to_owned();
+
+        Ok(self)
+    }
+
+    /// Returns the `Store` backing the `UtxoSet`.
+    pub fn store(&mut self) -> &mut St {
+        &mut self.store
+    }
+
+    /// Consumes the `UtxoSet`, returning its `Store`.
+    pub fn into_store(self) -> St {
+        self.store
+    }
+
+    /// Lookups a `Utxo`, spent or unspent, from its coin id.
+    pub fn lookup(&mut self, id: &D) -> Result<bool> {
+        Utxo::<D, A, P>::store_lookup(&mut self.store, id)
+    }
+
+    /// Retrieves a `Utxo`, spent or unspent, from its coin id.
+    pub fn get(&mut self, id: &D) -> Result<Utxo<D, A, P>> {
+        Utxo::store_get(&mut self.store, id)
+    }
+
+    /// Returns if a `Coin` is in the `UtxoSet` and unspent.
+    pub fn is_unspent(&mut self, coin: &Coin<D, A>) -> Result<bool> {
+        coin.check()?;
+
+        if !self.lookup(&coin.id)? {
+            return Ok(false);
+        }
+
+        let utxo = self.get(&coin.id)?;
+
+        Ok(&utxo.coin == coin && !utxo.is_spent())
+    }
+
+    /// Returns the outputs spent by the inputs of a `Transaction`, in the same order as the inputs.
+    pub fn spent_outputs<IP, TP>(&mut self, transaction: &Transaction<D, A, IP, P, TP>)
+        -> Result<Vec<Output<D, A, P>>>
+        where   IP: Datable + Serializable,
+                TP: Datable + Serializable
+    {
+        let mut outputs = Vec::new();
+
+        for input in transaction.inputs.iter() {
+            let utxo = self.get(&input.coin.id)?;
+
+            if utxo.coin != input.coin {
+                return Err(String::from("invalid coin"));
+            }
+
+            outputs.push(utxo.output);
+        }
+
+        Ok(outputs)
+    }
+
+    /// Returns the unspent `Utxo`s locked by a `Lock`.
+    pub fn unspent(&mut self, lock: &Lock) -> Result<Vec<Utxo<D, A, P>>> {
+        lock.check()?;
+
+        let index_key = IndexKey::from_key(UTXO_LOCK_INDEX, &lock.to_bytes()?)?;
+
+        Utxo::store_get_by_index(&mut self.store, &index_key)
+    }
+
+    /// Returns the sum of the amounts of the unspent `Utxo`s locked by a `Lock`.
+    pub fn balance(&mut self, lock: &Lock) -> Result<A> {
+        let utxos = self.unspent(lock)?;
+
+        A::checked_sum(utxos.iter().map(|utxo| &utxo.coin.out_amount))
+            .ok_or_else(|| String::from("amount overflow"))
+    }
+
+    /// Returns the sum of the amounts of all the unspent `Utxo`s.
+    pub fn total_balance(&mut self) -> Result<A> {
+        let utxos = Utxo::<D, A, P>::store_list(&mut self.store, None, None, None, 0)?;
+
+        A::checked_sum(utxos.iter().filter(|utxo| !utxo.is_spent()).map(|utxo| &utxo.coin.out_amount))
+            .ok_or_else(|| String::from("amount overflow"))
+    }
+
+    /// Applies a `Transaction`, spending the coins of its inputs and adding the coins derived from its
+    /// outputs.
+    pub fn apply_transaction<H, IP, TP>(&mut self, hasher: &mut H, transaction: &Transaction<D, A, IP, P, TP>)
+        -> Result<()>
+        where   H: Hash<D>,
+                IP: Datable + Serializable,
+                TP: Datable + Serializable
+    {
+        let mut changes = BTreeMap::new();
+
+        self.stage_apply(&mut changes, hasher, transaction)?;
+
+        self.commit(changes)
+    }
+
+    /// Reverts a `Transaction`, removing the coins derived from its outputs and restoring the coins of
+    /// its inputs as unspent. The `Transaction` outputs should be unspent.
+    pub fn revert_transaction<H, IP, TP>(&mut self, hasher: &mut H, transaction: &Transaction<D, A, IP, P, TP>)
+        -> Result<()>
+        where   H: Hash<D>,
+                IP: Datable + Serializable,
+                TP: Datable + Serializable
+    {
+        let mut changes = BTreeMap::new();
+
+        self.stage_revert(&mut changes, hasher, transaction)?;
+
+        self.commit(changes)
+    }
+
+    /// Applies the transactions of a `Block`, in order. Either all the transactions are applied or none is.
+    pub fn apply_block<H, IP, TP, BP, Pr>(&mut self, hasher: &mut H, block: &Block<D, A, IP, P, TP, BP, Pr>)
+        -> Result<()>
+        where   H: Hash<D>,
+                IP: Datable + Serializable,
+                TP: Datable + Serializable,
+                BP: Datable + Serializable,
+                Pr: Datable + Serializable
+    {
+        block.check()?;
+        block.check_digest(hasher)?;
+        block.check_transactions_root(hasher)?;
+
+        let mut changes = BTreeMap::new();
+
+        for transaction in block.transactions.iter() {
+            self.stage_apply(&mut changes, hasher, transaction)?;
+        }
+
+        self.commit(changes)
+    }
+
+    /// Reverts the transactions of a `Block`, in reverse order. Either all the transactions are
+    /// reverted or none is.
+    pub fn revert_block<H, IP, TP, BP, Pr>(&mut self, hasher: &mut H, block: &Block<D, A, IP, P, TP, BP, Pr>)
+        -> Result<()>
+        where   H: Hash<D>,
+                IP: Datable + Serializable,
+                TP: Datable + Serializable,
+                BP: Datable + Serializable,
+                Pr: Datable + Serializable
+    {
+        block.check()?;
+        block.check_digest(hasher)?;
+        block.check_transactions_root(hasher)?;
+
+        let mut changes = BTreeMap::new();
+
+        for transaction in block.transactions.iter().rev() {
+            self.stage_revert(&mut changes, hasher, transaction)?;
+        }
+
+        self.commit(changes)
+    }
+
+    /// Retrieves a `Utxo` from the staged changes, or from the store if not changed.
+    fn fetch(&mut self, changes: &BTreeMap<D, Option<Utxo<D, A, P>>>, id: &D)
+        -> Result<Option<Utxo<D, A, P>>>
+    {
+        if let Some(utxo) = changes.get(id) {
+            return Ok(utxo.to_owned());
+        }
+
+        if !self.lookup(id)? {
+            return Ok(None);
+        }
+
+        Ok(Some(self.get(id)?))
+    }
+
+    /// Stages the changes of the application of a `Transaction`. The balance of a `Transaction` with
+    /// inputs is checked against the `FeePolicy` of the `UtxoSet`.
+    fn stage_apply<H, IP, TP>(&mut self,
+                              changes: &mut BTreeMap<D, Option<Utxo<D, A, P>>>,
+                              hasher: &mut H,
+                              transaction: &Transaction<D, A, IP, P, TP>)
+        -> Result<()>
+        where   H: Hash<D>,
+                IP: Datable + Serializable,
+                TP: Datable + Serializable
+    {
+        transaction.check()?;
+        transaction.check_digest(hasher)?;
+
+        if !transaction.inputs.is_empty() {
+            transaction.check_balance(&self.fee_policy)?;
+        }
+
+        for input in transaction.inputs.iter() {
+            let mut utxo = match self.fetch(changes, &input.coin.id)? {
+                Some(utxo) => utxo,
+                None => return Err(String::from("not found")),
+            };
+
+            if utxo.coin != input.coin {
+                return Err(String::from("invalid coin"));
+            }
+
+            if utxo.is_spent() {
+                return Err(String::from("already spent"));
+            }
+
+            utxo.spent_by = Some(transaction.id.clone());
+
+            changes.insert(input.coin.id.clone(), Some(utxo));
+        }
+
+        let coins = transaction.output_coins(hasher)?;
+
+        for (coin, output) in coins.iter().zip(transaction.outputs.iter()) {
+            if self.fetch(changes, &coin.id)?.is_some() {
+                return Err(String::from("already found"));
+            }
+
+            changes.insert(coin.id.clone(), Some(Utxo::new(coin, output)?));
+        }
+
+        Ok(())
+    }
+
+    /// Stages the changes of the reversion of a `Transaction`.
+    fn stage_revert<H, IP, TP>(&mut self,
+                               changes: &mut BTreeMap<D, Option<Utxo<D, A, P>>>,
+                               hasher: &mut H,
+                               transaction: &Transaction<D, A, IP, P, TP>)
+        -> Result<()>
+        where   H: Hash<D>,
+                IP: Datable + Serializable,
+                TP: Datable + Serializable
+    {
+        transaction.check()?;
+        transaction.check_digest(hasher)?;
+
+        let coins = transaction.output_coins(hasher)?;
+
+        for coin in coins.iter() {
+            let utxo = match self.fetch(changes, &coin.id)? {
+                Some(utxo) => utxo,
+                None => return Err(String::from("not found")),
+            };
+
+            if utxo.is_spent() {
+                return Err(String::from("already spent"));
+            }
+
+            changes.insert(coin.id.clone(), None);
+        }
+
+        for input in transaction.inputs.iter().rev() {
+            let mut utxo = match self.fetch(changes, &input.coin.id)? {
+                Some(utxo) => utxo,
+                None => return Err(String::from("not found")),
+            };
+
+            if utxo.spent_by != Some(transaction.id.clone()) {
+                return Err(String::from("not spent"));
+            }
+
+            utxo.spent_by = None;
+
+            changes.insert(input.coin.id.clone(), Some(utxo));
+        }
+
+        Ok(())
+    }
+
+    /// Commits the staged changes in a single `Batch`.
+    fn commit(&mut self, changes: BTreeMap<D, Option<Utxo<D, A, P>>>) -> Result<()> {
+        let session = self.store.session(&Permission::Write)?;
+
+        let mut batch = self.store.begin(&session)?;
+
+        for (id, utxo) in changes.into_iter() {
+            let found = self.lookup(&id)?;
+
+            match utxo {
+                Some(utxo) => {
+                    if found {
+                        utxo.store_batch_update(&mut self.store, &mut batch)?;
+                    } else {
+                        utxo.store_batch_create(&mut self.store, &mut batch)?;
+                    }
+                },
+                None => {
+                    if found {
+                        self.get(&id)?.store_batch_delete(&mut self.store, &mut batch)?;
+                    }
+                },
+            }
+        }
+
+        self.store.commit(&session, &batch)
+    }
+}
+
+impl<St, S, D, A, IP, P, TP, BP, Pr>
+    StateTransition<D, A, IP, P, TP, BP, Pr>
+    for UtxoSet<St, S, D, A, P>
+    where   St: Store<S>,
+            S: Datable + Serializable,
+            D: Ord + Datable + ConstantSize + Serializable + StoreKey,
+            A: Numerical + Serializable,
+            IP: Datable + Serializable,
+            P: Datable + Serializable,
+            TP: Datable + Serializable,
+            BP: Datable + Serializable,
+            Pr: Datable + Serializable
+{
+    fn apply_block<H: Hash<D>>(&mut self, hasher: &mut H, block: &Block<D, A, IP, P, TP, BP, Pr>)
+        -> Result<()>
+    {
+        UtxoSet::apply_block(self, hasher, block)
+    }
+
+    fn revert_block<H: Hash<D>>(&mut self, hasher: &mut H, block: &Block<D, A, IP, P, TP, BP, Pr>)
+        -> Result<()>
+    {
+        UtxoSet::revert_block(self, hasher, block)
+    }
+}
\ No newline at end of file
@@ -2,8 +2,6 @@
 //!
 //! `wallet` is the module providing the type used for wallets (accounts) in the distributed ledger.
 
-use std::mem;
-
 use base::Result;
 use base::Checkable;
 use base::Datable;
@@ -14,6 +12,8 @@ use base::Numerical;
 use base::Meta;
 use crypto::Hash;
 use io::{Store, Storable};
+use io::store::prefix::encode_prefix;
+use io::StoreKey;
 use model::Coin;
 
 /// Code of the `Wallet` type.
@@ -389,17 +389,12 @@ impl<St, S, D, A, P>
     for Wallet<D, A, P>
     where   St: Store<S>,
             S: Datable + Serializable,
-            D: Ord + Datable + ConstantSize + Serializable,
+            D: Ord + Datable + ConstantSize + Serializable + StoreKey,
             A: Numerical + Serializable,
             P: Datable + Serializable
 {
     fn store_prefix() -> Vec<u8> {
-        let mut prefix = Vec::new();
-
-        let _prefix: [u8; 8] = unsafe { mem::transmute(WALLET_CODE) };
-        prefix.extend_from_slice(&_prefix[..]);
-
-        prefix
+        encode_prefix(WALLET_CODE)
     }
 
     fn store_key(&self) -> Result<D> {
@@ -0,0 +1,106 @@
+use mitrid_core::base::Checkable;
+use mitrid_core::base::Serializable;
+use mitrid_core::base::Numerical;
+use mitrid_core::base::BigUint;
+
+#[test]
+fn test_biguint_conversions() {
+    let n = BigUint::from(u64::max_value());
+    assert_eq!(n.to_u64(), Some(u64::max_value()));
+    assert_eq!(n.to_u128(), Some(u128::from(u64::max_value())));
+
+    let n = BigUint::from(u128::max_value());
+    assert_eq!(n.to_u64(), None);
+    assert_eq!(n.to_u128(), Some(u128::max_value()));
+
+    let zero = BigUint::from(0u64);
+    assert_eq!(zero, BigUint::zero());
+    assert!(zero.limbs().is_empty());
+    assert!(zero.is_zero());
+}
+
+#[test]
+fn test_biguint_parse() {
+    let s = "340282366920938463463374607431768211456";
+
+    let res = BigUint::parse(s);
+    assert!(res.is_ok());
+
+    let n = res.unwrap();
+    assert_eq!(n, BigUint::from(u128::max_value()) + BigUint::from(1u32));
+    assert_eq!(format!("{}", n), String::from(s));
+
+    assert_eq!(format!("{}", BigUint::new()), String::from("0"));
+    assert_eq!(format!("{}", BigUint::parse("1000000000").unwrap()), String::from("1000000000"));
+
+    let res = BigUint::parse("");
+    assert!(res.is_err());
+
+    let res = BigUint::parse("12a");
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_biguint_ord() {
+    let a = BigUint::from(u64::max_value());
+    let b = BigUint::from(u128::from(u64::max_value()) + 1);
+    let c = BigUint::from(1u32);
+
+    assert!(a < b);
+    assert!(c < a);
+    assert!(BigUint::new() < c);
+}
+
+#[test]
+fn test_biguint_arithmetic() {
+    let a = u128::from(u64::max_value());
+    let b = 12_345_678_901_234_567_890u128;
+
+    let big_a = BigUint::from(a);
+    let big_b = BigUint::from(b);
+
+    assert_eq!(big_a.clone() + big_b.clone(), BigUint::from(a + b));
+    assert_eq!(big_a.clone() - big_b.clone(), BigUint::from(a - b));
+    assert_eq!(big_a.clone() * BigUint::from(3u32), BigUint::from(a * 3));
+
+    assert_eq!(big_b.checked_sub(&big_a), None);
+    assert_eq!(big_b.saturating_sub(&big_a), BigUint::zero());
+
+    let max = BigUint::from(u128::max_value());
+    let square = max.checked_mul(&max).unwrap();
+    let expected = BigUint::parse("115792089237316195423570985008687907852589419931798687112530834793049593217025").unwrap();
+    assert_eq!(square, expected);
+
+    let values = vec![max.clone(), max.clone(), BigUint::from(2u32)];
+    let sum = BigUint::checked_sum(&values).unwrap();
+    assert_eq!(sum, (max + BigUint::from(1u32)) * BigUint::from(2u32));
+}
+
+#[test]
+fn test_biguint_check() {
+    let n = BigUint::from(1u64 << 40);
+
+    let res = n.check();
+    assert!(res.is_ok());
+
+    let n = BigUint::from_limbs(&[1, 0, 0]);
+    assert_eq!(n.limbs().len(), 1);
+
+    let res = BigUint::from_json("{\"limbs\":[1,0]}");
+    assert!(res.is_ok());
+
+    let res = res.unwrap().check();
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_biguint_bytes() {
+    let n = BigUint::from(u128::max_value());
+
+    let res = n.to_bytes();
+    assert!(res.is_ok());
+
+    let res = BigUint::from_bytes(&res.unwrap());
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), n);
+}
\ No newline at end of file
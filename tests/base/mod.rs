@@ -1,3 +1,5 @@
 mod serialize;
 mod stage;
-mod meta;
\ No newline at end of file
+mod meta;
+mod numerical;
+mod bignum;
\ No newline at end of file
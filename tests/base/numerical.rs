@@ -0,0 +1,79 @@
+use mitrid_core::base::Numerical;
+
+#[test]
+fn test_numerical_zero() {
+    assert_eq!(<u8 as Numerical>::zero(), 0);
+    assert_eq!(<i64 as Numerical>::zero(), 0);
+
+    assert!(Numerical::is_zero(&0u32));
+    assert!(!Numerical::is_zero(&1i16));
+}
+
+#[test]
+fn test_numerical_checked() {
+    assert_eq!(Numerical::checked_add(&250u8, &5u8), Some(255));
+    assert_eq!(Numerical::checked_add(&250u8, &6u8), None);
+
+    assert_eq!(Numerical::checked_sub(&5u64, &5u64), Some(0));
+    assert_eq!(Numerical::checked_sub(&5u64, &6u64), None);
+    assert_eq!(Numerical::checked_sub(&-120i8, &9i8), None);
+
+    assert_eq!(Numerical::checked_mul(&2u16, &3u16), Some(6));
+    assert_eq!(Numerical::checked_mul(&u16::max_value(), &2u16), None);
+}
+
+#[test]
+fn test_numerical_saturating() {
+    assert_eq!(Numerical::saturating_add(&250u8, &10u8), 255);
+    assert_eq!(Numerical::saturating_sub(&5u32, &10u32), 0);
+    assert_eq!(Numerical::saturating_sub(&-120i8, &10i8), -128);
+    assert_eq!(Numerical::saturating_mul(&i32::max_value(), &2i32), i32::max_value());
+}
+
+#[test]
+fn test_numerical_sum() {
+    let values: Vec<u64> = vec![1, 2, 3];
+
+    assert_eq!(u64::checked_sum(&values), Some(6));
+    assert_eq!(u64::saturating_sum(&values), 6);
+
+    let empty: Vec<u64> = vec![];
+
+    assert_eq!(u64::checked_sum(&empty), Some(0));
+
+    let values: Vec<u64> = vec![u64::max_value(), 1];
+
+    assert_eq!(u64::checked_sum(&values), None);
+    assert_eq!(u64::saturating_sum(&values), u64::max_value());
+}
+
+#[test]
+fn test_numerical_128() {
+    assert_eq!(<u128 as Numerical>::zero(), 0);
+    assert_eq!(<i128 as Numerical>::zero(), 0);
+
+    assert_eq!(Numerical::checked_add(&(u128::max_value() - 1), &1u128), Some(u128::max_value()));
+    assert_eq!(Numerical::checked_add(&u128::max_value(), &1u128), None);
+    assert_eq!(Numerical::checked_sub(&0u128, &1u128), None);
+    assert_eq!(Numerical::checked_mul(&(u128::max_value() / 2), &2u128), Some(u128::max_value() - 1));
+    assert_eq!(Numerical::checked_mul(&u128::max_value(), &2u128), None);
+
+    assert_eq!(Numerical::checked_add(&i128::max_value(), &1i128), None);
+    assert_eq!(Numerical::checked_sub(&(i128::min_value() + 1), &1i128), Some(i128::min_value()));
+    assert_eq!(Numerical::checked_sub(&i128::min_value(), &1i128), None);
+    assert_eq!(Numerical::checked_mul(&i128::min_value(), &-1i128), None);
+
+    assert_eq!(Numerical::saturating_add(&u128::max_value(), &1u128), u128::max_value());
+    assert_eq!(Numerical::saturating_sub(&0u128, &1u128), 0);
+    assert_eq!(Numerical::saturating_sub(&i128::min_value(), &1i128), i128::min_value());
+    assert_eq!(Numerical::saturating_mul(&i128::max_value(), &2i128), i128::max_value());
+
+    let values: Vec<u128> = vec![u128::from(u64::max_value()), u128::from(u64::max_value())];
+
+    assert_eq!(u128::checked_sum(&values), Some(u128::from(u64::max_value()) * 2));
+
+    let values: Vec<u128> = vec![u128::max_value(), 1];
+
+    assert_eq!(u128::checked_sum(&values), None);
+    assert_eq!(u128::saturating_sum(&values), u128::max_value());
+}
\ No newline at end of file
@@ -0,0 +1,207 @@
+use mitrid_core::base::Checkable;
+use mitrid_core::base::Serializable;
+use mitrid_core::crypto::Hash;
+use mitrid_core::crypto::{MerkleTree, MerkleProof};
+
+use fixture::crypto::{Digest, Hasher};
+
+fn leaves(len: u8) -> Vec<Digest> {
+    (0..len).map(|n| Digest::from_slice(&[n + 1; 64]).unwrap()).collect()
+}
+
+#[test]
+fn test_merkle_tree_new() {
+    let mut hasher = Hasher{};
+
+    let res = MerkleTree::new(&mut hasher, &[]);
+    assert!(res.is_ok());
+
+    let tree = res.unwrap();
+    assert_eq!(tree.leaves_len, 0);
+    assert_eq!(tree.root().unwrap(), hasher.digest(&[]).unwrap());
+
+    let res = tree.proof(0);
+    assert!(res.is_err());
+
+    let mut roots = vec![tree.root().unwrap()];
+
+    for len in 1..10 {
+        let leaves = leaves(len);
+
+        let res = MerkleTree::new(&mut hasher, &leaves);
+        assert!(res.is_ok());
+
+        let tree = res.unwrap();
+        assert_eq!(tree.leaves_len, len as u64);
+        assert_eq!(tree.root().unwrap(), MerkleTree::new(&mut hasher, &leaves).unwrap().root().unwrap());
+        assert!(!roots.contains(&tree.root().unwrap()));
+        assert!(!leaves.contains(&tree.root().unwrap()));
+
+        roots.push(tree.root().unwrap());
+    }
+
+    let mut swapped_leaves = leaves(4);
+    swapped_leaves.swap(1, 2);
+
+    let tree = MerkleTree::new(&mut hasher, &leaves(4)).unwrap();
+    let swapped_tree = MerkleTree::new(&mut hasher, &swapped_leaves).unwrap();
+    assert_ne!(tree.root().unwrap(), swapped_tree.root().unwrap());
+
+    let mut duplicated_leaves = leaves(3);
+    let last_leaf = duplicated_leaves[2].clone();
+    duplicated_leaves.push(last_leaf);
+
+    let tree = MerkleTree::new(&mut hasher, &leaves(3)).unwrap();
+    let duplicated_tree = MerkleTree::new(&mut hasher, &duplicated_leaves).unwrap();
+    assert_ne!(tree.root().unwrap(), duplicated_tree.root().unwrap());
+}
+
+#[test]
+fn test_merkle_tree_check() {
+    let mut hasher = Hasher{};
+
+    for len in 0..10 {
+        let tree = MerkleTree::new(&mut hasher, &leaves(len)).unwrap();
+
+        let res = tree.check();
+        assert!(res.is_ok());
+    }
+
+    let tree = MerkleTree::<Digest>::default();
+
+    let res = tree.check();
+    assert!(res.is_err());
+
+    let res = tree.root();
+    assert!(res.is_err());
+
+    let res = tree.proof(0);
+    assert!(res.is_err());
+
+    let tree = MerkleTree::new(&mut hasher, &leaves(5)).unwrap();
+
+    let mut invalid_tree = tree.clone();
+    invalid_tree.leaves_len = 4;
+
+    let res = invalid_tree.root();
+    assert!(res.is_err());
+
+    let mut invalid_tree = tree.clone();
+    invalid_tree.levels.pop();
+
+    let res = invalid_tree.root();
+    assert!(res.is_err());
+
+    let mut invalid_tree = tree.clone();
+    invalid_tree.levels[1].pop();
+
+    let res = invalid_tree.proof(0);
+    assert!(res.is_err());
+
+    let mut invalid_tree = tree.clone();
+    invalid_tree.levels.push(vec![]);
+
+    let res = invalid_tree.root();
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_merkle_proof_verify() {
+    let mut hasher = Hasher{};
+
+    for len in 1..10 {
+        let leaves = leaves(len);
+        let tree = MerkleTree::new(&mut hasher, &leaves).unwrap();
+        let root = tree.root().unwrap();
+
+        for (idx, leaf) in leaves.iter().enumerate() {
+            let res = tree.proof(idx as u64);
+            assert!(res.is_ok());
+
+            let proof = res.unwrap();
+            assert!(proof.check().is_ok());
+
+            let res = proof.verify(&mut hasher, leaf, &root);
+            assert!(res.is_ok());
+            assert!(res.unwrap());
+
+            let res = proof.check_root(&mut hasher, leaf, &root);
+            assert!(res.is_ok());
+
+            let other_leaf = &leaves[(idx + 1) % leaves.len()];
+
+            if other_leaf != leaf {
+                let res = proof.verify(&mut hasher, other_leaf, &root);
+                assert!(res.is_ok());
+                assert!(!res.unwrap());
+            }
+
+            let res = proof.verify(&mut hasher, leaf, &Digest::default());
+            assert!(res.is_ok());
+            assert!(!res.unwrap());
+        }
+
+        let res = tree.proof(len as u64);
+        assert!(res.is_err());
+    }
+
+    let leaves = leaves(5);
+    let tree = MerkleTree::new(&mut hasher, &leaves).unwrap();
+    let root = tree.root().unwrap();
+    let proof = tree.proof(2).unwrap();
+
+    let mut invalid_proof = proof.clone();
+    invalid_proof.leaf_idx = 3;
+
+    let res = invalid_proof.verify(&mut hasher, &leaves[2], &root);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let mut invalid_proof = proof.clone();
+    invalid_proof.siblings.pop();
+
+    let res = invalid_proof.verify(&mut hasher, &leaves[2], &root);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let mut invalid_proof = proof.clone();
+    invalid_proof.siblings.push(Digest::default());
+
+    let res = invalid_proof.check_root(&mut hasher, &leaves[2], &root);
+    assert!(res.is_err());
+
+    let mut invalid_proof = proof.clone();
+    invalid_proof.leaf_idx = invalid_proof.leaves_len;
+
+    let res = invalid_proof.check();
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_merkle_tree_bytes() {
+    let mut hasher = Hasher{};
+
+    let tree = MerkleTree::new(&mut hasher, &leaves(3)).unwrap();
+
+    let res = tree.to_bytes();
+    assert!(res.is_ok());
+
+    let res = MerkleTree::from_bytes(&res.unwrap());
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), tree);
+}
+
+#[test]
+fn test_merkle_proof_bytes() {
+    let mut hasher = Hasher{};
+
+    let tree = MerkleTree::new(&mut hasher, &leaves(3)).unwrap();
+    let proof = tree.proof(1).unwrap();
+
+    let res = proof.to_bytes();
+    assert!(res.is_ok());
+
+    let res = MerkleProof::from_bytes(&res.unwrap());
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), proof);
+}
\ No newline at end of file
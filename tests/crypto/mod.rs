@@ -0,0 +1 @@
+mod merkle;
\ No newline at end of file
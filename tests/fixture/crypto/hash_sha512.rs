@@ -8,6 +8,7 @@ use mitrid_core::base::Checkable;
 use mitrid_core::base::Serializable;
 use mitrid_core::base::Datable;
 use mitrid_core::crypto::Hash;
+use mitrid_core::io::StoreKey;
 
 pub const DIGEST_SIZE: u64 = DIGESTBYTES as u64;
 
@@ -71,6 +72,18 @@ impl Serializable for Digest {}
 
 impl Datable for Digest {}
 
+impl StoreKey for Digest {
+    fn encode_store_key(&self, buf: &mut Vec<u8>) -> Result<()> {
+        self.0.encode_store_key(buf)
+    }
+
+    fn decode_store_key(buf: &[u8]) -> Result<(Digest, usize)> {
+        let (digest, len) = Vec::decode_store_key(buf)?;
+
+        Ok((Digest::from_vec(&digest)?, len))
+    }
+}
+
 pub struct SHA512 {}
 
 impl SHA512 {
@@ -6,6 +6,7 @@ use mitrid_core::base::{Sizable, VariableSize};
 use mitrid_core::base::Checkable;
 use mitrid_core::base::Serializable;
 use mitrid_core::base::Datable;
+use mitrid_core::io::StoreKey;
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash, Serialize, Deserialize)]
 pub struct Address(pub String);
@@ -51,4 +52,16 @@ impl Checkable for Address {}
 
 impl Serializable for Address {}
 
-impl Datable for Address {}
\ No newline at end of file
+impl Datable for Address {}
+
+impl StoreKey for Address {
+    fn encode_store_key(&self, buf: &mut Vec<u8>) -> Result<()> {
+        self.0.encode_store_key(buf)
+    }
+
+    fn decode_store_key(buf: &[u8]) -> Result<(Address, usize)> {
+        let (addr, len) = String::decode_store_key(buf)?;
+
+        Ok((Address(addr), len))
+    }
+}
\ No newline at end of file
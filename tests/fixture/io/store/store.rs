@@ -1,5 +1,5 @@
 use std::sync::{Arc, Mutex};
-use std::collections::HashMap;
+use std::collections::{HashMap, BTreeMap};
 
 use mitrid_core::base::Result;
 use mitrid_core::base::Sizable;
@@ -7,6 +7,8 @@ use mitrid_core::base::Checkable;
 use mitrid_core::util::{Timestamp, TimestampDiff};
 use mitrid_core::io::Permission;
 use mitrid_core::io::Store as BasicStore;
+use mitrid_core::io::Direction;
+use mitrid_core::io::store::iter::prefix_range;
 
 use fixture::io::Session;
 
@@ -15,7 +17,7 @@ pub const SESSION_DURATION: u64 = 3600;
 #[derive(Debug, Default)]
 pub struct Store {
     pub(crate) sessions: Arc<Mutex<HashMap<u64, Session>>>,
-    pub(crate) items: Arc<Mutex<HashMap<Vec<u8>, Vec<u8>>>>,
+    pub(crate) items: Arc<Mutex<BTreeMap<Vec<u8>, Vec<u8>>>>,
 }
 
 impl Store {
@@ -99,7 +101,7 @@ impl BasicStore<()> for Store {
             return Err(String::from("expired session"));
         }
 
-        if session.permission > Permission::Read {
+        if !session.permission.can_read() {
             return Err(String::from("invalid permission")).into();
         }
 
@@ -149,10 +151,12 @@ impl BasicStore<()> for Store {
 
     fn count_prefix(&mut self,
                     session: &Session,
-                    _prefix: &[u8])
+                    prefix: &[u8])
         -> Result<u64>
     {
-        self.count(session, None, None,)
+        let (from, to) = prefix_range(prefix);
+
+        self.count(session, from, to)
     }
     
     fn list(&mut self,
@@ -169,7 +173,7 @@ impl BasicStore<()> for Store {
             return Err(String::from("expired session"));
         }
 
-        if session.permission > Permission::Read {
+        if !session.permission.can_read() {
             return Err(String::from("invalid permission")).into();
         }
 
@@ -222,15 +226,6 @@ impl BasicStore<()> for Store {
         let mut skip = skip;
 
         for (key, value) in items.iter() {
-            if skip > 0 {
-                skip -= 1;
-                continue;
-            }
-
-            if cnt == 0 {
-                break;
-            }
-
             if let Some(ref from) = from {
                 if key < from {
                     continue;
@@ -243,6 +238,15 @@ impl BasicStore<()> for Store {
                 }
             }
 
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+
+            if cnt == 0 {
+                break;
+            }
+
             list.push(value.to_owned());
             cnt -= 1;
         }
@@ -252,12 +256,90 @@ impl BasicStore<()> for Store {
 
     fn list_prefix(&mut self,
                    session: &Session,
-                   _prefix: &[u8],
+                   prefix: &[u8],
                    count: Option<u64>,
                    skip: u64)
         -> Result<Vec<Vec<u8>>>
     {
-        self.list(session, None, None, count, skip)
+        let (from, to) = prefix_range(prefix);
+
+        self.list(session, from, to, count, skip)
+    }
+    
+    fn scan(&mut self,
+            session: &Session,
+            from: Option<Vec<u8>>,
+            to: Option<Vec<u8>>,
+            cursor: Option<Vec<u8>>,
+            direction: Direction,
+            count: u64)
+        -> Result<Vec<(Vec<u8>, Vec<u8>)>>
+    {
+        session.check()?;
+
+        if session.is_expired()? {
+            return Err(String::from("expired session"));
+        }
+
+        if !session.permission.can_read() {
+            return Err(String::from("invalid permission")).into();
+        }
+
+        if count == 0 {
+            return Err(String::from("invalid count"));
+        }
+
+        if let Some(ref from) = from {
+            if let Some(ref to) = to {
+                if from >= to {
+                    return Err(String::from("invalid range"));
+                } 
+            }
+        }
+
+        let sessions = &*self.sessions.lock().unwrap();
+
+        if !sessions.contains_key(&session.id) {
+            return Err(String::from("session not found"));
+        }
+
+        let items = &*self.items.lock().unwrap();
+
+        let mut list: Vec<(Vec<u8>, Vec<u8>)> = items.iter()
+            .filter(|&(key, _)| {
+                if let Some(ref from) = from {
+                    if key < from {
+                        return false;
+                    }
+                }
+
+                if let Some(ref to) = to {
+                    if key >= to {
+                        return false;
+                    }
+                }
+
+                if let Some(ref cursor) = cursor {
+                    match direction {
+                        Direction::Forward => key > cursor,
+                        Direction::Reverse => key < cursor,
+                    }
+                } else {
+                    true
+                }
+            })
+            .map(|(key, value)| (key.to_owned(), value.to_owned()))
+            .collect();
+
+        list.sort();
+
+        if direction == Direction::Reverse {
+            list.reverse();
+        }
+
+        list.truncate(count as usize);
+
+        Ok(list)
     }
     
     fn lookup(&mut self,
@@ -271,7 +353,7 @@ impl BasicStore<()> for Store {
             return Err(String::from("expired session"));
         }
 
-        if session.permission > Permission::Read {
+        if !session.permission.can_read() {
             return Err(String::from("invalid permission")).into();
         }
 
@@ -299,7 +381,7 @@ impl BasicStore<()> for Store {
             return Err(String::from("expired session"));
         }
 
-        if session.permission > Permission::Read {
+        if !session.permission.can_read() {
             return Err(String::from("invalid permission")).into();
         }
 
@@ -330,7 +412,7 @@ impl BasicStore<()> for Store {
             return Err(String::from("expired session"));
         }
 
-        if session.permission < Permission::Write {
+        if !session.permission.can_write() {
             return Err(String::from("invalid permission")).into();
         }
 
@@ -363,7 +445,7 @@ impl BasicStore<()> for Store {
             return Err(String::from("expired session"));
         }
 
-        if session.permission < Permission::Write {
+        if !session.permission.can_write() {
             return Err(String::from("invalid permission")).into();
         }
 
@@ -396,7 +478,7 @@ impl BasicStore<()> for Store {
             return Err(String::from("expired session"));
         }
 
-        if session.permission < Permission::Write {
+        if !session.permission.can_write() {
             return Err(String::from("invalid permission")).into();
         }
 
@@ -424,7 +506,7 @@ impl BasicStore<()> for Store {
             return Err(String::from("expired session"));
         }
 
-        if session.permission < Permission::Write {
+        if !session.permission.can_write() {
             return Err(String::from("invalid permission")).into();
         }
 
@@ -2,13 +2,19 @@ use mitrid_core::base::Result;
 use mitrid_core::base::Checkable;
 use mitrid_core::base::{Eval, EvalMut};
 use mitrid_core::model::BlockGraph as BaseBlockGraph;
+use mitrid_core::model::BlockGraphEngine as BaseBlockGraphEngine;
 
 use fixture::base::Payload;
 use fixture::base::eval::*;
 use fixture::crypto::Digest;
+use fixture::crypto::Proof;
+use fixture::model::Amount;
+use fixture::io::store::Store;
 
 pub type BlockGraph = BaseBlockGraph<Digest, Payload>;
 
+pub type BlockGraphEngine = BaseBlockGraphEngine<Store, (), Digest, Amount, Payload, Payload, Payload, Payload, Proof, Payload>;
+
 #[derive(Clone)]
 pub struct BlockGraphEvaluator {}
 
@@ -56,4 +62,19 @@ impl EvalMut<BlockGraph, PayloadEvalMutParams, PayloadEvalMutResult> for BlockGr
             },
         }
     }
+}
+
+#[derive(Clone)]
+pub struct BlockGraphTipEvaluator {}
+
+impl Eval<BlockGraph, (), Option<u64>> for BlockGraphTipEvaluator {
+    fn eval(&self, blockgraph: &BlockGraph, _params: &()) -> Result<Option<u64>> {
+        blockgraph.check()?;
+
+        if blockgraph.frontier_len == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(blockgraph.frontier_len - 1))
+    }
 }
\ No newline at end of file
@@ -0,0 +1,60 @@
+use mitrid_core::base::Result;
+use mitrid_core::base::Checkable;
+use mitrid_core::base::{Eval, EvalMut};
+use mitrid_core::model::BlockHeader as BaseBlockHeader;
+
+use fixture::base::Payload;
+use fixture::base::eval::*;
+use fixture::crypto::Digest;
+use fixture::crypto::Proof;
+
+pub type BlockHeader = BaseBlockHeader<Digest, Payload, Proof>;
+
+#[derive(Clone)]
+pub struct BlockHeaderEvaluator {}
+
+impl Eval<BlockHeader, PayloadEvalParams, PayloadEvalResult> for BlockHeaderEvaluator {
+    fn eval(&self, header: &BlockHeader, params: &PayloadEvalParams) -> Result<PayloadEvalResult> {
+        header.check()?;
+        params.check()?;
+
+        let s = header.payload.to_string();
+
+        match params {
+            &PayloadEvalParams::Const => {
+                let res = PayloadEvalResult::Const(s);
+                Ok(res)
+            },
+            &PayloadEvalParams::IsEmpty => {
+                let res = PayloadEvalResult::IsEmpty(s.is_empty());
+                Ok(res)
+            },
+        }
+    }
+}
+
+impl EvalMut<BlockHeader, PayloadEvalMutParams, PayloadEvalMutResult> for BlockHeaderEvaluator {
+    fn eval_mut(&mut self, header: &mut BlockHeader, params: &PayloadEvalMutParams) -> Result<PayloadEvalMutResult> {
+        header.check()?;
+        params.check()?;
+
+        let s = header.payload.to_string();
+
+        match params {
+            &PayloadEvalMutParams::ToUppercase => {
+                let new_s = s.to_uppercase();
+                header.payload = Payload::new(&new_s);
+
+                let res = PayloadEvalMutResult::ToUppercase(new_s);
+                Ok(res)
+            },
+            &PayloadEvalMutParams::ToLowercase => {
+                let new_s = s.to_lowercase();
+                header.payload = Payload::new(&new_s);
+
+                let res = PayloadEvalMutResult::ToLowercase(new_s);
+                Ok(res)
+            },
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,8 @@
+use mitrid_core::model::ChainSpec as BaseChainSpec;
+
+use fixture::base::Payload;
+use fixture::crypto::Digest;
+use fixture::crypto::Proof;
+use fixture::model::Amount;
+
+pub type ChainSpec = BaseChainSpec<Digest, Amount, Payload, Payload, Payload, Payload, Proof, Payload>;
\ No newline at end of file
@@ -3,9 +3,13 @@ pub mod coin;
 pub mod input;
 pub mod output;
 pub mod transaction;
+pub mod utxo;
 pub mod blocknode;
 pub mod block;
+pub mod blockheader;
 pub mod blockgraph;
+pub mod reorg;
+pub mod chainspec;
 pub mod wallet;
 
 pub use self::amount::*;
@@ -13,7 +17,11 @@ pub use self::coin::*;
 pub use self::input::*;
 pub use self::output::*;
 pub use self::transaction::*;
+pub use self::utxo::*;
 pub use self::blocknode::*;
 pub use self::block::*;
+pub use self::blockheader::*;
 pub use self::blockgraph::*;
+pub use self::reorg::*;
+pub use self::chainspec::*;
 pub use self::wallet::*;
\ No newline at end of file
@@ -0,0 +1,40 @@
+use mitrid_core::base::Result;
+use mitrid_core::crypto::Hash;
+use mitrid_core::model::Reorg as BaseReorg;
+use mitrid_core::model::StateTransition;
+
+use fixture::base::Payload;
+use fixture::crypto::Digest;
+use fixture::crypto::Proof;
+use fixture::model::Amount;
+use fixture::model::block::Block;
+
+pub type Reorg = BaseReorg<Digest>;
+
+#[derive(Clone, Debug, Default)]
+pub struct BlocksState {
+    pub applied: Vec<Digest>,
+    pub invalid: Vec<Digest>,
+}
+
+impl StateTransition<Digest, Amount, Payload, Payload, Payload, Payload, Proof> for BlocksState {
+    fn apply_block<H: Hash<Digest>>(&mut self, _hasher: &mut H, block: &Block) -> Result<()> {
+        if self.invalid.contains(&block.id) {
+            return Err(String::from("invalid block"));
+        }
+
+        self.applied.push(block.id.clone());
+
+        Ok(())
+    }
+
+    fn revert_block<H: Hash<Digest>>(&mut self, _hasher: &mut H, block: &Block) -> Result<()> {
+        if self.applied.last() != Some(&block.id) {
+            return Err(String::from("invalid block"));
+        }
+
+        self.applied.pop();
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,11 @@
+use mitrid_core::model::Utxo as BaseUtxo;
+use mitrid_core::model::UtxoSet as BaseUtxoSet;
+
+use fixture::base::Payload;
+use fixture::crypto::Digest;
+use fixture::model::Amount;
+use fixture::io::store::Store;
+
+pub type Utxo = BaseUtxo<Digest, Amount, Payload>;
+
+pub type UtxoSet = BaseUtxoSet<Store, (), Digest, Amount, Payload>;
\ No newline at end of file
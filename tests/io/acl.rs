@@ -0,0 +1,146 @@
+use mitrid_core::base::Checkable;
+use mitrid_core::base::Sizable;
+use mitrid_core::base::Serializable;
+use mitrid_core::io::{Capability, Acl};
+use mitrid_core::io::network::{Method, Resource};
+
+#[test]
+fn test_capability_parse() {
+    let valid = vec!["get:block", "eval:*", "*:coin", "*:*", "*", "list"];
+
+    for capability in valid {
+        let res = Capability::parse(capability);
+        assert!(res.is_ok());
+    }
+
+    let invalid = vec!["", "invalid:block", "get:invalid", "get:block:coin"];
+
+    for capability in invalid {
+        let res = Capability::parse(capability);
+        assert!(res.is_err());
+    }
+
+    let capability = Capability::parse("get:block").unwrap();
+    assert_eq!(capability, Capability::new(Some(Method::Get), Some(Resource::Block)));
+
+    let capability = Capability::parse("list").unwrap();
+    assert_eq!(capability, Capability::new(Some(Method::List), None));
+}
+
+#[test]
+fn test_capability_format() {
+    let capabilities = vec!["get:block", "eval:*", "*:coin", "*:*"];
+
+    for capability_str in capabilities {
+        let capability = Capability::parse(capability_str).unwrap();
+        assert_eq!(format!("{}", capability), String::from(capability_str));
+    }
+}
+
+#[test]
+fn test_capability_allows() {
+    let capability = Capability::parse("get:block").unwrap();
+    assert!(capability.allows(&Method::Get, &Resource::Block));
+    assert!(!capability.allows(&Method::Get, &Resource::Coin));
+    assert!(!capability.allows(&Method::Delete, &Resource::Block));
+
+    let capability = Capability::parse("*:block").unwrap();
+    assert!(capability.allows(&Method::Get, &Resource::Block));
+    assert!(capability.allows(&Method::Delete, &Resource::Block));
+    assert!(!capability.allows(&Method::Get, &Resource::Coin));
+
+    let capability = Capability::parse("*:*").unwrap();
+    assert!(capability.allows(&Method::Delete, &Resource::Coin));
+}
+
+#[test]
+fn test_acl_parse() {
+    let acl = Acl::parse("").unwrap();
+    assert!(acl.is_empty());
+
+    let acl = Acl::parse("get:block, list:block,get:block").unwrap();
+    assert_eq!(acl.capabilities.len(), 2);
+    assert!(acl.check().is_ok());
+
+    let res = Acl::parse("get:block,invalid:block");
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_acl_format() {
+    let acl = Acl::parse("list:block,get:block,eval:*").unwrap();
+    let acl_string = format!("{}", acl);
+
+    assert_eq!(Acl::parse(&acl_string).unwrap(), acl);
+    assert_eq!(format!("{}", Acl::new()), String::new());
+}
+
+#[test]
+fn test_acl_grant_revoke() {
+    let capability = Capability::parse("delete:coin").unwrap();
+
+    let acl = Acl::new().grant(&capability).unwrap();
+    assert!(acl.allows(&Method::Delete, &Resource::Coin));
+
+    let acl = acl.grant(&capability).unwrap();
+    assert_eq!(acl.capabilities.len(), 1);
+
+    let acl = acl.revoke(&capability).unwrap();
+    assert!(acl.is_empty());
+    assert!(!acl.allows(&Method::Delete, &Resource::Coin));
+
+    let res = acl.revoke(&capability);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_acl_check_access() {
+    let acl = Acl::parse("get:block,eval:*").unwrap();
+
+    assert!(acl.check_access(&Method::Get, &Resource::Block).is_ok());
+    assert!(acl.check_access(&Method::Get, &Resource::Coin).is_err());
+    assert!(acl.check_access(&Method::Eval, &Resource::EvalParams).is_ok());
+    assert!(acl.check_access(&Method::Delete, &Resource::Block).is_err());
+
+    assert!(acl.check_access(&Method::Ping, &Resource::None).is_ok());
+    assert!(acl.check_access(&Method::Session, &Resource::Session).is_ok());
+
+    assert!(acl.check_access(&Method::Get, &Resource::Error).is_ok());
+    assert!(acl.check_access(&Method::Delete, &Resource::Error).is_err());
+}
+
+#[test]
+fn test_acl_check() {
+    let acl = Acl::parse("get:block,eval:*").unwrap();
+    assert!(acl.check().is_ok());
+
+    let mut invalid_acl = acl.clone();
+    invalid_acl.capabilities.reverse();
+    assert!(invalid_acl.check().is_err());
+
+    let mut invalid_acl = acl.clone();
+    let capability = invalid_acl.capabilities[0].clone();
+    invalid_acl.capabilities.push(capability);
+    assert!(invalid_acl.check().is_err());
+}
+
+#[test]
+fn test_acl_size() {
+    let acl = Acl::parse("get:block,eval:*").unwrap();
+
+    assert_eq!(acl.size(), acl.capabilities.size());
+}
+
+#[test]
+fn test_acl_bytes() {
+    let acl = Acl::parse("get:block,eval:*").unwrap();
+
+    let res = acl.to_bytes();
+    assert!(res.is_ok());
+
+    let acl_bytes = res.unwrap();
+
+    let res = Acl::from_bytes(&acl_bytes);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), acl);
+}
\ No newline at end of file
@@ -1,4 +1,6 @@
 mod permission;
+mod acl;
 mod session;
+mod session_manager;
 mod store;
 mod network;
\ No newline at end of file
@@ -5,12 +5,17 @@ use mitrid_core::util::Version;
 use mitrid_core::util::Timestamp;
 use mitrid_core::base::Meta;
 use mitrid_core::io::Permission;
+use mitrid_core::io::Acl;
 use mitrid_core::io::network::Method;
 use mitrid_core::io::network::Resource;
 
 use fixture::base::eval::*;
 use fixture::base::Payload;
+use mitrid_core::crypto::{Authenticate, Sign};
+
 use fixture::crypto::Hasher;
+use fixture::crypto::hmac_sha512::SHA512HMAC;
+use fixture::crypto::sign_ed25519::Ed25519;
 use fixture::io::Session;
 use fixture::io::message::*;
 
@@ -228,6 +233,40 @@ fn test_message_check() {
     assert!(res.is_err());
 }
 
+#[test]
+fn test_message_check_acl() {
+    let acl = Acl::parse("get:block").unwrap();
+    let session = Session::default().acl(&acl).unwrap();
+
+    let mut message = Message::new()
+                        .session(&session)
+                        .unwrap()
+                        .method(&Method::Get)
+                        .unwrap()
+                        .resource(&Resource::Block)
+                        .unwrap();
+
+    let res = message.check();
+    assert!(res.is_ok());
+
+    message.resource = Resource::Coin;
+
+    let res = message.check();
+    assert!(res.is_err());
+
+    message.method = Method::Delete;
+    message.resource = Resource::Block;
+
+    let res = message.check();
+    assert!(res.is_err());
+
+    message.method = Method::Ping;
+    message.resource = Resource::None;
+
+    let res = message.check();
+    assert!(res.is_ok());
+}
+
 #[test]
 fn test_digest() {
     let message = Message::new();
@@ -295,6 +334,91 @@ fn test_message_finalize() {
     assert!(res.is_err());
 }
 
+#[test]
+fn test_message_session_authentication() {
+    let mut authenticator = SHA512HMAC;
+    let key = authenticator.generate_key().unwrap();
+
+    let mut hasher = Hasher{};
+
+    let expires_at = Timestamp::now().unwrap() * 2;
+    let session = Session::new(1, &Permission::Read, &expires_at, &())
+                    .unwrap()
+                    .authenticate(&mut authenticator, &key)
+                    .unwrap();
+
+    let message = Message::new()
+                    .session(&session)
+                    .unwrap()
+                    .method(&Method::Get)
+                    .unwrap()
+                    .resource(&Resource::Coin)
+                    .unwrap()
+                    .finalize(&mut hasher)
+                    .unwrap();
+
+    let res = message.verify_session_authentication(&mut authenticator, &key);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let res = message.check_session_authentication(&mut authenticator, &key);
+    assert!(res.is_ok());
+
+    let mut forged_session = session.clone();
+    forged_session.permission = Permission::Write;
+
+    let forged_message = message.clone()
+                            .session(&forged_session)
+                            .unwrap()
+                            .method(&Method::Create)
+                            .unwrap()
+                            .finalize(&mut hasher)
+                            .unwrap();
+
+    let res = forged_message.check_session_authentication(&mut authenticator, &key);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_message_session_signature() {
+    let mut signer = Ed25519{};
+    let (pk, sk) = signer.generate_keys(None).unwrap();
+
+    let mut hasher = Hasher{};
+
+    let expires_at = Timestamp::now().unwrap() * 2;
+    let session = Session::new(1, &Permission::Read, &expires_at, &())
+                    .unwrap()
+                    .sign(&mut signer, &sk)
+                    .unwrap();
+
+    let message = Message::new()
+                    .session(&session)
+                    .unwrap()
+                    .method(&Method::Get)
+                    .unwrap()
+                    .resource(&Resource::Coin)
+                    .unwrap()
+                    .finalize(&mut hasher)
+                    .unwrap();
+
+    let res = message.verify_session_signature(&mut signer, &pk);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let res = message.check_session_signature(&mut signer, &pk);
+    assert!(res.is_ok());
+
+    let unsigned_message = message.clone()
+                            .session(&Session::new(1, &Permission::Read, &expires_at, &()).unwrap())
+                            .unwrap()
+                            .finalize(&mut hasher)
+                            .unwrap();
+
+    let res = unsigned_message.check_session_signature(&mut signer, &pk);
+    assert!(res.is_err());
+}
+
 #[test]
 fn test_message_json() {
     let message_a = Message::new();
@@ -124,6 +124,24 @@ fn test_method_check_permission() {
     }
 }
 
+#[test]
+fn test_method_check_combined_permission() {
+    let read_write_permission = Permission::Read | Permission::Write;
+
+    let method_strs = vec!["session", "count", "list", "lookup", "get", "eval",
+                           "create", "update", "upsert", "delete", "evalmut"];
+
+    for method_str in method_strs.iter() {
+        let method = Method::parse(method_str).unwrap();
+
+        let res = method.check_permission(&read_write_permission);
+        assert!(res.is_ok());
+    }
+
+    let res = Method::Ping.check_permission(&read_write_permission);
+    assert!(res.is_err());
+}
+
 #[test]
 fn test_method_default() {
     let method = Method::default();
@@ -19,7 +19,8 @@ fn test_resource_new_parse() {
                                    "evalresult",
                                    "evalmutparams",
                                    "evalmutresult",
-                                   "error"];
+                                   "error",
+                                   "blockheader"];
 
     let invalid_resource_str = "wallet";
 
@@ -48,7 +49,8 @@ fn test_resource_check_method() {
                              "evalresult",
                              "evalmutparams",
                              "evalmutresult",
-                             "error"];
+                             "error",
+                             "blockheader"];
 
     for resource_str in resource_strs.iter() {
         let resource = Resource::parse(resource_str).unwrap();
@@ -91,6 +93,7 @@ fn test_resource_check_method() {
             {
                 if (resource >= Resource::Node &&
                     resource < Resource::EvalParams) ||
+                    resource == Resource::BlockHeader ||
                     resource == Resource::Error
                 {
                     assert!(res.is_ok());
@@ -140,7 +143,8 @@ fn test_resource_display() {
                              "evalresult",
                              "evalmutparams",
                              "evalmutresult",
-                             "error"];
+                             "error",
+                             "blockheader"];
 
     for resource_str in resource_strs.iter() {
         let resource = Resource::parse(resource_str).unwrap();
@@ -154,6 +158,13 @@ fn test_resource_default() {
     assert_eq!(resource, Resource::None);
 }
 
+#[test]
+fn test_resource_discriminant() {
+    assert_eq!(Resource::BlockGraph as u8, 9);
+    assert_eq!(Resource::Error as u8, 14);
+    assert_eq!(Resource::BlockHeader as u8, 15);
+}
+
 #[test]
 fn test_resource_size() {
     let resource = Resource::default();
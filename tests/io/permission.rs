@@ -15,6 +15,15 @@ fn test_permission_parse() {
             assert!(res.is_err());
         }
     }
+
+    let read_write = Permission::Read | Permission::Write;
+
+    assert_eq!(Permission::parse("read,write").unwrap(), read_write);
+    assert_eq!(Permission::parse("write, read").unwrap(), read_write);
+    assert_eq!(Permission::parse("none,read").unwrap(), Permission::Read);
+
+    let res = Permission::parse("read,invalid");
+    assert!(res.is_err());
 }
 
 #[test]
@@ -29,6 +38,31 @@ fn test_permission_format() {
     assert_eq!(none_string, String::from("none"));
     assert_eq!(read_string, String::from("read"));
     assert_eq!(write_string, String::from("write"));
+
+    let read_write = Permission::Read | Permission::Write;
+    let read_write_string = format!("{}", read_write);
+
+    assert_eq!(read_write_string, String::from("read,write"));
+    assert_eq!(Permission::parse(&read_write_string).unwrap(), read_write);
+}
+
+#[test]
+fn test_permission_contains() {
+    let none = Permission::None;
+    assert!(!none.can_read());
+    assert!(!none.can_write());
+
+    let read = Permission::Read;
+    assert!(read.can_read());
+    assert!(!read.can_write());
+
+    let write = Permission::Write;
+    assert!(!write.can_read());
+    assert!(write.can_write());
+
+    let read_write = Permission::Read | Permission::Write;
+    assert!(read_write.can_read());
+    assert!(read_write.can_write());
 }
 
 #[test]
@@ -44,6 +78,11 @@ fn test_permission_check() {
     let res = permission.check();
 
     assert!(res.is_ok());
+
+    let permission = Permission::Read | Permission::Write;
+    let res = permission.check();
+
+    assert!(res.is_ok());
 }
 
 #[test]
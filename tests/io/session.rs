@@ -3,7 +3,13 @@ use mitrid_core::base::Sizable;
 use mitrid_core::base::Serializable;
 use mitrid_core::util::Timestamp;
 use mitrid_core::io::Permission;
+use mitrid_core::io::Acl;
+use mitrid_core::io::network::{Method, Resource};
 
+use mitrid_core::crypto::{Authenticate, Sign};
+
+use fixture::crypto::hmac_sha512::SHA512HMAC;
+use fixture::crypto::sign_ed25519::Ed25519;
 use fixture::io::Session;
 
 #[test]
@@ -46,6 +52,33 @@ fn test_session_check() {
     assert!(res.is_ok());
 }
 
+#[test]
+fn test_session_check_access() {
+    let id = 0;
+    let permission = Permission::Read;
+    let timestamp = Timestamp::now().unwrap();
+
+    let session = Session::new(id, &permission, &timestamp, &()).unwrap();
+
+    let res = session.check_access(&Method::Get, &Resource::Coin);
+    assert!(res.is_ok());
+
+    let res = session.check_access(&Method::Delete, &Resource::Coin);
+    assert!(res.is_err());
+
+    let acl = Acl::parse("get:block,delete:coin").unwrap();
+    let session = session.acl(&acl).unwrap();
+
+    let res = session.check_access(&Method::Get, &Resource::Block);
+    assert!(res.is_ok());
+
+    let res = session.check_access(&Method::Get, &Resource::Coin);
+    assert!(res.is_err());
+
+    let res = session.check_access(&Method::Delete, &Resource::Coin);
+    assert!(res.is_ok());
+}
+
 #[test]
 fn test_session_size() {
     let id = 0;
@@ -111,4 +144,90 @@ fn test_session_hex() {
     let session_b = res.unwrap();
 
     assert_eq!(session_a, session_b);
+}
+
+#[test]
+fn test_session_authenticate() {
+    let mut authenticator = SHA512HMAC;
+
+    let key = authenticator.generate_key().unwrap();
+    let other_key = authenticator.generate_key().unwrap();
+
+    let expires_at = Timestamp::now().unwrap();
+
+    let session = Session::new(1, &Permission::Read, &expires_at, &()).unwrap();
+    assert!(!session.is_authenticated());
+
+    let res = session.verify_authentication(&mut authenticator, &key);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let res = session.clone().authenticate(&mut authenticator, &key);
+    assert!(res.is_ok());
+
+    let session = res.unwrap();
+    assert!(session.is_authenticated());
+
+    let res = session.verify_authentication(&mut authenticator, &key);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let res = session.check_authentication(&mut authenticator, &key);
+    assert!(res.is_ok());
+
+    let res = session.check_authentication(&mut authenticator, &other_key);
+    assert!(res.is_err());
+
+    let mut forged_session = session.clone();
+    forged_session.permission = Permission::Write;
+
+    let res = forged_session.check_authentication(&mut authenticator, &key);
+    assert!(res.is_err());
+
+    let mut forged_session = session.clone();
+    forged_session.expires_at *= 2;
+
+    let res = forged_session.check_authentication(&mut authenticator, &key);
+    assert!(res.is_err());
+
+    let mut forged_session = session.clone();
+    forged_session.tag = vec![1, 2, 3];
+
+    let res = forged_session.verify_authentication(&mut authenticator, &key);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+}
+
+#[test]
+fn test_session_sign() {
+    let mut signer = Ed25519{};
+
+    let (pk, sk) = signer.generate_keys(None).unwrap();
+    let (other_pk, _) = signer.generate_keys(None).unwrap();
+
+    let expires_at = Timestamp::now().unwrap();
+
+    let session = Session::new(1, &Permission::Read, &expires_at, &()).unwrap();
+
+    let res = session.verify_signature(&mut signer, &pk);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let res = session.clone().sign(&mut signer, &sk);
+    assert!(res.is_ok());
+
+    let session = res.unwrap();
+    assert!(session.is_authenticated());
+
+    let res = session.check_signature(&mut signer, &pk);
+    assert!(res.is_ok());
+
+    let res = session.check_signature(&mut signer, &other_pk);
+    assert!(res.is_err());
+
+    let mut forged_session = session.clone();
+    forged_session.permission = Permission::Write;
+
+    let res = forged_session.check_signature(&mut signer, &pk);
+    assert!(res.is_err());
 }
\ No newline at end of file
@@ -0,0 +1,445 @@
+use std::thread::sleep;
+use std::time::Duration;
+
+use mitrid_core::base::Checkable;
+use mitrid_core::base::Meta;
+use mitrid_core::util::TimestampDiff;
+use mitrid_core::io::Permission;
+use mitrid_core::io::Acl;
+use mitrid_core::io::SessionManager;
+use mitrid_core::io::{Store, MemoryStore};
+use mitrid_core::io::network::{Method, Resource};
+
+use fixture::base::Payload;
+use mitrid_core::crypto::{Authenticate, Sign};
+
+use fixture::crypto::Hasher;
+use fixture::crypto::hmac_sha512::SHA512HMAC;
+use fixture::crypto::sign_ed25519::Ed25519;
+use fixture::io::{Session, Message, Request};
+
+#[test]
+fn test_session_manager_duration() {
+    let res = SessionManager::<()>::new().duration(&TimestampDiff::from_secs(0));
+    assert!(res.is_err());
+
+    let res = SessionManager::<()>::new().duration(&TimestampDiff::from_secs(1));
+    assert!(res.is_ok());
+}
+
+#[test]
+fn test_session_manager_issue() {
+    let manager = SessionManager::<()>::new();
+    assert!(manager.is_empty());
+
+    let res = manager.issue(&Permission::Read, &());
+    assert!(res.is_ok());
+
+    let session = res.unwrap();
+    assert_ne!(session.id, 0);
+    assert_eq!(session.permission, Permission::Read);
+    assert!(!session.is_expired().unwrap());
+
+    let other_session = manager.issue(&Permission::Write, &()).unwrap();
+    assert_ne!(session.id, other_session.id);
+    assert_eq!(manager.len(), 2);
+
+    let res = manager.get(session.id);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), session);
+
+    let res = manager.check();
+    assert!(res.is_ok());
+}
+
+#[test]
+fn test_session_manager_issue_with_acl() {
+    let manager = SessionManager::<()>::new();
+
+    let acl = Acl::parse("get:block,delete:coin").unwrap();
+
+    let res = manager.issue_with_acl(&Permission::None, &acl, &());
+    assert!(res.is_ok());
+
+    let session = res.unwrap();
+    assert_eq!(session.acl, acl);
+
+    let res = manager.validate(&session);
+    assert!(res.is_ok());
+
+    let res = session.check_access(&Method::Get, &Resource::Block);
+    assert!(res.is_ok());
+
+    let res = session.check_access(&Method::Get, &Resource::Coin);
+    assert!(res.is_err());
+
+    let mut authenticator = SHA512HMAC;
+    let key = authenticator.generate_key().unwrap();
+
+    let res = manager.authenticate(&session, &mut authenticator, &key);
+    assert!(res.is_ok());
+
+    let authenticated_session = res.unwrap();
+    assert_eq!(authenticated_session.acl, acl);
+
+    let res = manager.validate(&authenticated_session);
+    assert!(res.is_ok());
+
+    let res = authenticated_session.check_authentication(&mut authenticator, &key);
+    assert!(res.is_ok());
+
+    let res = manager.validate(&session);
+    assert!(res.is_err());
+
+    let session = manager.issue(&Permission::None, &()).unwrap();
+
+    let res = manager.validate(&session.acl(&acl).unwrap());
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_session_manager_validate() {
+    let manager = SessionManager::<()>::new();
+
+    let read_session = manager.issue(&Permission::Read, &()).unwrap();
+    let write_session = manager.issue(&Permission::Write, &()).unwrap();
+
+    let res = manager.validate(&read_session);
+    assert!(res.is_ok());
+
+    let res = manager.validate_read(&read_session);
+    assert!(res.is_ok());
+
+    let res = manager.validate_write(&read_session);
+    assert!(res.is_err());
+
+    let res = manager.validate_write(&write_session);
+    assert!(res.is_ok());
+
+    let res = manager.validate_read(&write_session);
+    assert!(res.is_err());
+
+    let mut forged_session = read_session.clone();
+    forged_session.permission = Permission::Write;
+
+    let res = manager.validate(&forged_session);
+    assert!(res.is_err());
+
+    let mut unknown_session = read_session.clone();
+    unknown_session.id = read_session.id.wrapping_add(1);
+
+    let res = manager.validate(&unknown_session);
+    assert!(res.is_err());
+
+    let other_manager = SessionManager::<()>::new();
+
+    let res = other_manager.validate(&read_session);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_session_manager_renew() {
+    let manager = SessionManager::<()>::new()
+                    .duration(&TimestampDiff::from_millis(50))
+                    .unwrap();
+
+    let session = manager.issue(&Permission::Read, &()).unwrap();
+
+    sleep(Duration::from_millis(10));
+
+    let res = manager.renew(&session);
+    assert!(res.is_ok());
+
+    let renewed_session = res.unwrap();
+    assert_eq!(renewed_session.id, session.id);
+    assert!(renewed_session.expires_at > session.expires_at);
+
+    let res = manager.validate(&session);
+    assert!(res.is_err());
+
+    let res = manager.validate(&renewed_session);
+    assert!(res.is_ok());
+
+    sleep(Duration::from_millis(60));
+
+    let res = manager.renew(&renewed_session);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_session_manager_authenticate() {
+    let manager = SessionManager::<()>::new();
+
+    let mut authenticator = SHA512HMAC;
+    let key = authenticator.generate_key().unwrap();
+
+    let session = manager.issue(&Permission::Read, &()).unwrap();
+
+    let res = manager.authenticate(&session, &mut authenticator, &key);
+    assert!(res.is_ok());
+
+    let authenticated_session = res.unwrap();
+    assert!(authenticated_session.is_authenticated());
+
+    let res = manager.validate(&authenticated_session);
+    assert!(res.is_ok());
+
+    let res = manager.validate(&session);
+    assert!(res.is_err());
+
+    let res = authenticated_session.check_authentication(&mut authenticator, &key);
+    assert!(res.is_ok());
+
+    let res = manager.renew(&authenticated_session);
+    assert!(res.is_err());
+
+    let res = manager.validate(&authenticated_session);
+    assert!(res.is_ok());
+
+    let res = manager.renew_authenticated(&authenticated_session, &mut authenticator, &key);
+    assert!(res.is_ok());
+
+    let renewed_session = res.unwrap();
+    assert!(renewed_session.is_authenticated());
+    assert!(renewed_session.expires_at >= authenticated_session.expires_at);
+
+    let res = renewed_session.check_authentication(&mut authenticator, &key);
+    assert!(res.is_ok());
+
+    let res = manager.validate(&renewed_session);
+    assert!(res.is_ok());
+}
+
+#[test]
+fn test_session_manager_sign() {
+    let manager = SessionManager::<()>::new();
+
+    let mut signer = Ed25519{};
+    let (pk, sk) = signer.generate_keys(None).unwrap();
+
+    let session = manager.issue(&Permission::Read, &()).unwrap();
+
+    let res = manager.sign(&session, &mut signer, &sk);
+    assert!(res.is_ok());
+
+    let signed_session = res.unwrap();
+
+    let res = signed_session.check_signature(&mut signer, &pk);
+    assert!(res.is_ok());
+
+    let res = manager.renew(&signed_session);
+    assert!(res.is_err());
+
+    let res = manager.renew_signed(&signed_session, &mut signer, &sk);
+    assert!(res.is_ok());
+
+    let renewed_session = res.unwrap();
+
+    let res = renewed_session.check_signature(&mut signer, &pk);
+    assert!(res.is_ok());
+
+    let res = manager.validate(&renewed_session);
+    assert!(res.is_ok());
+}
+
+#[test]
+fn test_session_manager_revoke() {
+    let manager = SessionManager::<()>::new();
+
+    let session = manager.issue(&Permission::Read, &()).unwrap();
+    manager.issue(&Permission::Write, &()).unwrap();
+
+    let res = manager.revoke(session.id);
+    assert!(res.is_ok());
+    assert_eq!(manager.len(), 1);
+
+    let res = manager.validate(&session);
+    assert!(res.is_err());
+
+    let res = manager.revoke(session.id);
+    assert!(res.is_err());
+
+    manager.revoke_all();
+    assert!(manager.is_empty());
+}
+
+#[test]
+fn test_session_manager_clear_expired() {
+    let manager = SessionManager::<()>::new()
+                    .duration(&TimestampDiff::from_millis(1))
+                    .unwrap();
+
+    manager.issue(&Permission::Read, &()).unwrap();
+
+    sleep(Duration::from_millis(10));
+
+    let res = manager.clear_expired();
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), 1);
+    assert!(manager.is_empty());
+
+    manager.issue(&Permission::Read, &()).unwrap();
+
+    sleep(Duration::from_millis(10));
+
+    manager.issue(&Permission::Read, &()).unwrap();
+    assert_eq!(manager.len(), 1);
+}
+
+#[test]
+fn test_session_manager_store() {
+    let manager = SessionManager::<()>::new();
+
+    let mut store_a = MemoryStore::<()>::new().session_manager(&manager);
+    let mut store_b = MemoryStore::<()>::new().session_manager(&manager);
+
+    let session = store_a.session(&Permission::Write).unwrap();
+    assert_eq!(manager.len(), 1);
+
+    let res = store_b.create(&session, &[1], &[1]);
+    assert!(res.is_ok());
+
+    manager.revoke(session.id).unwrap();
+
+    let res = store_a.create(&session, &[2], &[2]);
+    assert!(res.is_err());
+
+    let session = store_a.session(&Permission::Read).unwrap();
+
+    store_a.sessions().revoke_all();
+
+    let res = store_b.lookup(&session, &[1]);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_session_manager_handle_session() {
+    let manager = SessionManager::<()>::new();
+
+    let mut hasher = Hasher{};
+
+    let requested_session = Session::new(0, &Permission::Read, &Default::default(), &()).unwrap();
+
+    let message = Message::new()
+                    .meta(&Meta::default())
+                    .unwrap()
+                    .session(&requested_session)
+                    .unwrap()
+                    .method(&Method::Session)
+                    .unwrap()
+                    .resource(&Resource::Session)
+                    .unwrap()
+                    .payload(&Payload::default())
+                    .unwrap()
+                    .finalize(&mut hasher)
+                    .unwrap();
+
+    let request = Request::new(&message).unwrap();
+
+    let res = manager.handle_session(&request, &mut hasher);
+    assert!(res.is_ok());
+
+    let response = res.unwrap();
+    assert_eq!(response.message.method, Method::Session);
+    assert_eq!(response.message.resource, Resource::Session);
+    assert_eq!(response.message.session.permission, Permission::Read);
+
+    let session = response.message.session.clone();
+
+    let res = manager.validate(&session);
+    assert!(res.is_ok());
+
+    let message = message.clone()
+                    .session(&session)
+                    .unwrap()
+                    .finalize(&mut hasher)
+                    .unwrap();
+
+    let request = Request::new(&message).unwrap();
+
+    let res = manager.handle_session(&request, &mut hasher);
+    assert!(res.is_ok());
+
+    let renewed_session = res.unwrap().message.session;
+    assert_eq!(renewed_session.id, session.id);
+    assert_eq!(manager.len(), 1);
+
+    let message = Message::new()
+                    .meta(&Meta::default())
+                    .unwrap()
+                    .session(&renewed_session)
+                    .unwrap()
+                    .method(&Method::Get)
+                    .unwrap()
+                    .resource(&Resource::Coin)
+                    .unwrap()
+                    .payload(&Payload::default())
+                    .unwrap()
+                    .finalize(&mut hasher)
+                    .unwrap();
+
+    let request = Request::new(&message).unwrap();
+
+    let res = manager.handle_session(&request, &mut hasher);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_session_manager_handle_session_authenticated() {
+    let manager = SessionManager::<()>::new();
+
+    let mut hasher = Hasher{};
+
+    let mut authenticator = SHA512HMAC;
+    let key = authenticator.generate_key().unwrap();
+
+    let requested_session = Session::new(0, &Permission::Read, &Default::default(), &()).unwrap();
+
+    let message = Message::new()
+                    .meta(&Meta::default())
+                    .unwrap()
+                    .session(&requested_session)
+                    .unwrap()
+                    .method(&Method::Session)
+                    .unwrap()
+                    .resource(&Resource::Session)
+                    .unwrap()
+                    .payload(&Payload::default())
+                    .unwrap()
+                    .finalize(&mut hasher)
+                    .unwrap();
+
+    let request = Request::new(&message).unwrap();
+
+    let res = manager.handle_session_authenticated(&request, &mut hasher, &mut authenticator, &key);
+    assert!(res.is_ok());
+
+    let session = res.unwrap().message.session;
+
+    let res = session.check_authentication(&mut authenticator, &key);
+    assert!(res.is_ok());
+
+    let res = manager.validate(&session);
+    assert!(res.is_ok());
+
+    let message = message.clone()
+                    .session(&session)
+                    .unwrap()
+                    .finalize(&mut hasher)
+                    .unwrap();
+
+    let request = Request::new(&message).unwrap();
+
+    let res = manager.handle_session(&request, &mut hasher);
+    assert!(res.is_err());
+
+    let res = manager.handle_session_authenticated(&request, &mut hasher, &mut authenticator, &key);
+    assert!(res.is_ok());
+
+    let renewed_session = res.unwrap().message.session;
+    assert_eq!(renewed_session.id, session.id);
+    assert_eq!(manager.len(), 1);
+
+    let res = renewed_session.check_authentication(&mut authenticator, &key);
+    assert!(res.is_ok());
+}
\ No newline at end of file
@@ -0,0 +1,135 @@
+use mitrid_core::base::Sizable;
+use mitrid_core::base::Checkable;
+use mitrid_core::base::Serializable;
+use mitrid_core::io::{Batch, BatchOp};
+
+#[test]
+fn test_batch_op_apply() {
+    let key = vec![1];
+    let value = vec![2];
+
+    let op = BatchOp::Create(key.clone(), value.clone());
+    assert_eq!(op.key(), key.as_slice());
+    assert_eq!(op.value(), Some(value.as_slice()));
+
+    let res = op.apply(None);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), Some(value.clone()));
+
+    let res = op.apply(Some(&value));
+    assert!(res.is_err());
+
+    let op = BatchOp::Update(key.clone(), value.clone());
+
+    let res = op.apply(None);
+    assert!(res.is_err());
+
+    let res = op.apply(Some(&[]));
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), Some(value.clone()));
+
+    let op = BatchOp::Upsert(key.clone(), value.clone());
+
+    let res = op.apply(None);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), Some(value.clone()));
+
+    let res = op.apply(Some(&[]));
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), Some(value.clone()));
+
+    let op = BatchOp::Delete(key.clone());
+    assert_eq!(op.value(), None);
+
+    let res = op.apply(None);
+    assert!(res.is_err());
+
+    let res = op.apply(Some(&value));
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), None);
+}
+
+#[test]
+fn test_batch_ops() {
+    let mut batch = Batch::new();
+    assert!(batch.is_empty());
+
+    let key = vec![1];
+    let value = vec![2];
+
+    batch.create(&key, &value).unwrap();
+    batch.update(&key, &value).unwrap();
+    batch.upsert(&key, &value).unwrap();
+    batch.delete(&key).unwrap();
+
+    assert!(!batch.is_empty());
+    assert_eq!(batch.ops_len, 4);
+    assert_eq!(batch.ops, vec![BatchOp::Create(key.clone(), value.clone()),
+                               BatchOp::Update(key.clone(), value.clone()),
+                               BatchOp::Upsert(key.clone(), value.clone()),
+                               BatchOp::Delete(key.clone())]);
+
+    batch.clear();
+    assert!(batch.is_empty());
+    assert_eq!(batch.ops_len, 0);
+}
+
+#[test]
+fn test_batch_size() {
+    let mut batch = Batch::new();
+    assert_eq!(batch.size(), batch.ops_len.size());
+
+    let key = vec![1, 2];
+    let value = vec![3];
+
+    batch.create(&key, &value).unwrap();
+
+    let op_size = 0u8.size() + key.size() + value.size();
+    assert_eq!(batch.size(), batch.ops_len.size() + op_size);
+}
+
+#[test]
+fn test_batch_check() {
+    let mut batch = Batch::new();
+    batch.upsert(&[1], &[2]).unwrap();
+
+    let res = batch.check();
+    assert!(res.is_ok());
+
+    batch.ops_len += 1;
+
+    let res = batch.check();
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_batch_bytes() {
+    let mut batch = Batch::new();
+    batch.create(&[1], &[2]).unwrap();
+    batch.delete(&[3]).unwrap();
+
+    let res = batch.to_bytes();
+    assert!(res.is_ok());
+
+    let batch_bytes = res.unwrap();
+
+    let res = Batch::from_bytes(&batch_bytes);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), batch);
+}
+
+#[test]
+fn test_batch_get() {
+    let mut batch = Batch::new();
+    assert_eq!(batch.get(&[1]), None);
+
+    batch.create(&[1], &[2]).unwrap();
+    assert_eq!(batch.get(&[1]), Some(Some([2].as_ref())));
+
+    batch.update(&[1], &[3]).unwrap();
+    assert_eq!(batch.get(&[1]), Some(Some([3].as_ref())));
+
+    batch.delete(&[1]).unwrap();
+    assert_eq!(batch.get(&[1]), Some(None));
+    assert_eq!(batch.get(&[2]), None);
+}
\ No newline at end of file
@@ -0,0 +1,97 @@
+use mitrid_core::base::Checkable;
+use mitrid_core::base::Serializable;
+use mitrid_core::io::IndexKey;
+use mitrid_core::io::store::index::{index_store_prefix, index_prefix, index_key_prefix};
+
+#[test]
+fn test_index_key_new() {
+    let res = IndexKey::new("height", &[1, 2]);
+    assert!(res.is_ok());
+
+    let res = IndexKey::new("", &[1, 2]);
+    assert!(res.is_err());
+
+    let res = IndexKey::from_u64("height", 258);
+    assert!(res.is_ok());
+
+    let index_key = res.unwrap();
+    assert_eq!(index_key.key, vec![0, 0, 0, 0, 0, 0, 1, 2]);
+}
+
+#[test]
+fn test_index_key_check() {
+    let mut index_key = IndexKey::new("height", &[1, 2]).unwrap();
+
+    let res = index_key.check();
+    assert!(res.is_ok());
+
+    index_key.index = String::new();
+
+    let res = index_key.check();
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_index_key_bytes() {
+    let index_key = IndexKey::new("height", &[1, 2]).unwrap();
+
+    let res = index_key.to_bytes();
+    assert!(res.is_ok());
+
+    let index_key_bytes = res.unwrap();
+
+    let res = IndexKey::from_bytes(&index_key_bytes);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), index_key);
+}
+
+#[test]
+fn test_index_prefix() {
+    let prefix = vec![5];
+
+    let res = index_prefix(&prefix, "");
+    assert!(res.is_err());
+
+    let res = index_prefix(&prefix, "height");
+    assert!(res.is_ok());
+
+    let height_prefix = res.unwrap();
+    assert!(height_prefix.starts_with(&index_store_prefix()));
+
+    let res = index_prefix(&prefix, "height_");
+    assert!(res.is_ok());
+    assert!(!res.unwrap().starts_with(&height_prefix));
+
+    let key_prefix = index_key_prefix(&prefix, "height", &[1]).unwrap();
+    assert!(key_prefix.starts_with(&height_prefix));
+
+    let long_key_prefix = index_key_prefix(&prefix, "height", &[1, 0]).unwrap();
+    assert!(!long_key_prefix.starts_with(&key_prefix));
+}
+
+#[test]
+fn test_index_key_entry_key() {
+    let prefix = vec![5];
+    let store_key = vec![9, 9];
+
+    let keys: Vec<Vec<u8>> = vec![vec![], vec![0], vec![0, 0], vec![0, 1], vec![1], vec![1, 0], vec![2]];
+
+    let mut entry_keys = Vec::new();
+
+    for key in keys.iter() {
+        let index_key = IndexKey::new("height", key).unwrap();
+
+        let res = index_key.entry_key(&prefix, &store_key);
+        assert!(res.is_ok());
+
+        let entry_key = res.unwrap();
+        assert!(entry_key.starts_with(&index_key_prefix(&prefix, "height", key).unwrap()));
+        assert!(entry_key.ends_with(&store_key));
+
+        entry_keys.push(entry_key);
+    }
+
+    let mut sorted_entry_keys = entry_keys.clone();
+    sorted_entry_keys.sort();
+    assert_eq!(sorted_entry_keys, entry_keys);
+}
\ No newline at end of file
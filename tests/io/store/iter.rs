@@ -0,0 +1,136 @@
+use mitrid_core::base::Serializable;
+use mitrid_core::io::Permission;
+use mitrid_core::io::Store;
+use mitrid_core::io::MemoryStore;
+use mitrid_core::io::{Direction, StoreIter};
+use mitrid_core::io::store::iter::prefix_range;
+
+#[test]
+fn test_direction_bytes() {
+    let direction = Direction::Reverse;
+
+    let res = direction.to_bytes();
+    assert!(res.is_ok());
+
+    let direction_bytes = res.unwrap();
+
+    let res = Direction::from_bytes(&direction_bytes);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), direction);
+}
+
+#[test]
+fn test_prefix_range() {
+    assert_eq!(prefix_range(&[]), (None, None));
+    assert_eq!(prefix_range(&[1, 2]), (Some(vec![1, 2]), Some(vec![1, 3])));
+    assert_eq!(prefix_range(&[1, 255]), (Some(vec![1, 255]), Some(vec![2])));
+    assert_eq!(prefix_range(&[255, 255]), (Some(vec![255, 255]), None));
+}
+
+#[test]
+fn test_store_iter() {
+    let mut store = MemoryStore::<()>::new();
+
+    let write_session = store.session(&Permission::Write).unwrap();
+
+    for i in 0..10u8 {
+        store.create(&write_session, &[i], &[i]).unwrap();
+    }
+
+    let read_session = store.session(&Permission::Read).unwrap();
+
+    let res = StoreIter::new(&mut store, &read_session, Some(vec![5]), Some(vec![5]), Direction::Forward);
+    assert!(res.is_err());
+
+    let res = StoreIter::new(&mut store, &read_session, None, None, Direction::Forward)
+                .unwrap()
+                .page_size(0);
+    assert!(res.is_err());
+
+    let iter = StoreIter::new(&mut store, &read_session, Some(vec![2]), Some(vec![8]), Direction::Forward)
+                .unwrap()
+                .page_size(4)
+                .unwrap();
+
+    let res: Result<Vec<(Vec<u8>, Vec<u8>)>, String> = iter.collect();
+    assert!(res.is_ok());
+
+    let keys: Vec<Vec<u8>> = res.unwrap().into_iter().map(|(key, _)| key).collect();
+    assert_eq!(keys, vec![vec![2], vec![3], vec![4], vec![5], vec![6], vec![7]]);
+
+    let iter = StoreIter::new(&mut store, &read_session, Some(vec![2]), Some(vec![8]), Direction::Reverse)
+                .unwrap()
+                .page_size(4)
+                .unwrap();
+
+    let res: Result<Vec<(Vec<u8>, Vec<u8>)>, String> = iter.collect();
+    assert!(res.is_ok());
+
+    let values: Vec<Vec<u8>> = res.unwrap().into_iter().map(|(_, value)| value).collect();
+    assert_eq!(values, vec![vec![7], vec![6], vec![5], vec![4], vec![3], vec![2]]);
+}
+
+#[test]
+fn test_store_iter_resume() {
+    let mut store = MemoryStore::<()>::new();
+
+    let write_session = store.session(&Permission::Write).unwrap();
+
+    for i in 0..10u8 {
+        store.create(&write_session, &[i], &[i]).unwrap();
+    }
+
+    let read_session = store.session(&Permission::Read).unwrap();
+
+    let position = {
+        let mut iter = StoreIter::new(&mut store, &read_session, None, None, Direction::Forward)
+                        .unwrap()
+                        .page_size(3)
+                        .unwrap();
+
+        assert_eq!(iter.position(), None);
+
+        for _ in 0..4 {
+            let res = iter.next().unwrap();
+            assert!(res.is_ok());
+        }
+
+        iter.position()
+    };
+
+    assert_eq!(position, Some(vec![3]));
+
+    let iter = StoreIter::new(&mut store, &read_session, None, None, Direction::Forward)
+                .unwrap()
+                .resume(&position.unwrap())
+                .unwrap();
+
+    let res: Result<Vec<(Vec<u8>, Vec<u8>)>, String> = iter.collect();
+    assert!(res.is_ok());
+
+    let keys: Vec<Vec<u8>> = res.unwrap().into_iter().map(|(key, _)| key).collect();
+    assert_eq!(keys, (4..10u8).map(|i| vec![i]).collect::<Vec<Vec<u8>>>());
+}
+
+#[test]
+fn test_store_iter_prefix() {
+    let mut store = MemoryStore::<()>::new();
+
+    let write_session = store.session(&Permission::Write).unwrap();
+
+    store.create(&write_session, &[0, 255], &[0]).unwrap();
+    store.create(&write_session, &[1, 0], &[1]).unwrap();
+    store.create(&write_session, &[1, 255], &[2]).unwrap();
+    store.create(&write_session, &[2], &[3]).unwrap();
+
+    let read_session = store.session(&Permission::Read).unwrap();
+
+    let iter = StoreIter::new_prefix(&mut store, &read_session, &[1], Direction::Reverse)
+                .unwrap()
+                .page_size(1)
+                .unwrap();
+
+    let res: Result<Vec<(Vec<u8>, Vec<u8>)>, String> = iter.collect();
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![(vec![1, 255], vec![2]), (vec![1, 0], vec![1])]);
+}
\ No newline at end of file
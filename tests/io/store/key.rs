@@ -0,0 +1,118 @@
+use mitrid_core::util::Timestamp;
+use mitrid_core::io::StoreKey;
+use mitrid_core::io::store::key::{encode_bytes, decode_bytes};
+
+fn check_order<K: StoreKey + Ord + Clone + ::std::fmt::Debug>(keys: &[K]) {
+    let mut sorted_keys = keys.to_vec();
+    sorted_keys.sort();
+
+    let mut encoded_keys: Vec<Vec<u8>> = keys.iter()
+                                             .map(|key| key.to_store_key().unwrap())
+                                             .collect();
+    encoded_keys.sort();
+
+    let decoded_keys: Vec<K> = encoded_keys.iter()
+                                           .map(|key| K::from_store_key(key).unwrap())
+                                           .collect();
+
+    assert_eq!(decoded_keys, sorted_keys);
+}
+
+#[test]
+fn test_store_key_unsigned() {
+    check_order(&[255u8, 0, 1, 128]);
+    check_order(&[65535u16, 0, 256, 255]);
+    check_order(&[u32::max_value(), 0, 65536, 255]);
+    check_order(&[u64::max_value(), 0, 258, 256, 1]);
+    check_order(&[u128::max_value(), 0, u128::from(u64::max_value()) + 1, u128::from(u64::max_value()), 1]);
+
+    let res = 258u64.to_store_key();
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![0, 0, 0, 0, 0, 0, 1, 2]);
+
+    let res = u64::from_store_key(&[0, 1]);
+    assert!(res.is_err());
+
+    let res = u128::max_value().to_store_key();
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![255; 16]);
+
+    let res = u128::from_store_key(&[0; 8]);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_store_key_signed() {
+    check_order(&[127i8, -128, -1, 0, 1]);
+    check_order(&[i16::max_value(), i16::min_value(), -256, 0, 255]);
+    check_order(&[i32::max_value(), i32::min_value(), -1, 0, 1]);
+    check_order(&[i64::max_value(), i64::min_value(), -258, -1, 0, 258]);
+    check_order(&[i128::max_value(), i128::min_value(), i128::from(i64::min_value()) - 1, -1, 0, 1]);
+
+    let res = i128::min_value().to_store_key();
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![0; 16]);
+}
+
+#[test]
+fn test_store_key_bool() {
+    check_order(&[true, false]);
+
+    let res = bool::from_store_key(&[2]);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_store_key_bytes() {
+    check_order(&[vec![1u8], vec![], vec![0], vec![0, 0], vec![0, 1], vec![1, 0], vec![255]]);
+
+    let mut buf = Vec::new();
+    encode_bytes(&[0, 1, 0], &mut buf);
+    assert_eq!(buf, vec![0, 255, 1, 0, 255, 0, 1]);
+
+    buf.push(9);
+
+    let res = decode_bytes(&buf);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), (vec![0, 1, 0], 7));
+
+    let res = Vec::<u8>::from_store_key(&buf);
+    assert!(res.is_err());
+
+    let res = decode_bytes(&[1, 2]);
+    assert!(res.is_err());
+
+    let res = decode_bytes(&[1, 0, 2]);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_store_key_string() {
+    let keys: Vec<String> = vec!["b", "", "a", "ab", "a\u{0}", "é"].iter()
+                                                                 .map(|s| s.to_string())
+                                                                 .collect();
+    check_order(&keys);
+}
+
+#[test]
+fn test_store_key_timestamp() {
+    check_order(&[Timestamp::from_u64(1000), Timestamp::from_u64(0), Timestamp::from_u64(256)]);
+}
+
+#[test]
+fn test_store_key_tuple() {
+    check_order(&[(1u64, String::from("b")),
+                  (1u64, String::from("a")),
+                  (0u64, String::from("c")),
+                  (1u64, String::from("ab"))]);
+
+    check_order(&[(vec![1u8], -1i32, true),
+                  (vec![1u8], -1i32, false),
+                  (vec![], 5i32, false),
+                  (vec![1u8, 0], -5i32, true)]);
+
+    check_order(&[(1u8, 2u16, 3u32, 4u64), (1u8, 2u16, 3u32, 3u64), (0u8, 9u16, 9u32, 9u64)]);
+
+    let res = <(u64, u64)>::from_store_key(&[0, 0, 0, 0, 0, 0, 0, 1]);
+    assert!(res.is_err());
+}
\ No newline at end of file
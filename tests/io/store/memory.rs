@@ -0,0 +1,540 @@
+use mitrid_core::base::Sizable;
+use mitrid_core::base::Checkable;
+use mitrid_core::util::TimestampDiff;
+use mitrid_core::io::Permission;
+use mitrid_core::io::Acl;
+use mitrid_core::io::Store;
+use mitrid_core::io::MemoryStore;
+use mitrid_core::io::Direction;
+
+#[test]
+fn test_memory_store_size() {
+    let mut store = MemoryStore::<()>::new();
+
+    let size_a = store.size();
+    assert_eq!(size_a, 0);
+
+    let permission = Permission::Write;
+
+    let session = store.session(&permission).unwrap();
+
+    let session_size = session.id.size() + session.size();
+    let size_b = store.size();
+    assert_eq!(size_b, size_a + session_size);
+
+    let key = vec![1];
+    let value = vec![2, 3];
+
+    store.create(&session, &key, &value).unwrap();
+
+    let item_size = key.size() + value.size();
+    let size_c = store.size();
+    assert_eq!(size_c, size_b + item_size);
+
+    store.delete(&session, &key).unwrap();
+    let size_d = store.size();
+    assert_eq!(size_d, size_c - item_size);
+}
+
+#[test]
+fn test_memory_store_check() {
+    let mut store = MemoryStore::<()>::new();
+    let res = store.check();
+    assert!(res.is_ok());
+
+    let permission = Permission::Write;
+
+    let session = store.session(&permission).unwrap();
+    let res = store.check();
+    assert!(res.is_ok());
+
+    let key = vec![1];
+    let value = vec![2];
+
+    store.create(&session, &key, &value).unwrap();
+    let res = store.check();
+    assert!(res.is_ok());
+}
+
+#[test]
+fn test_memory_store_session() {
+    let mut store = MemoryStore::<()>::new();
+
+    let read_permission = Permission::Read;
+    let res = store.session(&read_permission);
+    assert!(res.is_ok());
+
+    let read_session = res.unwrap();
+    assert_eq!(read_session.permission, read_permission);
+    assert!(!read_session.is_expired().unwrap());
+
+    let write_permission = Permission::Write;
+    let res = store.session(&write_permission);
+    assert!(res.is_ok());
+
+    let write_session = res.unwrap();
+    assert_eq!(write_session.permission, write_permission);
+    assert_ne!(write_session.id, read_session.id);
+
+    assert_eq!(store.sessions_len(), 2);
+
+    let mut forged_session = read_session.clone();
+    forged_session.permission = Permission::Write;
+
+    let res = store.create(&forged_session, &[1], &[2]);
+    assert!(res.is_err());
+
+    let mut unknown_session = read_session.clone();
+    unknown_session.id = read_session.id.wrapping_add(1);
+
+    if unknown_session.id != write_session.id {
+        let res = store.lookup(&unknown_session, &[1]);
+        assert!(res.is_err());
+    }
+}
+
+#[test]
+fn test_memory_store_session_duration() {
+    let res = MemoryStore::<()>::new().session_duration(&TimestampDiff::from_secs(0));
+    assert!(res.is_err());
+
+    let duration = TimestampDiff::from_millis(1);
+
+    let mut store = MemoryStore::<()>::new()
+                        .session_duration(&duration)
+                        .unwrap();
+
+    let permission = Permission::Read;
+    let session = store.session(&permission).unwrap();
+
+    ::std::thread::sleep(::std::time::Duration::from_millis(10));
+
+    assert!(session.is_expired().unwrap());
+
+    let res = store.lookup(&session, &[]);
+    assert!(res.is_err());
+
+    store.clear_expired_sessions().unwrap();
+    assert_eq!(store.sessions_len(), 0);
+}
+
+#[test]
+fn test_memory_store_permissions() {
+    let mut store = MemoryStore::<()>::new();
+
+    let none_session = store.session(&Permission::None).unwrap();
+    let read_session = store.session(&Permission::Read).unwrap();
+    let write_session = store.session(&Permission::Write).unwrap();
+
+    let key = vec![1];
+    let value = vec![2];
+
+    let res = store.create(&none_session, &key, &value);
+    assert!(res.is_err());
+
+    let res = store.create(&read_session, &key, &value);
+    assert!(res.is_err());
+
+    let res = store.create(&write_session, &key, &value);
+    assert!(res.is_ok());
+
+    let res = store.get(&write_session, &key);
+    assert!(res.is_err());
+
+    let res = store.get(&read_session, &key);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), value);
+
+    let read_write_session = store.session(&(Permission::Read | Permission::Write)).unwrap();
+
+    let res = store.update(&read_write_session, &key, &key);
+    assert!(res.is_ok());
+
+    let res = store.get(&read_write_session, &key);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), key);
+}
+
+#[test]
+fn test_memory_store_acl() {
+    let mut store = MemoryStore::<()>::new();
+
+    let write_session = store.session(&Permission::Write).unwrap();
+
+    store.create(&write_session, &[1], &[1]).unwrap();
+
+    let acl = Acl::parse("get:*,upsert:*").unwrap();
+    let acl_session = store.sessions().issue_with_acl(&Permission::None, &acl, &()).unwrap();
+
+    let res = store.get(&acl_session, &[1]);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![1]);
+
+    let res = store.lookup(&acl_session, &[1]);
+    assert!(res.is_err());
+
+    let res = store.count(&acl_session, None, None);
+    assert!(res.is_err());
+
+    let res = store.upsert(&acl_session, &[1], &[2]);
+    assert!(res.is_ok());
+
+    let res = store.create(&acl_session, &[2], &[2]);
+    assert!(res.is_err());
+
+    let res = store.delete(&acl_session, &[1]);
+    assert!(res.is_err());
+
+    let mut batch = store.begin(&acl_session).unwrap();
+    batch.upsert(&[3], &[3]).unwrap();
+    batch.delete(&[1]).unwrap();
+
+    let res = store.commit(&acl_session, &batch);
+    assert!(res.is_err());
+    assert_eq!(store.items_len(), 1);
+
+    let acl = Acl::parse("get:block").unwrap();
+    let block_session = store.sessions().issue_with_acl(&Permission::Read, &acl, &()).unwrap();
+
+    let res = store.get(&block_session, &[1]);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_memory_store_count() {
+    let mut store = MemoryStore::<()>::new();
+
+    let write_session = store.session(&Permission::Write).unwrap();
+
+    for i in 0..10u8 {
+        store.create(&write_session, &[i], &[i]).unwrap();
+    }
+
+    let read_session = store.session(&Permission::Read).unwrap();
+
+    let res = store.count(&read_session, None, None);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), 10);
+
+    let res = store.count(&read_session, Some(vec![3]), None);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), 7);
+
+    let res = store.count(&read_session, None, Some(vec![3]));
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), 3);
+
+    let res = store.count(&read_session, Some(vec![3]), Some(vec![5]));
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), 2);
+
+    let res = store.count(&read_session, Some(vec![5]), Some(vec![5]));
+    assert!(res.is_err());
+
+    let res = store.count(&read_session, Some(vec![5]), Some(vec![3]));
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_memory_store_count_prefix() {
+    let mut store = MemoryStore::<()>::new();
+
+    let write_session = store.session(&Permission::Write).unwrap();
+
+    store.create(&write_session, &[0, 1], &[]).unwrap();
+    store.create(&write_session, &[1, 0], &[]).unwrap();
+    store.create(&write_session, &[1, 1], &[]).unwrap();
+    store.create(&write_session, &[2, 0], &[]).unwrap();
+
+    let read_session = store.session(&Permission::Read).unwrap();
+
+    let res = store.count_prefix(&read_session, &[1]);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), 2);
+
+    let res = store.count_prefix(&read_session, &[3]);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), 0);
+
+    let res = store.count_prefix(&read_session, &[]);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), 4);
+}
+
+#[test]
+fn test_memory_store_list() {
+    let mut store = MemoryStore::<()>::new();
+
+    let write_session = store.session(&Permission::Write).unwrap();
+
+    for i in (0..10u8).rev() {
+        store.create(&write_session, &[i], &[i]).unwrap();
+    }
+
+    let read_session = store.session(&Permission::Read).unwrap();
+
+    let res = store.list(&read_session, None, None, None, 0);
+    assert!(res.is_ok());
+
+    let expected: Vec<Vec<u8>> = (0..10u8).map(|i| vec![i]).collect();
+    assert_eq!(res.unwrap(), expected);
+
+    let res = store.list(&read_session, Some(vec![2]), Some(vec![6]), None, 0);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![vec![2], vec![3], vec![4], vec![5]]);
+
+    let res = store.list(&read_session, Some(vec![2]), None, Some(3), 1);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![vec![3], vec![4], vec![5]]);
+
+    let res = store.list(&read_session, None, None, Some(0), 0);
+    assert!(res.is_err());
+
+    let res = store.list(&read_session, None, None, Some(1), 2);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![vec![2]]);
+
+    let res = store.list(&read_session, None, None, Some(1), 20);
+    assert!(res.is_ok());
+    assert!(res.unwrap().is_empty());
+
+    let res = store.list(&read_session, Some(vec![6]), Some(vec![2]), None, 0);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_memory_store_list_prefix() {
+    let mut store = MemoryStore::<()>::new();
+
+    let write_session = store.session(&Permission::Write).unwrap();
+
+    store.create(&write_session, &[0, 1], &[0]).unwrap();
+    store.create(&write_session, &[1, 2], &[2]).unwrap();
+    store.create(&write_session, &[1, 1], &[1]).unwrap();
+    store.create(&write_session, &[1, 3], &[3]).unwrap();
+    store.create(&write_session, &[2, 0], &[4]).unwrap();
+
+    let read_session = store.session(&Permission::Read).unwrap();
+
+    let res = store.list_prefix(&read_session, &[1], None, 0);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![vec![1], vec![2], vec![3]]);
+
+    let res = store.list_prefix(&read_session, &[1], Some(2), 1);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![vec![2], vec![3]]);
+
+    let res = store.list_prefix(&read_session, &[3], None, 0);
+    assert!(res.is_ok());
+    assert!(res.unwrap().is_empty());
+}
+
+#[test]
+fn test_memory_store_crud() {
+    let mut store = MemoryStore::<()>::new();
+
+    let write_session = store.session(&Permission::Write).unwrap();
+    let read_session = store.session(&Permission::Read).unwrap();
+
+    let key = vec![1];
+    let value_a = vec![2];
+    let value_b = vec![3];
+
+    let res = store.lookup(&read_session, &key);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let res = store.update(&write_session, &key, &value_a);
+    assert!(res.is_err());
+
+    let res = store.create(&write_session, &key, &value_a);
+    assert!(res.is_ok());
+
+    let res = store.create(&write_session, &key, &value_a);
+    assert!(res.is_err());
+
+    let res = store.lookup(&read_session, &key);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let res = store.update(&write_session, &key, &value_b);
+    assert!(res.is_ok());
+
+    let res = store.get(&read_session, &key);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), value_b);
+
+    let res = store.upsert(&write_session, &key, &value_a);
+    assert!(res.is_ok());
+
+    let res = store.get(&read_session, &key);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), value_a);
+
+    let res = store.delete(&write_session, &key);
+    assert!(res.is_ok());
+
+    let res = store.delete(&write_session, &key);
+    assert!(res.is_err());
+
+    let res = store.get(&read_session, &key);
+    assert!(res.is_err());
+
+    let res = store.upsert(&write_session, &key, &value_b);
+    assert!(res.is_ok());
+    assert_eq!(store.items_len(), 1);
+
+    store.clear();
+    assert_eq!(store.items_len(), 0);
+    assert_eq!(store.sessions_len(), 0);
+}
+
+#[test]
+fn test_memory_store_commit() {
+    let mut store = MemoryStore::<()>::new();
+
+    let read_session = store.session(&Permission::Read).unwrap();
+    let write_session = store.session(&Permission::Write).unwrap();
+
+    let res = store.begin(&read_session);
+    assert!(res.is_err());
+
+    store.create(&write_session, &[0], &[0]).unwrap();
+
+    let mut batch = store.begin(&write_session).unwrap();
+    batch.create(&[1], &[1]).unwrap();
+    batch.create(&[2], &[2]).unwrap();
+    batch.update(&[1], &[3]).unwrap();
+    batch.delete(&[0]).unwrap();
+
+    let res = store.commit(&read_session, &batch);
+    assert!(res.is_err());
+
+    let res = store.commit(&write_session, &batch);
+    assert!(res.is_ok());
+
+    let res = store.list(&read_session, None, None, None, 0);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![vec![3], vec![2]]);
+
+    let mut batch = store.begin(&write_session).unwrap();
+    batch.create(&[4], &[4]).unwrap();
+    batch.delete(&[4]).unwrap();
+    batch.upsert(&[5], &[5]).unwrap();
+    batch.create(&[1], &[1]).unwrap();
+
+    let res = store.commit(&write_session, &batch);
+    assert!(res.is_err());
+
+    let res = store.list(&read_session, None, None, None, 0);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![vec![3], vec![2]]);
+
+    let res = store.rollback(&write_session, &mut batch);
+    assert!(res.is_ok());
+    assert!(batch.is_empty());
+}
+
+#[test]
+fn test_memory_store_commit_concurrent() {
+    let store = MemoryStore::<()>::new();
+
+    let mut handles = Vec::new();
+
+    for i in 0..8u8 {
+        let mut store = store.clone();
+
+        let handle = ::std::thread::spawn(move || {
+            let write_session = store.session(&Permission::Write).unwrap();
+
+            let mut results = Vec::new();
+
+            for key in 0..64u8 {
+                let mut batch = store.begin(&write_session).unwrap();
+                batch.create(&[key], &[i]).unwrap();
+                batch.create(&[key, 0], &[i]).unwrap();
+
+                results.push(store.commit(&write_session, &batch).is_ok());
+            }
+
+            results
+        });
+
+        handles.push(handle);
+    }
+
+    let mut committed = vec![0; 64];
+
+    for handle in handles.into_iter() {
+        for (key, ok) in handle.join().unwrap().into_iter().enumerate() {
+            if ok {
+                committed[key] += 1;
+            }
+        }
+    }
+
+    assert_eq!(committed, vec![1; 64]);
+
+    let mut store = store;
+
+    let read_session = store.session(&Permission::Read).unwrap();
+
+    for key in 0..64u8 {
+        let value = store.get(&read_session, &[key]).unwrap();
+        assert_eq!(store.get(&read_session, &[key, 0]).unwrap(), value);
+    }
+}
+
+#[test]
+fn test_memory_store_scan() {
+    let mut store = MemoryStore::<()>::new();
+
+    let write_session = store.session(&Permission::Write).unwrap();
+
+    for i in 0..10u8 {
+        store.create(&write_session, &[i], &[i]).unwrap();
+    }
+
+    let res = store.scan(&write_session, None, None, None, Direction::Forward, 1);
+    assert!(res.is_err());
+
+    let read_session = store.session(&Permission::Read).unwrap();
+
+    let res = store.scan(&read_session, None, None, None, Direction::Forward, 0);
+    assert!(res.is_err());
+
+    let res = store.scan(&read_session, Some(vec![3]), Some(vec![3]), None, Direction::Forward, 1);
+    assert!(res.is_err());
+
+    let res = store.scan(&read_session, Some(vec![3]), Some(vec![7]), None, Direction::Forward, 2);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![(vec![3], vec![3]), (vec![4], vec![4])]);
+
+    let res = store.scan(&read_session, Some(vec![3]), Some(vec![7]), Some(vec![4]), Direction::Forward, 5);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![(vec![5], vec![5]), (vec![6], vec![6])]);
+
+    let res = store.scan(&read_session, Some(vec![3]), Some(vec![7]), Some(vec![0]), Direction::Forward, 1);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![(vec![3], vec![3])]);
+
+    let res = store.scan(&read_session, Some(vec![3]), Some(vec![7]), Some(vec![6]), Direction::Forward, 5);
+    assert!(res.is_ok());
+    assert!(res.unwrap().is_empty());
+
+    let res = store.scan(&read_session, Some(vec![3]), Some(vec![7]), None, Direction::Reverse, 2);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![(vec![6], vec![6]), (vec![5], vec![5])]);
+
+    let res = store.scan(&read_session, Some(vec![3]), Some(vec![7]), Some(vec![5]), Direction::Reverse, 5);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![(vec![4], vec![4]), (vec![3], vec![3])]);
+
+    let res = store.scan(&read_session, Some(vec![3]), Some(vec![7]), Some(vec![9]), Direction::Reverse, 1);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![(vec![6], vec![6])]);
+
+    let res = store.scan(&read_session, Some(vec![3]), Some(vec![7]), Some(vec![3]), Direction::Reverse, 5);
+    assert!(res.is_ok());
+    assert!(res.unwrap().is_empty());
+}
\ No newline at end of file
@@ -1,2 +1,12 @@
 mod eval;
-mod store;
\ No newline at end of file
+mod store;
+mod memory;
+mod batch;
+mod iter;
+mod index;
+mod prefix;
+mod key;
+mod wal;
+mod snapshot;
+mod query;
+mod watch;
\ No newline at end of file
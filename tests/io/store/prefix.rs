@@ -0,0 +1,134 @@
+use mitrid_core::base::Serializable;
+use mitrid_core::base::Meta;
+use mitrid_core::io::Permission;
+use mitrid_core::io::{Store, Storable};
+use mitrid_core::io::MemoryStore;
+use mitrid_core::io::IndexKey;
+use mitrid_core::io::store::prefix::*;
+use mitrid_core::model::coin::{COIN_CODE, COIN_TX_ID_INDEX};
+use mitrid_core::model::block::BLOCK_CODE;
+
+use fixture::crypto::{Digest, Hasher};
+use fixture::model::Amount;
+use fixture::model::coin::*;
+
+#[test]
+fn test_encode_prefix() {
+    let prefix = encode_prefix(COIN_CODE);
+    assert_eq!(prefix.len(), PREFIX_LEN);
+    assert_eq!(prefix, vec![0x81, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+    let prefix = encode_prefix(258);
+    assert_eq!(prefix, vec![0x81, 0, 0, 0, 0, 0, 0, 1, 2]);
+
+    assert!(encode_prefix(1) < encode_prefix(2));
+    assert!(encode_prefix(255) < encode_prefix(256));
+
+    let legacy_prefix = encode_legacy_prefix(258);
+    assert_eq!(legacy_prefix.len(), LEGACY_PREFIX_LEN);
+    assert_eq!(legacy_prefix, vec![2, 1, 0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn test_decode_prefix() {
+    let mut key = encode_prefix(258);
+    key.extend_from_slice(&[1, 2, 3]);
+
+    let res = decode_prefix(&key);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), 258);
+
+    let res = decode_prefix(&key[..PREFIX_LEN - 1]);
+    assert!(res.is_err());
+
+    let res = decode_prefix(&encode_legacy_prefix(258));
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_migrate_prefixes() {
+    let mut store = MemoryStore::<()>::new();
+
+    let mut legacy_key = encode_legacy_prefix(COIN_CODE);
+    legacy_key.extend_from_slice(&[1, 2, 3]);
+
+    let write_session = store.session(&Permission::Write).unwrap();
+
+    store.create(&write_session, &legacy_key, &[4, 5, 6]).unwrap();
+    store.create(&write_session, &[7], &[7]).unwrap();
+
+    let res = migrate_prefixes(&mut store, &[COIN_CODE, BLOCK_CODE]);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), 1);
+
+    let mut key = encode_prefix(COIN_CODE);
+    key.extend_from_slice(&[1, 2, 3]);
+
+    let read_session = store.session(&Permission::Read).unwrap();
+
+    let res = store.get(&read_session, &key);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![4, 5, 6]);
+
+    let res = store.lookup(&read_session, &legacy_key);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let res = store.lookup(&read_session, &[7]);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let res = migrate_prefixes(&mut store, &[COIN_CODE, BLOCK_CODE]);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), 0);
+}
+
+#[test]
+fn test_store_migrate() {
+    let mut hasher = Hasher{};
+
+    let mut store = MemoryStore::<()>::new();
+
+    let coin = Coin::new()
+                    .meta(&Meta::default())
+                    .unwrap()
+                    .output_data(&Digest::default(), 0, &Amount::default())
+                    .unwrap()
+                    .finalize(&mut hasher)
+                    .unwrap();
+
+    let mut legacy_key = encode_legacy_prefix(COIN_CODE);
+    legacy_key.extend_from_slice(&coin.id.to_bytes().unwrap());
+
+    let write_session = store.session(&Permission::Write).unwrap();
+
+    store.create(&write_session, &legacy_key, &coin.to_bytes().unwrap()).unwrap();
+
+    let res = Coin::store_lookup(&mut store, &coin.id);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let res = Coin::store_migrate(&mut store);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), 1);
+
+    let res = Coin::store_get(&mut store, &coin.id);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), coin);
+
+    let index_key = IndexKey::from_key(COIN_TX_ID_INDEX, &coin.tx_id).unwrap();
+
+    let res = Coin::store_get_by_index(&mut store, &index_key);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![coin.clone()]);
+
+    let read_session = store.session(&Permission::Read).unwrap();
+
+    let res = store.lookup(&read_session, &legacy_key);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let res = Coin::store_migrate(&mut store);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), 0);
+}
\ No newline at end of file
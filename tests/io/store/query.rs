@@ -0,0 +1,246 @@
+use mitrid_core::base::Meta;
+use mitrid_core::base::Serializable;
+use mitrid_core::io::Permission;
+use mitrid_core::io::Store as BasicStore;
+use mitrid_core::io::Storable;
+use mitrid_core::io::Direction;
+use mitrid_core::io::{Filter, Query};
+use mitrid_core::io::store::QueryPlan;
+
+use fixture::crypto::{Digest, Hasher};
+use fixture::model::Amount;
+use fixture::model::coin::*;
+use fixture::io::store::*;
+
+fn populated_store() -> (Store, Vec<Coin>) {
+    let mut hasher = Hasher{};
+
+    let mut store = Store::new();
+
+    let tx_id_a = Digest::from_slice(&[1; 64]).unwrap();
+    let tx_id_b = Digest::from_slice(&[2; 64]).unwrap();
+
+    let mut coins = Vec::new();
+
+    for out_idx in 0..6 {
+        let tx_id = if out_idx % 2 == 0 { &tx_id_a } else { &tx_id_b };
+        let amount: Amount = out_idx * 10;
+
+        let coin = Coin::new()
+                        .meta(&Meta::default())
+                        .unwrap()
+                        .output_data(tx_id, out_idx, &amount)
+                        .unwrap()
+                        .finalize(&mut hasher)
+                        .unwrap();
+
+        coin.store_create(&mut store).unwrap();
+
+        coins.push(coin);
+    }
+
+    coins.sort();
+
+    (store, coins)
+}
+
+#[test]
+fn test_filter() {
+    let filter = Filter::new(|n: &u64| Ok(*n > 2))
+                    .and(Filter::new(|n: &u64| Ok(*n < 6)));
+
+    assert!(!filter.matches(&2).unwrap());
+    assert!(filter.matches(&3).unwrap());
+    assert!(!filter.matches(&6).unwrap());
+
+    let filter = filter.negate();
+
+    assert!(filter.matches(&2).unwrap());
+    assert!(!filter.matches(&3).unwrap());
+
+    let filter = Filter::new(|n: &u64| Ok(*n == 1))
+                    .or(Filter::new(|n: &u64| Ok(*n == 3)));
+
+    assert!(filter.matches(&1).unwrap());
+    assert!(!filter.matches(&2).unwrap());
+    assert!(filter.matches(&3).unwrap());
+
+    let filter = Filter::new(|_: &u64| Err(String::from("invalid item")));
+
+    assert!(filter.matches(&1).is_err());
+
+    assert!(Filter::<u64>::default().is_all());
+    assert!(Filter::All.and(Filter::All).matches(&1).unwrap());
+    assert!(Filter::new(|n: &u64| Ok(*n == 1)).or(Filter::All).is_all());
+}
+
+#[test]
+fn test_query_plan() {
+    let res = Query::<Digest, Coin>::new().limit(0);
+    assert!(res.is_err());
+
+    let res = Query::<Digest, Coin>::new().plan(&[1, 2]);
+    assert!(res.is_ok());
+
+    let plan = res.unwrap();
+    assert_eq!(plan.from, Some(vec![1, 2]));
+    assert_eq!(plan.to, Some(vec![1, 3]));
+    assert_eq!(plan.limit, None);
+
+    let plan = Query::<Digest, Coin>::new()
+                    .skip(1)
+                    .limit(2)
+                    .unwrap()
+                    .plan(&[1])
+                    .unwrap();
+
+    assert_eq!(plan.skip, 1);
+    assert_eq!(plan.limit, Some(2));
+
+    let plan = Query::<Digest, Coin>::new()
+                    .skip(1)
+                    .limit(2)
+                    .unwrap()
+                    .order_by_key(|coin| coin.out_idx)
+                    .plan(&[1])
+                    .unwrap();
+
+    assert_eq!(plan.skip, 0);
+    assert_eq!(plan.limit, None);
+
+    let key = Digest::from_slice(&[1; 64]).unwrap();
+
+    let res = Query::<Digest, Coin>::new()
+                .from(&key)
+                .unwrap()
+                .to(&key)
+                .unwrap()
+                .plan(&[1]);
+    assert!(res.is_err());
+
+    let res = plan.to_bytes();
+    assert!(res.is_ok());
+
+    let plan_bytes = res.unwrap();
+
+    let res = QueryPlan::from_bytes(&plan_bytes);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), plan);
+}
+
+#[test]
+fn test_query_execute() {
+    let (mut store, coins) = populated_store();
+
+    let res = Query::<Digest, Coin>::new().execute(&mut store);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), coins);
+
+    let res = Coin::store_query(&mut store, &Query::new());
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), coins);
+
+    let query = Query::<Digest, Coin>::new()
+                    .filter(|coin| Ok(coin.out_amount > 20));
+
+    let res = query.execute(&mut store);
+    assert!(res.is_ok());
+
+    let found = res.unwrap();
+    let expected: Vec<Coin> = coins.iter().filter(|coin| coin.out_amount > 20).cloned().collect();
+    assert_eq!(found, expected);
+
+    let res = query.count(&mut store);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), 3);
+
+    let tx_id_a = Digest::from_slice(&[1; 64]).unwrap();
+
+    let query = Query::<Digest, Coin>::new()
+                    .filter(|coin| Ok(coin.out_amount > 0))
+                    .filter(move |coin| Ok(coin.tx_id == tx_id_a))
+                    .order_by_key(|coin| coin.out_idx);
+
+    let res = query.select(&mut store, |coin| Ok(coin.out_idx));
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![2, 4]);
+
+    let query = Query::<Digest, Coin>::new()
+                    .order_by(|a, b| b.out_amount.cmp(&a.out_amount))
+                    .skip(1)
+                    .limit(2)
+                    .unwrap();
+
+    let res = query.select(&mut store, |coin| Ok(coin.out_amount));
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![40, 30]);
+
+    let query = Query::<Digest, Coin>::new()
+                    .direction(Direction::Reverse)
+                    .skip(1)
+                    .limit(3)
+                    .unwrap();
+
+    let res = query.execute(&mut store);
+    assert!(res.is_ok());
+
+    let mut expected = coins.clone();
+    expected.reverse();
+    assert_eq!(res.unwrap(), expected[1..4].to_vec());
+
+    let query = Query::<Digest, Coin>::new()
+                    .from(&coins[1].id)
+                    .unwrap()
+                    .to(&coins[4].id)
+                    .unwrap();
+
+    let res = query.execute(&mut store);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), coins[1..4].to_vec());
+
+    let query = Query::<Digest, Coin>::new()
+                    .filter(|_| Err(String::from("invalid coin")));
+
+    let res = query.execute(&mut store);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_store_query() {
+    let (mut store, coins) = populated_store();
+
+    let plan = Query::<Digest, Coin>::new()
+                    .skip(2)
+                    .limit(2)
+                    .unwrap()
+                    .plan(&<Coin as Storable<Store, (), Digest, Coin>>::store_prefix())
+                    .unwrap();
+
+    let session = store.session(&Permission::Read).unwrap();
+
+    let res = store.query(&session, &plan, &mut |_, _| Ok(true));
+    assert!(res.is_ok());
+
+    let items = res.unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(Coin::from_bytes(&items[0].1).unwrap(), coins[2]);
+    assert_eq!(Coin::from_bytes(&items[1].1).unwrap(), coins[3]);
+
+    let mut seen = 0;
+
+    let res = store.query(&session, &plan, &mut |_, _| {
+        seen += 1;
+        Ok(seen % 2 == 0)
+    });
+    assert!(res.is_ok());
+
+    let items = res.unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(Coin::from_bytes(&items[0].1).unwrap(), coins[5]);
+
+    let mut invalid_plan = plan.clone();
+    invalid_plan.limit = Some(0);
+
+    let res = store.query(&session, &invalid_plan, &mut |_, _| Ok(true));
+    assert!(res.is_err());
+}
\ No newline at end of file
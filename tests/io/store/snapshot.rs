@@ -0,0 +1,221 @@
+use std::fs::write;
+
+use tempfile::tempdir;
+
+use mitrid_core::base::Checkable;
+use mitrid_core::base::Serializable;
+use mitrid_core::base::Meta;
+use mitrid_core::base::Stage;
+use mitrid_core::util::Version;
+use mitrid_core::io::Permission;
+use mitrid_core::io::Store;
+use mitrid_core::io::MemoryStore;
+use mitrid_core::io::store::Snapshot;
+
+use fixture::crypto::{Digest, Hasher};
+
+fn populated_store() -> MemoryStore<()> {
+    let mut store = MemoryStore::<()>::new();
+
+    let session = store.session(&Permission::Write).unwrap();
+
+    for i in 0..4u8 {
+        store.create(&session, &[1, i], &[i]).unwrap();
+        store.create(&session, &[2, i], &[i]).unwrap();
+    }
+
+    store
+}
+
+#[test]
+fn test_snapshot_export() {
+    let mut store = populated_store();
+    let mut hasher = Hasher{};
+    let meta = Meta::default();
+
+    let res = Snapshot::<Digest>::export(&mut store, &meta, &[], &mut hasher);
+    assert!(res.is_ok());
+
+    let snapshot = res.unwrap();
+    assert_eq!(snapshot.header.items_len, 8);
+    assert_eq!(snapshot.header.meta, meta);
+
+    let res = snapshot.check();
+    assert!(res.is_ok());
+
+    let res = snapshot.verify_digest(&mut hasher);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let res = Snapshot::<Digest>::export(&mut store, &meta, &[2], &mut hasher);
+    assert!(res.is_ok());
+
+    let snapshot = res.unwrap();
+    assert_eq!(snapshot.header.prefix, vec![2]);
+    assert_eq!(snapshot.header.items_len, 4);
+    assert!(snapshot.items.iter().all(|(key, _)| key[0] == 2));
+
+    let res = snapshot.digest(&mut hasher);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), snapshot.header.digest);
+
+    let mut invalid_snapshot = snapshot.clone();
+    invalid_snapshot.items[0].1 = vec![9];
+
+    let res = invalid_snapshot.verify_digest(&mut hasher);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let mut invalid_snapshot = snapshot.clone();
+    invalid_snapshot.header.meta.chain = String::from("other");
+
+    let res = invalid_snapshot.verify_digest(&mut hasher);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let mut invalid_snapshot = snapshot.clone();
+    invalid_snapshot.items[0].0 = vec![1, 0];
+
+    let res = invalid_snapshot.check();
+    assert!(res.is_err());
+
+    let mut invalid_snapshot = snapshot.clone();
+    invalid_snapshot.items.pop();
+
+    let res = invalid_snapshot.check();
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_snapshot_import() {
+    let mut store = populated_store();
+    let mut hasher = Hasher{};
+    let meta = Meta::default();
+
+    let snapshot = Snapshot::<Digest>::export(&mut store, &meta, &[1], &mut hasher).unwrap();
+
+    let mut other_store = MemoryStore::<()>::new();
+
+    let mut other_meta = meta.clone();
+    other_meta.chain = String::from("other");
+
+    let res = snapshot.import(&mut other_store, &other_meta, &mut hasher);
+    assert!(res.is_err());
+
+    let mut other_meta = meta.clone();
+    other_meta.stage = Stage::Production;
+
+    let res = snapshot.import(&mut other_store, &other_meta, &mut hasher);
+    assert!(res.is_err());
+
+    let mut other_meta = meta.clone();
+    other_meta.version = Version::new(meta.version.major + 1, 0, 0, "", "").unwrap();
+
+    let res = snapshot.import(&mut other_store, &other_meta, &mut hasher);
+    assert!(res.is_err());
+
+    let mut tampered_snapshot = snapshot.clone();
+    tampered_snapshot.items[0].1 = vec![9];
+
+    let res = tampered_snapshot.import(&mut other_store, &meta, &mut hasher);
+    assert!(res.is_err());
+    assert_eq!(other_store.items_len(), 0);
+
+    let mut other_meta = meta.clone();
+    other_meta.chain = String::from("other");
+
+    let mut tampered_snapshot = snapshot.clone();
+    tampered_snapshot.header.meta.chain = other_meta.chain.clone();
+
+    let res = tampered_snapshot.import(&mut other_store, &other_meta, &mut hasher);
+    assert!(res.is_err());
+    assert_eq!(other_store.items_len(), 0);
+
+    let res = snapshot.import(&mut other_store, &meta, &mut hasher);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), 4);
+    assert_eq!(other_store.items_len(), 4);
+
+    let session = other_store.session(&Permission::Read).unwrap();
+
+    let res = other_store.get(&session, &[1, 3]);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![3]);
+}
+
+#[test]
+fn test_snapshot_restore() {
+    let mut store = populated_store();
+    let mut hasher = Hasher{};
+    let meta = Meta::default();
+
+    let snapshot = Snapshot::<Digest>::export(&mut store, &meta, &[1], &mut hasher).unwrap();
+
+    let mut other_store = MemoryStore::<()>::new();
+
+    let session = other_store.session(&Permission::Write).unwrap();
+
+    other_store.create(&session, &[1, 9], &[9]).unwrap();
+    other_store.create(&session, &[2, 9], &[9]).unwrap();
+
+    let res = snapshot.import(&mut other_store, &meta, &mut hasher);
+    assert!(res.is_ok());
+    assert_eq!(other_store.items_len(), 6);
+
+    let res = snapshot.restore(&mut other_store, &meta, &mut hasher);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), 4);
+    assert_eq!(other_store.items_len(), 5);
+
+    let session = other_store.session(&Permission::Read).unwrap();
+
+    let res = other_store.lookup(&session, &[1, 9]);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let res = other_store.lookup(&session, &[2, 9]);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+}
+
+#[test]
+fn test_snapshot_file() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("store.snapshot");
+
+    let mut store = populated_store();
+    let mut hasher = Hasher{};
+    let meta = Meta::default();
+
+    let snapshot = Snapshot::<Digest>::export(&mut store, &meta, &[], &mut hasher).unwrap();
+
+    let res = snapshot.write_file(&path);
+    assert!(res.is_ok());
+
+    let res = Snapshot::<Digest>::read_file(&path);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), snapshot);
+
+    let invalid_path = dir.path().join("invalid.snapshot");
+    write(&invalid_path, b"not a snapshot").unwrap();
+
+    let res = Snapshot::<Digest>::read_file(&invalid_path);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_snapshot_bytes() {
+    let mut store = populated_store();
+    let mut hasher = Hasher{};
+
+    let snapshot = Snapshot::<Digest>::export(&mut store, &Meta::default(), &[], &mut hasher).unwrap();
+
+    let res = snapshot.to_bytes();
+    assert!(res.is_ok());
+
+    let snapshot_bytes = res.unwrap();
+
+    let res = Snapshot::from_bytes(&snapshot_bytes);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), snapshot);
+}
\ No newline at end of file
@@ -2,6 +2,8 @@ use mitrid_core::base::Sizable;
 use mitrid_core::base::Checkable;
 use mitrid_core::io::store::Store as BasicStore;
 use mitrid_core::io::Permission;
+use mitrid_core::io::Acl;
+use mitrid_core::io::Direction;
 
 use fixture::io::store::*;
 
@@ -507,12 +509,52 @@ fn test_eval_size() {
     }
 }
 
+#[test]
+fn test_eval_acl() {
+    let mut store = Store::new();
+    let evaluator = StoreEvaluator{};
+
+    let write_permission = Permission::Write;
+    let session = store.session(&write_permission).unwrap();
+
+    let params = StoreEvalParams::Size;
+
+    let res = store.eval(&session, &params, &evaluator);
+    assert!(res.is_err());
+
+    let acl = Acl::parse("eval:evalparams").unwrap();
+    let eval_session = session.clone().acl(&acl).unwrap();
+
+    let res = store.eval(&eval_session, &params, &evaluator);
+    assert!(res.is_ok());
+
+    let acl = Acl::parse("evalmut:*").unwrap();
+    let eval_mut_session = session.acl(&acl).unwrap();
+
+    let res = store.eval(&eval_mut_session, &params, &evaluator);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_eval_read_write() {
+    let mut store = Store::new();
+    let evaluator = StoreEvaluator{};
+
+    let permission = Permission::Read | Permission::Write;
+    let session = store.session(&permission).unwrap();
+
+    let params = StoreEvalParams::Size;
+
+    let res = store.eval(&session, &params, &evaluator);
+    assert!(res.is_ok());
+}
+
 #[test]
 fn test_eval_dump_sessions() {
     let mut store = Store::new();
     let evaluator = StoreEvaluator{};
 
-    let permission = Permission::default();
+    let permission = Permission::Read;
 
     let session_a = store.session(&permission).unwrap();
     let session_b = store.session(&permission).unwrap();
@@ -761,4 +803,87 @@ fn test_eval_mut_clear() {
             assert_eq!(store.size(), 0)
         },
     }
+}
+
+#[test]
+fn test_store_commit() {
+    let mut store = Store::new();
+
+    let read_permission = Permission::Read;
+    let read_session = store.session(&read_permission).unwrap();
+
+    let write_permission = Permission::Write;
+    let write_session = store.session(&write_permission).unwrap();
+
+    let res = store.begin(&read_session);
+    assert!(res.is_err());
+
+    store.create(&write_session, &[0], &[0]).unwrap();
+
+    let mut batch = store.begin(&write_session).unwrap();
+    batch.create(&[1], &[1]).unwrap();
+    batch.update(&[0], &[2]).unwrap();
+
+    let res = store.commit(&write_session, &batch);
+    assert!(res.is_ok());
+
+    let res = store.get(&read_session, &[0]);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![2]);
+
+    let res = store.get(&read_session, &[1]);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![1]);
+
+    let mut batch = store.begin(&write_session).unwrap();
+    batch.upsert(&[0], &[3]).unwrap();
+    batch.delete(&[1]).unwrap();
+    batch.create(&[2], &[2]).unwrap();
+    batch.update(&[3], &[3]).unwrap();
+
+    let res = store.commit(&write_session, &batch);
+    assert!(res.is_err());
+
+    let res = store.get(&read_session, &[0]);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![2]);
+
+    let res = store.get(&read_session, &[1]);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![1]);
+
+    let res = store.lookup(&read_session, &[2]);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let res = store.rollback(&write_session, &mut batch);
+    assert!(res.is_ok());
+    assert!(batch.is_empty());
+}
+
+#[test]
+fn test_store_scan() {
+    let mut store = Store::new();
+
+    let write_permission = Permission::Write;
+    let write_session = store.session(&write_permission).unwrap();
+
+    for i in 0..5u8 {
+        store.create(&write_session, &[i], &[i]).unwrap();
+    }
+
+    let read_permission = Permission::Read;
+    let read_session = store.session(&read_permission).unwrap();
+
+    let res = store.scan(&read_session, None, None, None, Direction::Forward, 2);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![(vec![0], vec![0]), (vec![1], vec![1])]);
+
+    let res = store.scan(&read_session, None, Some(vec![4]), Some(vec![1]), Direction::Forward, 5);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![(vec![2], vec![2]), (vec![3], vec![3])]);
+
+    let res = store.scan(&read_session, Some(vec![1]), None, Some(vec![3]), Direction::Reverse, 5);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![(vec![2], vec![2]), (vec![1], vec![1])]);
 }
\ No newline at end of file
@@ -0,0 +1,356 @@
+use std::fs::{OpenOptions, metadata, write, create_dir, remove_dir};
+use std::io::Write;
+
+use tempfile::tempdir;
+
+use mitrid_core::base::Checkable;
+use mitrid_core::base::Meta;
+use mitrid_core::io::Permission;
+use mitrid_core::io::{Store, Storable};
+use mitrid_core::io::WalStore;
+use mitrid_core::io::store::SyncPolicy;
+use mitrid_core::io::Batch;
+
+use fixture::crypto::{Digest, Hasher};
+use fixture::model::Amount;
+use fixture::model::coin::*;
+
+#[test]
+fn test_sync_policy_check() {
+    let res = SyncPolicy::Always.check();
+    assert!(res.is_ok());
+
+    let res = SyncPolicy::Every(10).check();
+    assert!(res.is_ok());
+
+    let res = SyncPolicy::Every(0).check();
+    assert!(res.is_err());
+
+    let res = SyncPolicy::Never.check();
+    assert!(res.is_ok());
+}
+
+#[test]
+fn test_wal_store_open() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("store.wal");
+
+    let res = WalStore::<()>::open(&path);
+    assert!(res.is_ok());
+
+    let store = res.unwrap();
+    assert_eq!(store.path(), path);
+    assert_eq!(store.items_len(), 0);
+    assert_eq!(store.records_len(), 0);
+
+    let res = store.check();
+    assert!(res.is_ok());
+
+    let res = store.clone().sync_policy(SyncPolicy::Every(0));
+    assert!(res.is_err());
+
+    let res = store.clone().compaction_threshold(0);
+    assert!(res.is_err());
+
+    let invalid_path = dir.path().join("invalid.wal");
+    write(&invalid_path, b"not a log").unwrap();
+
+    let res = WalStore::<()>::open(&invalid_path);
+    assert!(res.is_err());
+
+    let res = WalStore::<()>::open(&path);
+    assert!(res.is_err());
+
+    drop(store);
+
+    let res = WalStore::<()>::open(&path);
+    assert!(res.is_ok());
+}
+
+#[test]
+fn test_wal_store_crud() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("store.wal");
+
+    {
+        let mut store = WalStore::<()>::open(&path).unwrap();
+
+        let write_session = store.session(&Permission::Write).unwrap();
+        let read_session = store.session(&Permission::Read).unwrap();
+
+        let res = store.create(&read_session, &[1], &[1]);
+        assert!(res.is_err());
+
+        let res = store.create(&write_session, &[1], &[1]);
+        assert!(res.is_ok());
+
+        let res = store.create(&write_session, &[1], &[1]);
+        assert!(res.is_err());
+
+        let res = store.update(&write_session, &[1], &[2]);
+        assert!(res.is_ok());
+
+        let res = store.update(&write_session, &[2], &[2]);
+        assert!(res.is_err());
+
+        let res = store.upsert(&write_session, &[2], &[2]);
+        assert!(res.is_ok());
+
+        let res = store.upsert(&write_session, &[3], &[3]);
+        assert!(res.is_ok());
+
+        let res = store.delete(&write_session, &[3]);
+        assert!(res.is_ok());
+
+        let res = store.delete(&write_session, &[3]);
+        assert!(res.is_err());
+
+        let res = store.get(&read_session, &[1]);
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), vec![2]);
+
+        assert_eq!(store.items_len(), 2);
+        assert_eq!(store.records_len(), 5);
+    }
+
+    let mut store = WalStore::<()>::open(&path).unwrap();
+    assert_eq!(store.items_len(), 2);
+    assert_eq!(store.records_len(), 5);
+
+    let read_session = store.session(&Permission::Read).unwrap();
+
+    let res = store.list(&read_session, None, None, None, 0);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![vec![2], vec![2]]);
+
+    let res = store.lookup(&read_session, &[3]);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+}
+
+#[test]
+fn test_wal_store_commit() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("store.wal");
+
+    let mut store = WalStore::<()>::open(&path).unwrap();
+
+    let write_session = store.session(&Permission::Write).unwrap();
+
+    let mut batch = store.begin(&write_session).unwrap();
+    batch.create(&[1], &[1]).unwrap();
+    batch.create(&[2], &[2]).unwrap();
+    batch.delete(&[1]).unwrap();
+
+    let res = store.commit(&write_session, &batch);
+    assert!(res.is_ok());
+    assert_eq!(store.items_len(), 1);
+    assert_eq!(store.records_len(), 1);
+
+    let mut invalid_batch = Batch::new();
+    invalid_batch.create(&[3], &[3]).unwrap();
+    invalid_batch.create(&[2], &[2]).unwrap();
+
+    let res = store.commit(&write_session, &invalid_batch);
+    assert!(res.is_err());
+    assert_eq!(store.items_len(), 1);
+    assert_eq!(store.records_len(), 1);
+
+    drop(store);
+
+    let mut store = WalStore::<()>::open(&path).unwrap();
+    assert_eq!(store.items_len(), 1);
+
+    let read_session = store.session(&Permission::Read).unwrap();
+
+    let res = store.get(&read_session, &[2]);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![2]);
+}
+
+#[test]
+fn test_wal_store_recovery() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("store.wal");
+
+    {
+        let mut store = WalStore::<()>::open(&path).unwrap();
+
+        let write_session = store.session(&Permission::Write).unwrap();
+
+        store.create(&write_session, &[1], &[1]).unwrap();
+        store.create(&write_session, &[2], &[2]).unwrap();
+    }
+
+    let valid_len = metadata(&path).unwrap().len();
+
+    {
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+
+        file.write_all(&[0, 0, 0, 64, 1, 2, 3, 4, 5]).unwrap();
+    }
+
+    let mut store = WalStore::<()>::open(&path).unwrap();
+    assert_eq!(store.items_len(), 2);
+    assert_eq!(metadata(&path).unwrap().len(), valid_len);
+
+    let write_session = store.session(&Permission::Write).unwrap();
+
+    store.create(&write_session, &[3], &[3]).unwrap();
+
+    drop(store);
+
+    {
+        let file = OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .unwrap();
+
+        file.set_len(metadata(&path).unwrap().len() - 1).unwrap();
+    }
+
+    let store = WalStore::<()>::open(&path).unwrap();
+    assert_eq!(store.items_len(), 2);
+    assert_eq!(metadata(&path).unwrap().len(), valid_len);
+}
+
+#[test]
+fn test_wal_store_compact() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("store.wal");
+
+    let mut store = WalStore::<()>::open(&path)
+                        .unwrap()
+                        .sync_policy(SyncPolicy::Never)
+                        .unwrap()
+                        .compaction_threshold(8)
+                        .unwrap();
+
+    let write_session = store.session(&Permission::Write).unwrap();
+
+    for i in 0..4u8 {
+        store.create(&write_session, &[i], &[i]).unwrap();
+    }
+
+    for _ in 0..7 {
+        store.update(&write_session, &[0], &[9]).unwrap();
+    }
+
+    assert_eq!(store.records_len(), 11);
+
+    store.update(&write_session, &[0], &[10]).unwrap();
+    assert_eq!(store.records_len(), 1);
+
+    store.delete(&write_session, &[3]).unwrap();
+    assert_eq!(store.records_len(), 2);
+
+    let res = store.compact();
+    assert!(res.is_ok());
+    assert_eq!(store.records_len(), 1);
+
+    let res = store.sync();
+    assert!(res.is_ok());
+
+    drop(store);
+
+    let mut store = WalStore::<()>::open(&path).unwrap();
+    assert_eq!(store.items_len(), 3);
+
+    let read_session = store.session(&Permission::Read).unwrap();
+
+    let res = store.list(&read_session, None, None, None, 0);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![vec![10], vec![1], vec![2]]);
+}
+
+#[test]
+fn test_wal_store_compact_failure() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("store.wal");
+    let tmp_path = dir.path().join("store.wal.compact");
+
+    let mut store = WalStore::<()>::open(&path)
+                        .unwrap()
+                        .compaction_threshold(2)
+                        .unwrap();
+
+    let write_session = store.session(&Permission::Write).unwrap();
+
+    create_dir(&tmp_path).unwrap();
+
+    store.create(&write_session, &[0], &[0]).unwrap();
+
+    assert!(store.last_compaction_error().is_none());
+
+    for i in 1..4u8 {
+        let res = store.update(&write_session, &[0], &[i]);
+        assert!(res.is_ok());
+    }
+
+    assert_eq!(store.records_len(), 4);
+    assert!(store.last_compaction_error().is_some());
+
+    let res = store.compact();
+    assert!(res.is_err());
+    assert_eq!(store.last_compaction_error(), res.err());
+
+    remove_dir(&tmp_path).unwrap();
+
+    let res = store.update(&write_session, &[0], &[4]);
+    assert!(res.is_ok());
+    assert_eq!(store.records_len(), 1);
+    assert!(store.last_compaction_error().is_none());
+
+    drop(store);
+
+    let mut store = WalStore::<()>::open(&path).unwrap();
+
+    let read_session = store.session(&Permission::Read).unwrap();
+
+    let res = store.get(&read_session, &[0]);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![4]);
+}
+
+#[test]
+fn test_wal_store_storable() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("store.wal");
+
+    let mut hasher = Hasher{};
+
+    let coin = Coin::new()
+                    .meta(&Meta::default())
+                    .unwrap()
+                    .output_data(&Digest::default(), 0, &Amount::default())
+                    .unwrap()
+                    .finalize(&mut hasher)
+                    .unwrap();
+
+    {
+        let mut store = WalStore::<()>::open(&path).unwrap();
+
+        let res = coin.store_create(&mut store);
+        assert!(res.is_ok());
+    }
+
+    let mut store = WalStore::<()>::open(&path).unwrap();
+
+    let res = Coin::store_get(&mut store, &coin.id);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), coin);
+
+    let res = Coin::store_count(&mut store, None, None);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), 1);
+
+    let res = coin.store_delete(&mut store);
+    assert!(res.is_ok());
+
+    let res = Coin::store_lookup(&mut store, &coin.id);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+}
\ No newline at end of file
@@ -0,0 +1,199 @@
+use std::sync::mpsc::channel;
+
+use tempfile::tempdir;
+
+use mitrid_core::base::Meta;
+use mitrid_core::base::Checkable;
+use mitrid_core::base::Serializable;
+use mitrid_core::io::Permission;
+use mitrid_core::io::{Store, Storable};
+use mitrid_core::io::{MemoryStore, WalStore};
+use mitrid_core::io::Batch;
+use mitrid_core::io::{StoreEvent, EventChannel};
+use mitrid_core::io::store::Watchers;
+
+use fixture::crypto::{Digest, Hasher};
+use fixture::model::Amount;
+use fixture::model::coin::*;
+use fixture::io::store::Store as FixtureStore;
+
+#[test]
+fn test_store_event() {
+    let event = StoreEvent::Create(vec![1], vec![2]);
+    assert_eq!(event.key(), &[1]);
+    assert_eq!(event.value(), Some(&[2][..]));
+
+    let event = StoreEvent::Delete(vec![1]);
+    assert_eq!(event.key(), &[1]);
+    assert_eq!(event.value(), None);
+
+    let res = event.check();
+    assert!(res.is_ok());
+
+    let res = StoreEvent::Delete(vec![]).check();
+    assert!(res.is_err());
+
+    let res = event.to_bytes();
+    assert!(res.is_ok());
+
+    let event_bytes = res.unwrap();
+
+    let res = StoreEvent::from_bytes(&event_bytes);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), event);
+}
+
+#[test]
+fn test_watchers() {
+    let mut watchers = Watchers::new();
+    assert!(watchers.is_empty());
+
+    let channel_a = EventChannel::new();
+    let channel_b = EventChannel::new();
+
+    let id_a = watchers.watch(&[1], &channel_a.sender);
+    let id_b = watchers.watch(&[], &channel_b.sender);
+    assert_ne!(id_a, id_b);
+    assert_eq!(watchers.len(), 2);
+
+    let events = vec![StoreEvent::Create(vec![1, 1], vec![1]), StoreEvent::Delete(vec![2, 1])];
+
+    watchers.notify(&events);
+
+    let received: Vec<StoreEvent> = channel_a.receiver.try_iter().collect();
+    assert_eq!(received, events[..1].to_vec());
+
+    let received: Vec<StoreEvent> = channel_b.receiver.try_iter().collect();
+    assert_eq!(received, events);
+
+    drop(channel_a);
+
+    watchers.notify(&events);
+    assert_eq!(watchers.len(), 1);
+
+    let res = watchers.unwatch(id_a);
+    assert!(res.is_err());
+
+    let res = watchers.unwatch(id_b);
+    assert!(res.is_ok());
+    assert!(watchers.is_empty());
+}
+
+#[test]
+fn test_memory_store_watch() {
+    let mut store = MemoryStore::<()>::new();
+
+    let (sender, receiver) = channel();
+
+    let write_session = store.session(&Permission::Write).unwrap();
+    let read_session = store.session(&Permission::Read).unwrap();
+
+    let res = store.watch(&write_session, &[1], &sender);
+    assert!(res.is_err());
+
+    let res = store.watch(&read_session, &[1], &sender);
+    assert!(res.is_ok());
+
+    let id = res.unwrap();
+    assert_eq!(store.watchers_len(), 1);
+
+    store.create(&write_session, &[1, 1], &[1]).unwrap();
+    store.create(&write_session, &[2, 1], &[1]).unwrap();
+    store.update(&write_session, &[1, 1], &[2]).unwrap();
+    store.upsert(&write_session, &[1, 2], &[3]).unwrap();
+    store.delete(&write_session, &[1, 2]).unwrap();
+
+    let res = store.create(&write_session, &[1, 1], &[1]);
+    assert!(res.is_err());
+
+    let mut batch = Batch::new();
+    batch.create(&[1, 3], &[4]).unwrap();
+    batch.delete(&[1, 3]).unwrap();
+    batch.upsert(&[1, 1], &[5]).unwrap();
+    batch.create(&[1, 4], &[6]).unwrap();
+
+    store.commit(&write_session, &batch).unwrap();
+
+    let received: Vec<StoreEvent> = receiver.try_iter().collect();
+    assert_eq!(received, vec![
+        StoreEvent::Create(vec![1, 1], vec![1]),
+        StoreEvent::Update(vec![1, 1], vec![2]),
+        StoreEvent::Create(vec![1, 2], vec![3]),
+        StoreEvent::Delete(vec![1, 2]),
+        StoreEvent::Update(vec![1, 1], vec![5]),
+        StoreEvent::Create(vec![1, 4], vec![6]),
+    ]);
+
+    let res = store.unwatch(&read_session, id);
+    assert!(res.is_ok());
+    assert_eq!(store.watchers_len(), 0);
+
+    store.delete(&write_session, &[1, 1]).unwrap();
+    assert!(receiver.try_recv().is_err());
+
+    let res = store.unwatch(&read_session, id);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_wal_store_watch() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("store.wal");
+
+    let mut store = WalStore::<()>::open(&path).unwrap();
+
+    let channel = EventChannel::new();
+
+    let read_session = store.session(&Permission::Read).unwrap();
+    let write_session = store.session(&Permission::Write).unwrap();
+
+    store.watch(&read_session, &[1], &channel.sender).unwrap();
+    assert_eq!(store.watchers_len(), 1);
+
+    store.create(&write_session, &[1, 1], &[1]).unwrap();
+    store.delete(&write_session, &[1, 1]).unwrap();
+
+    let received: Vec<StoreEvent> = channel.receiver.try_iter().collect();
+    assert_eq!(received, vec![
+        StoreEvent::Create(vec![1, 1], vec![1]),
+        StoreEvent::Delete(vec![1, 1]),
+    ]);
+}
+
+#[test]
+fn test_storable_watch() {
+    let mut hasher = Hasher{};
+
+    let mut store = MemoryStore::<()>::new();
+
+    let channel = EventChannel::new();
+
+    let res = Coin::store_watch(&mut store, &channel.sender);
+    assert!(res.is_ok());
+
+    let coin = Coin::new()
+                    .meta(&Meta::default())
+                    .unwrap()
+                    .output_data(&Digest::default(), 0, &Amount::default())
+                    .unwrap()
+                    .finalize(&mut hasher)
+                    .unwrap();
+
+    coin.store_create(&mut store).unwrap();
+
+    let event = channel.receiver.try_recv().unwrap();
+
+    match event {
+        StoreEvent::Create(_, value) => {
+            assert_eq!(Coin::from_bytes(&value).unwrap(), coin);
+        },
+        _ => panic!("invalid event"),
+    }
+
+    assert!(channel.receiver.try_recv().is_err());
+
+    let mut fixture_store = FixtureStore::new();
+
+    let res = Coin::store_watch(&mut fixture_store, &channel.sender);
+    assert!(res.is_err());
+}
\ No newline at end of file
@@ -8,6 +8,7 @@ extern crate tempfile;
 
 mod base;
 mod util;
+mod crypto;
 mod fixture;
 mod io;
 mod model;
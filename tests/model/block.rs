@@ -4,6 +4,8 @@ use mitrid_core::base::Serializable;
 use mitrid_core::util::Version;
 use mitrid_core::base::Meta;
 use mitrid_core::io::Storable;
+use mitrid_core::io::Store as BasicStore;
+use mitrid_core::io::{Permission, Batch};
 
 use fixture::base::eval::*;
 use fixture::base::Payload;
@@ -51,6 +53,17 @@ fn test_block_prev_blocks() {
     let res = Block::new().prev_blocks(&vec![bn.clone()]);
     assert!(res.is_ok());
 
+    let block = res.unwrap();
+    assert_eq!(block.height, block_height + 1);
+    assert!(!block.is_genesis());
+
+    let res = Block::new().prev_blocks(&vec![]);
+    assert!(res.is_ok());
+
+    let block = res.unwrap();
+    assert_eq!(block.height, 0);
+    assert!(block.is_genesis());
+
     let mut invalid_version = Version::default();
     invalid_version.buildmeta = "/\\".into();
 
@@ -129,20 +142,24 @@ fn test_block_payload() {
 
 #[test]
 fn test_block_prove() {
+    let mut hasher = Hasher{};
+
     let bits = 3;
     let mut prover = Prover::new(bits);
 
-    let res = Block::new().prove(&mut prover);
+    let res = Block::new().prove(&mut hasher, &mut prover);
     assert!(res.is_ok());
 }
 
 #[test]
 fn test_block_verify_proof() {
+    let mut hasher = Hasher{};
+
     let bits = 3;
     let mut prover = Prover::new(bits);
 
     let mut block = Block::new()
-                        .prove(&mut prover)
+                        .prove(&mut hasher, &mut prover)
                         .unwrap();
     
     let res = block.clone().verify_proof(&mut prover);
@@ -162,11 +179,13 @@ fn test_block_verify_proof() {
 
 #[test]
 fn test_block_check_proof() {
+    let mut hasher = Hasher{};
+
     let bits = 3;
     let mut prover = Prover::new(bits);
 
     let mut block = Block::new()
-                        .prove(&mut prover)
+                        .prove(&mut hasher, &mut prover)
                         .unwrap();
     
     let res = block.clone().check_proof(&mut prover);
@@ -198,6 +217,7 @@ fn test_block_verify_digest() {
 
     let mut hasher = Hasher{};
 
+    block.transactions_root = block.transactions_tree(&mut hasher).unwrap().root().unwrap();
     block.id = block.digest(&mut hasher).unwrap();
     
     let res = block.verify_digest(&mut hasher);
@@ -211,6 +231,7 @@ fn test_block_check_digest() {
 
     let mut hasher = Hasher{};
 
+    block.transactions_root = block.transactions_tree(&mut hasher).unwrap().root().unwrap();
     block.id = block.digest(&mut hasher).unwrap();
     
     let res = block.check_digest(&mut hasher);
@@ -277,7 +298,7 @@ fn test_block_finalize() {
                         .unwrap()
                         .payload(&Payload::default())
                         .unwrap()
-                        .prove(&mut prover)
+                        .prove(&mut hasher, &mut prover)
                         .unwrap();
 
     let res = block.clone().finalize(&mut hasher);
@@ -289,6 +310,104 @@ fn test_block_finalize() {
     assert!(res.is_err());
 }
 
+#[test]
+fn test_block_transactions_root() {
+    let mut hasher = Hasher{};
+
+    let mut transactions = Vec::new();
+
+    for amount in 1..4 {
+        let output = Output::new()
+                        .amount(&amount)
+                        .unwrap()
+                        .finalize(&mut hasher)
+                        .unwrap();
+
+        let tx = Transaction::new()
+                    .outputs(&vec![output])
+                    .unwrap()
+                    .finalize(&mut hasher)
+                    .unwrap();
+
+        transactions.push(tx);
+    }
+
+    let bits = 3;
+    let mut prover = Prover::new(bits);
+
+    let block = Block::new()
+                    .transactions(&transactions)
+                    .unwrap()
+                    .prove(&mut hasher, &mut prover)
+                    .unwrap()
+                    .finalize(&mut hasher)
+                    .unwrap();
+
+    let res = block.verify_transactions_root(&mut hasher);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let res = block.check_transactions_root(&mut hasher);
+    assert!(res.is_ok());
+
+    let res = block.check_proof(&mut prover);
+    assert!(res.is_ok());
+
+    let res = block.check_digest(&mut hasher);
+    assert!(res.is_ok());
+
+    for tx in transactions.iter() {
+        let res = block.transaction_proof(&mut hasher, &tx.id);
+        assert!(res.is_ok());
+
+        let proof = res.unwrap();
+
+        let res = block.verify_transaction_proof(&mut hasher, &tx.id, &proof);
+        assert!(res.is_ok());
+        assert!(res.unwrap());
+
+        let res = proof.verify(&mut hasher, &tx.id, &block.transactions_root);
+        assert!(res.is_ok());
+        assert!(res.unwrap());
+
+        let res = block.verify_transaction_proof(&mut hasher, &Digest::default(), &proof);
+        assert!(res.is_ok());
+        assert!(!res.unwrap());
+
+        let mut invalid_proof = proof.clone();
+        invalid_proof.leaves_len += 1;
+
+        let res = block.verify_transaction_proof(&mut hasher, &tx.id, &invalid_proof);
+        assert!(res.is_ok());
+        assert!(!res.unwrap());
+    }
+
+    let res = block.transaction_proof(&mut hasher, &Digest::default());
+    assert!(res.is_err());
+
+    let mut tampered_block = block.clone();
+    tampered_block.transactions.swap(0, 1);
+
+    let res = tampered_block.check_transactions_root(&mut hasher);
+    assert!(res.is_err());
+
+    let res = tampered_block.verify_digest(&mut hasher);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let res = tampered_block.check_digest(&mut hasher);
+    assert!(res.is_err());
+
+    let mut tampered_block = block.clone();
+    tampered_block.transactions_root = Digest::default();
+
+    let res = tampered_block.check_transactions_root(&mut hasher);
+    assert!(res.is_err());
+
+    let res = tampered_block.check_digest(&mut hasher);
+    assert!(res.is_err());
+}
+
 #[test]
 fn test_block_check() {
     let block_height = 0;
@@ -349,7 +468,7 @@ fn test_block_check() {
                         .unwrap()
                         .payload(&Payload::default())
                         .unwrap()
-                        .prove(&mut prover)
+                        .prove(&mut hasher, &mut prover)
                         .unwrap()
                         .finalize(&mut hasher)
                         .unwrap();
@@ -363,6 +482,65 @@ fn test_block_check() {
     assert!(res.is_err());
 }
 
+#[test]
+fn test_block_check_height() {
+    let bn = BlockNode::new()
+                .meta(&Meta::default())
+                .unwrap()
+                .block_data(&Digest::default(), 0)
+                .unwrap();
+
+    let mut hasher = Hasher{};
+
+    let mut genesis = Block::new()
+                        .meta(&Meta::default())
+                        .unwrap()
+                        .prev_blocks(&vec![])
+                        .unwrap()
+                        .finalize(&mut hasher)
+                        .unwrap();
+
+    assert_eq!(genesis.height, 0);
+    assert!(genesis.is_genesis());
+    assert!(!genesis.is_legacy_genesis());
+
+    let res = genesis.check();
+    assert!(res.is_ok());
+
+    genesis.height = 1;
+    assert!(!genesis.is_genesis());
+    assert!(genesis.is_legacy_genesis());
+
+    let res = genesis.check();
+    assert!(res.is_ok());
+
+    genesis.height = 2;
+    assert!(!genesis.is_legacy_genesis());
+
+    let res = genesis.check();
+    assert!(res.is_err());
+
+    let mut block = Block::new()
+                        .meta(&Meta::default())
+                        .unwrap()
+                        .prev_blocks(&vec![bn])
+                        .unwrap()
+                        .finalize(&mut hasher)
+                        .unwrap();
+
+    assert_eq!(block.height, 1);
+    assert!(!block.is_genesis());
+    assert!(!block.is_legacy_genesis());
+
+    let res = block.check();
+    assert!(res.is_ok());
+
+    block.height = 0;
+
+    let res = block.check();
+    assert!(res.is_err());
+}
+
 #[test]
 fn test_block_eval() {
     let block_height = 0;
@@ -425,7 +603,7 @@ fn test_block_eval() {
                         .unwrap()
                         .payload(&payload)
                         .unwrap()
-                        .prove(&mut prover)
+                        .prove(&mut hasher, &mut prover)
                         .unwrap()
                         .finalize(&mut hasher)
                         .unwrap();
@@ -569,7 +747,7 @@ fn test_block_store() {
                     .unwrap()
                     .payload(&Payload::default())
                     .unwrap()
-                    .prove(&mut prover)
+                    .prove(&mut hasher, &mut prover)
                     .unwrap()
                     .finalize(&mut hasher)
                     .unwrap();
@@ -724,4 +902,133 @@ fn test_block_store() {
 
     let list = res.unwrap();
     assert_eq!(list, vec![block.clone()]);
+}
+
+#[test]
+fn test_block_store_batch() {
+    let block_height = 0;
+
+    let bn = BlockNode::new()
+                .meta(&Meta::default())
+                .unwrap()
+                .block_data(&Digest::default(), block_height)
+                .unwrap();
+
+    let mut hasher = Hasher{};
+
+    let coin = Coin::new()
+                    .finalize(&mut hasher)
+                    .unwrap();
+
+    let input = Input::new()
+                    .meta(&Meta::default())
+                    .unwrap()
+                    .coin(&coin)
+                    .unwrap()
+                    .payload(&Payload::default())
+                    .unwrap()
+                    .finalize(&mut hasher)
+                    .unwrap();
+
+    let output = Output::new()
+                    .meta(&Meta::default())
+                    .unwrap()
+                    .amount(&Amount::default())
+                    .unwrap()
+                    .payload(&Payload::default())
+                    .unwrap()
+                    .finalize(&mut hasher)
+                    .unwrap();
+
+    let tx = Transaction::new()
+                .meta(&Meta::default())
+                .unwrap()
+                .inputs(&vec![input])
+                .unwrap()
+                .outputs(&vec![output])
+                .unwrap()
+                .payload(&Payload::default())
+                .unwrap()
+                .finalize(&mut hasher)
+                .unwrap();
+
+    let bits = 3;
+    let mut prover = Prover::new(bits);
+
+    let block = Block::new()
+                    .meta(&Meta::default())
+                    .unwrap()
+                    .prev_blocks(&vec![bn.clone()])
+                    .unwrap()
+                    .transactions(&vec![tx.clone()])
+                    .unwrap()
+                    .payload(&Payload::default())
+                    .unwrap()
+                    .prove(&mut hasher, &mut prover)
+                    .unwrap()
+                    .finalize(&mut hasher)
+                    .unwrap();
+
+    let mut store = Store::new();
+
+    let mut batch = Batch::new();
+
+    let res = tx.store_batch_create(&mut store, &mut batch);
+    assert!(res.is_ok());
+
+    let res = block.store_batch_create(&mut store, &mut batch);
+    assert!(res.is_ok());
+
+    let res = coin.store_batch_create(&mut store, &mut batch);
+    assert!(res.is_ok());
+
+    let res = block.store_batch_create(&mut store, &mut batch);
+    assert!(res.is_ok());
+
+    let session = store.session(&Permission::Write).unwrap();
+
+    let res = store.commit(&session, &batch);
+    assert!(res.is_err());
+
+    let res = Transaction::store_lookup(&mut store, &tx.id);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let res = Block::store_lookup(&mut store, &block.id);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    batch.clear();
+
+    let res = tx.store_batch_create(&mut store, &mut batch);
+    assert!(res.is_ok());
+
+    let res = block.store_batch_create(&mut store, &mut batch);
+    assert!(res.is_ok());
+
+    let res = store.commit(&session, &batch);
+    assert!(res.is_ok());
+
+    let res = Transaction::store_get(&mut store, &tx.id);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), tx);
+
+    let res = Block::store_get(&mut store, &block.id);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), block);
+
+    batch.clear();
+
+    let res = block.store_batch_update(&mut store, &mut batch);
+    assert!(res.is_ok());
+
+    let res = tx.store_batch_delete(&mut store, &mut batch);
+    assert!(res.is_ok());
+
+    let res = store.commit(&session, &batch);
+    assert!(res.is_ok());
+
+    let res = Transaction::store_lookup(&mut store, &tx.id);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
 }
\ No newline at end of file
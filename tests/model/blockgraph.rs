@@ -4,14 +4,30 @@ use mitrid_core::base::Serializable;
 use mitrid_core::util::Version;
 use mitrid_core::base::Meta;
 use mitrid_core::io::Storable;
+use mitrid_core::model::TipRule;
+use mitrid_core::model::BlockWeight;
+use mitrid_core::model::blockgraph::BLOCKGRAPH_MAX_ORPHANS;
 
 use fixture::base::eval::*;
 use fixture::base::Payload;
 use fixture::crypto::{Digest, Hasher};
 use fixture::model::blocknode::*;
+use fixture::model::block::*;
 use fixture::model::blockgraph::*;
 use fixture::io::store::*;
 
+fn graph_block(prev_blocks: &[BlockNode], payload: &str) -> Block {
+    let mut hasher = Hasher{};
+
+    Block::new()
+        .prev_blocks(&prev_blocks.to_vec())
+        .unwrap()
+        .payload(&Payload::new(payload))
+        .unwrap()
+        .finalize(&mut hasher)
+        .unwrap()
+}
+
 #[test]
 fn test_blockgraph_meta() {
     let valid_meta = Meta::default();
@@ -445,4 +461,444 @@ fn test_blockgraph_store() {
 
     let list = res.unwrap();
     assert_eq!(list, vec![bg.clone()]);
+}
+
+#[test]
+fn test_blockgraph_engine_add_block() {
+    let mut hasher = Hasher{};
+
+    let mut engine = BlockGraphEngine::new(Store::new(), &TipRule::Longest);
+    assert!(engine.tip().is_none());
+
+    let genesis = graph_block(&[], "genesis");
+
+    let res = engine.add_block(&mut hasher, &genesis);
+    assert!(res.is_ok());
+
+    let genesis_node = genesis.node(&mut hasher).unwrap();
+    assert_eq!(res.unwrap(), vec![genesis_node.clone()]);
+
+    let res = engine.add_block(&mut hasher, &genesis);
+    assert!(res.is_err());
+
+    let other_genesis = graph_block(&[], "other genesis");
+
+    let res = engine.add_block(&mut hasher, &other_genesis);
+    assert!(res.is_err());
+
+    let block_a = graph_block(&[genesis_node.clone()], "a");
+    let node_a = engine.add_block(&mut hasher, &block_a).unwrap()[0].clone();
+
+    let block_b = graph_block(&[node_a.clone()], "b");
+    let node_b = engine.add_block(&mut hasher, &block_b).unwrap()[0].clone();
+
+    assert_eq!(engine.genesis().unwrap(), genesis_node);
+    assert_eq!(engine.graph().frontier, vec![node_b.clone()]);
+    assert_eq!(engine.graph().height, 2);
+    assert_eq!(engine.tip(), Some(node_b.clone()));
+
+    assert!(engine.lookup(&block_b.id).unwrap());
+    assert_eq!(engine.node(&block_b.id).unwrap(), node_b);
+    assert_eq!(engine.block(&block_b.id).unwrap(), block_b);
+
+    assert_eq!(engine.parents(&block_b.id).unwrap(), vec![node_a.clone()]);
+    assert_eq!(engine.children(&genesis.id).unwrap(), vec![node_a.clone()]);
+    assert_eq!(engine.ancestors(&block_b.id).unwrap(), vec![genesis_node.clone(), node_a.clone()]);
+    assert_eq!(engine.descendants(&genesis.id).unwrap(), vec![node_a.clone(), node_b.clone()]);
+    assert!(engine.descendants(&block_b.id).unwrap().is_empty());
+
+    let block_c = graph_block(&[genesis_node.clone(), node_a.clone()], "c");
+    engine.add_block(&mut hasher, &block_c).unwrap();
+
+    let node_c = engine.node(&block_c.id).unwrap();
+
+    let mut frontier = engine.graph().frontier.clone();
+    frontier.sort();
+
+    let mut expected_frontier = vec![node_b.clone(), node_c.clone()];
+    expected_frontier.sort();
+
+    assert_eq!(frontier, expected_frontier);
+
+    let mut invalid_node = node_a.clone();
+    invalid_node.block_height = 5;
+    invalid_node = invalid_node.finalize(&mut hasher).unwrap();
+
+    let invalid_block = graph_block(&[invalid_node], "invalid");
+
+    let res = engine.add_block(&mut hasher, &invalid_block);
+    assert!(res.is_err());
+
+    let mut invalid_block = graph_block(&[node_c.clone()], "invalid");
+    invalid_block.height = 1;
+    invalid_block = invalid_block.finalize(&mut hasher).unwrap();
+
+    let res = engine.add_block(&mut hasher, &invalid_block);
+    assert!(res.is_err());
+
+    let mut invalid_block = graph_block(&[node_c.clone()], "invalid");
+    invalid_block.payload = Payload::new("tampered");
+
+    let res = engine.add_block(&mut hasher, &invalid_block);
+    assert!(res.is_err());
+
+    let unknown_id = Digest::default();
+
+    assert!(!engine.lookup(&unknown_id).unwrap());
+    assert!(engine.node(&unknown_id).is_err());
+    assert!(engine.children(&unknown_id).is_err());
+    assert!(engine.ancestors(&unknown_id).is_err());
+}
+
+#[test]
+fn test_blockgraph_engine_legacy_genesis() {
+    let mut hasher = Hasher{};
+
+    let mut engine = BlockGraphEngine::new(Store::new(), &TipRule::Longest);
+
+    let mut legacy_genesis = graph_block(&[], "legacy genesis");
+    legacy_genesis.height = 1;
+    legacy_genesis = legacy_genesis.finalize(&mut hasher).unwrap();
+
+    assert!(legacy_genesis.is_legacy_genesis());
+
+    let res = engine.add_block(&mut hasher, &legacy_genesis);
+    assert!(res.is_ok());
+
+    let genesis_node = res.unwrap()[0].clone();
+    assert_eq!(genesis_node.block_height, 1);
+    assert_eq!(engine.genesis().unwrap(), genesis_node);
+
+    let genesis = graph_block(&[], "genesis");
+
+    let res = engine.add_block(&mut hasher, &genesis);
+    assert!(res.is_err());
+
+    let block_a = graph_block(&[genesis_node], "a");
+    assert_eq!(block_a.height, 2);
+
+    let res = engine.add_block(&mut hasher, &block_a);
+    assert!(res.is_ok());
+
+    let node_a = res.unwrap()[0].clone();
+    assert_eq!(engine.tip(), Some(node_a));
+}
+
+#[test]
+fn test_blockgraph_engine_orphans() {
+    let mut hasher = Hasher{};
+
+    let mut engine = BlockGraphEngine::new(Store::new(), &TipRule::Longest);
+
+    let genesis = graph_block(&[], "genesis");
+    let genesis_node = genesis.node(&mut hasher).unwrap();
+
+    let block_a = graph_block(&[genesis_node.clone()], "a");
+    let node_a = block_a.node(&mut hasher).unwrap();
+
+    let block_b = graph_block(&[node_a.clone()], "b");
+    let node_b = block_b.node(&mut hasher).unwrap();
+
+    let res = engine.add_block(&mut hasher, &block_b);
+    assert!(res.is_ok());
+    assert!(res.unwrap().is_empty());
+
+    let res = engine.add_block(&mut hasher, &block_a);
+    assert!(res.is_ok());
+    assert!(res.unwrap().is_empty());
+
+    assert!(engine.is_orphan(&block_a.id).unwrap());
+    assert!(engine.is_orphan(&block_b.id).unwrap());
+    assert!(!engine.lookup(&block_b.id).unwrap());
+    assert_eq!(engine.orphans().unwrap().len(), 2);
+    assert_eq!(engine.graph().frontier_len, 0);
+
+    let res = engine.add_block(&mut hasher, &block_b);
+    assert!(res.is_err());
+
+    let mut invalid_node = node_a.clone();
+    invalid_node.block_height = 5;
+    invalid_node = invalid_node.finalize(&mut hasher).unwrap();
+
+    let invalid_block = graph_block(&[invalid_node], "invalid");
+
+    let res = engine.add_block(&mut hasher, &invalid_block);
+    assert!(res.is_ok());
+    assert!(res.unwrap().is_empty());
+    assert_eq!(engine.orphans().unwrap().len(), 3);
+
+    let invalid_child = graph_block(&[invalid_block.node(&mut hasher).unwrap()], "invalid child");
+
+    let res = engine.add_block(&mut hasher, &invalid_child);
+    assert!(res.is_ok());
+    assert!(res.unwrap().is_empty());
+    assert_eq!(engine.orphans().unwrap().len(), 4);
+
+    let res = engine.add_block(&mut hasher, &genesis);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![genesis_node.clone(), node_a.clone(), node_b.clone()]);
+
+    assert!(!engine.is_orphan(&block_b.id).unwrap());
+    assert!(engine.orphans().unwrap().is_empty());
+    assert!(engine.block(&invalid_block.id).is_err());
+    assert!(engine.block(&invalid_child.id).is_err());
+    assert_eq!(engine.graph().frontier, vec![node_b.clone()]);
+    assert_eq!(engine.tip(), Some(node_b));
+}
+
+#[test]
+fn test_blockgraph_engine_max_orphans() {
+    let mut hasher = Hasher{};
+
+    let mut engine = BlockGraphEngine::new(Store::new(), &TipRule::Longest);
+    assert_eq!(engine.max_orphans(), BLOCKGRAPH_MAX_ORPHANS);
+
+    let max_orphans = 3;
+    engine.set_max_orphans(max_orphans);
+    assert_eq!(engine.max_orphans(), max_orphans);
+
+    let genesis = graph_block(&[], "genesis");
+    let genesis_node = genesis.node(&mut hasher).unwrap();
+
+    let old_orphan = graph_block(&[genesis_node.clone()], "old orphan");
+    let old_orphan_node = old_orphan.node(&mut hasher).unwrap();
+
+    let res = engine.add_block(&mut hasher, &old_orphan);
+    assert!(res.is_ok());
+
+    let old_orphan_child = graph_block(&[old_orphan_node], "old orphan child");
+    let old_orphan_child_node = old_orphan_child.node(&mut hasher).unwrap();
+
+    let res = engine.add_block(&mut hasher, &old_orphan_child);
+    assert!(res.is_ok());
+
+    let mut orphans = Vec::new();
+    let mut orphan_nodes = Vec::new();
+
+    for idx in 0..max_orphans {
+        let orphan = graph_block(&[old_orphan_child_node.clone()], &format!("orphan {}", idx));
+
+        orphan_nodes.push(orphan.node(&mut hasher).unwrap());
+        orphans.push(orphan);
+    }
+
+    let res = engine.add_block(&mut hasher, &orphans[0]);
+    assert!(res.is_ok());
+    assert_eq!(engine.orphans().unwrap().len() as u64, max_orphans);
+
+    let res = engine.add_block(&mut hasher, &orphans[1]);
+    assert!(res.is_ok());
+    assert_eq!(engine.orphans().unwrap().len(), 1);
+    assert!(engine.block(&old_orphan.id).is_err());
+    assert!(engine.block(&old_orphan_child.id).is_err());
+    assert!(engine.block(&orphans[0].id).is_err());
+
+    for orphan in orphans[2..].iter() {
+        let res = engine.add_block(&mut hasher, orphan);
+        assert!(res.is_ok());
+    }
+
+    assert_eq!(engine.orphans().unwrap().len() as u64, max_orphans - 1);
+
+    let res = engine.add_block(&mut hasher, &genesis);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![genesis_node.clone()]);
+
+    let res = engine.add_block(&mut hasher, &old_orphan);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().len(), 1);
+
+    let res = engine.add_block(&mut hasher, &old_orphan_child);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().len() as u64, max_orphans);
+    assert!(engine.orphans().unwrap().is_empty());
+
+    engine.set_max_orphans(0);
+
+    let orphan = graph_block(&[orphan_nodes[1].clone()], "orphan");
+
+    let res = engine.add_block(&mut hasher, &orphan);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().len(), 1);
+
+    let unknown_node = graph_block(&[], "unknown").node(&mut hasher).unwrap();
+    let orphan = graph_block(&[unknown_node], "orphan");
+
+    let res = engine.add_block(&mut hasher, &orphan);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_blockgraph_engine_tip_rules() {
+    let mut hasher = Hasher{};
+
+    let genesis = graph_block(&[], "genesis");
+    let genesis_node = genesis.node(&mut hasher).unwrap();
+
+    let block_a1 = graph_block(&[genesis_node.clone()], "a1");
+    let node_a1 = block_a1.node(&mut hasher).unwrap();
+
+    let block_a2 = graph_block(&[node_a1.clone()], "a2");
+    let node_a2 = block_a2.node(&mut hasher).unwrap();
+
+    let block_a3 = graph_block(&[node_a2.clone()], "a3");
+    let node_a3 = block_a3.node(&mut hasher).unwrap();
+
+    let block_b1 = graph_block(&[genesis_node.clone()], "b1");
+    let node_b1 = block_b1.node(&mut hasher).unwrap();
+
+    let mut b_blocks = Vec::new();
+
+    for payload in ["b2", "b3", "b4"].iter() {
+        b_blocks.push(graph_block(&[node_b1.clone()], payload));
+    }
+
+    let mut blocks = vec![genesis, block_a1, block_a2, block_a3, block_b1];
+    blocks.extend(b_blocks.clone());
+
+    let mut longest = BlockGraphEngine::new(Store::new(), &TipRule::Longest);
+    let mut heaviest = BlockGraphEngine::new(Store::new(), &TipRule::Heaviest);
+    let mut custom = BlockGraphEngine::new(Store::new(), &TipRule::Custom);
+
+    for block in blocks.iter() {
+        longest.add_block(&mut hasher, block).unwrap();
+        heaviest.add_block(&mut hasher, block).unwrap();
+        custom.add_block(&mut hasher, block).unwrap();
+    }
+
+    assert_eq!(longest.graph().frontier_len, 4);
+    assert_eq!(longest.graph().height, 3);
+    assert_eq!(longest.tip(), Some(node_a3.clone()));
+
+    let mut b_nodes: Vec<BlockNode> = b_blocks.iter()
+                                        .map(|block| block.node(&mut hasher).unwrap())
+                                        .collect();
+    b_nodes.sort_by(|a, b| a.block_id.cmp(&b.block_id));
+
+    assert_eq!(heaviest.tip(), Some(b_nodes[0].clone()));
+
+    let graph_id = heaviest.graph().id.clone();
+
+    let res = BlockGraphEngine::open(heaviest.into_store(), &TipRule::Heaviest, &graph_id);
+    assert!(res.is_ok());
+
+    let mut heaviest = res.unwrap();
+    assert_eq!(heaviest.tip(), Some(b_nodes[0].clone()));
+
+    let block_a4 = graph_block(&[node_a3.clone()], "a4");
+    let node_a4 = heaviest.add_block(&mut hasher, &block_a4).unwrap()[0].clone();
+
+    let block_a5 = graph_block(&[node_a4], "a5");
+    let node_a5 = heaviest.add_block(&mut hasher, &block_a5).unwrap()[0].clone();
+
+    assert_eq!(heaviest.tip(), Some(node_a5));
+
+    assert!(custom.tip().is_none());
+
+    let evaluator = BlockGraphTipEvaluator{};
+
+    let res = custom.select_tip_eval(&mut hasher, &(), &evaluator);
+    assert!(res.is_ok());
+
+    let tip = res.unwrap().unwrap();
+    assert_eq!(Some(tip.clone()), custom.graph().frontier.last().cloned());
+
+    let block_a4 = graph_block(&[longest.tip().unwrap()], "a4");
+    custom.add_block(&mut hasher, &block_a4).unwrap();
+
+    assert_eq!(custom.tip(), Some(tip));
+}
+
+#[test]
+fn test_blockgraph_engine_weights() {
+    let mut hasher = Hasher{};
+
+    let mut engine = BlockGraphEngine::new(Store::new(), &TipRule::Heaviest);
+
+    let genesis = graph_block(&[], "genesis");
+    let genesis_node = engine.add_block(&mut hasher, &genesis).unwrap()[0].clone();
+
+    let block_a = graph_block(&[genesis_node.clone()], "a");
+    let node_a = engine.add_block(&mut hasher, &block_a).unwrap()[0].clone();
+
+    let block_b = graph_block(&[genesis_node.clone()], "b");
+    let node_b = engine.add_block(&mut hasher, &block_b).unwrap()[0].clone();
+
+    let block_c = graph_block(&[node_a, node_b], "c");
+    let node_c = engine.add_block(&mut hasher, &block_c).unwrap()[0].clone();
+
+    let block_d = graph_block(&[node_c], "d");
+    let node_d = engine.add_block(&mut hasher, &block_d).unwrap()[0].clone();
+
+    assert_eq!(engine.tip(), Some(node_d.clone()));
+
+    let graph_id = engine.graph().id.clone();
+    let blocks = vec![genesis, block_a, block_b, block_c, block_d];
+
+    let mut store = engine.into_store();
+
+    let mut weights = Vec::new();
+
+    for block in blocks.iter() {
+        let res = BlockWeight::<Digest>::store_get(&mut store, &block.id);
+        assert!(res.is_ok());
+
+        weights.push(res.unwrap());
+    }
+
+    let expected: Vec<u64> = vec![5, 3, 3, 2, 1];
+    assert_eq!(weights.iter().map(|weight| weight.weight).collect::<Vec<u64>>(), expected);
+
+    for weight in weights.iter() {
+        weight.store_delete(&mut store).unwrap();
+    }
+
+    let res = BlockGraphEngine::open(store, &TipRule::Heaviest, &graph_id);
+    assert!(res.is_ok());
+
+    let engine = res.unwrap();
+    assert_eq!(engine.tip(), Some(node_d));
+
+    let mut store = engine.into_store();
+
+    for weight in weights.iter() {
+        let res = BlockWeight::<Digest>::store_get(&mut store, &weight.node.block_id);
+        assert!(res.is_ok());
+        assert_eq!(&res.unwrap(), weight);
+    }
+}
+
+#[test]
+fn test_blockgraph_engine_store() {
+    let mut hasher = Hasher{};
+
+    let mut engine = BlockGraphEngine::new(Store::new(), &TipRule::Longest);
+
+    let genesis = graph_block(&[], "genesis");
+    let genesis_node = engine.add_block(&mut hasher, &genesis).unwrap()[0].clone();
+
+    let block = graph_block(&[genesis_node], "block");
+    engine.add_block(&mut hasher, &block).unwrap();
+
+    let graph = engine.graph().clone();
+
+    let res = graph.verify_digest(&mut hasher);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let mut store = engine.into_store();
+
+    let res = BlockGraph::store_count(&mut store, None, None);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), 1);
+
+    let res = BlockGraphEngine::open(store, &TipRule::Longest, &graph.id);
+    assert!(res.is_ok());
+
+    let mut engine = res.unwrap();
+    assert_eq!(engine.graph(), &graph);
+    assert!(engine.lookup(&block.id).unwrap());
+
+    let res = BlockGraphEngine::open(engine.into_store(), &TipRule::Longest, &Digest::default());
+    assert!(res.is_err());
 }
\ No newline at end of file
@@ -0,0 +1,437 @@
+use mitrid_core::base::Checkable;
+use mitrid_core::base::Sizable;
+use mitrid_core::base::Serializable;
+use mitrid_core::util::Version;
+use mitrid_core::base::Meta;
+use mitrid_core::io::Storable;
+use mitrid_core::io::IndexKey;
+
+use fixture::base::eval::*;
+use fixture::base::Payload;
+use fixture::crypto::{Digest, Hasher};
+use fixture::crypto::Prover;
+use fixture::model::output::*;
+use fixture::model::transaction::*;
+use fixture::model::blocknode::*;
+use fixture::model::block::*;
+use fixture::model::blockheader::*;
+use fixture::io::store::*;
+
+fn header_block() -> Block {
+    let mut hasher = Hasher{};
+
+    let bn = BlockNode::new()
+                .meta(&Meta::default())
+                .unwrap()
+                .block_data(&Digest::default(), 0)
+                .unwrap();
+
+    let mut transactions = Vec::new();
+
+    for amount in 1..4 {
+        let output = Output::new()
+                        .amount(&amount)
+                        .unwrap()
+                        .finalize(&mut hasher)
+                        .unwrap();
+
+        let tx = Transaction::new()
+                    .outputs(&vec![output])
+                    .unwrap()
+                    .finalize(&mut hasher)
+                    .unwrap();
+
+        transactions.push(tx);
+    }
+
+    let bits = 3;
+    let mut prover = Prover::new(bits);
+
+    Block::new()
+        .prev_blocks(&vec![bn])
+        .unwrap()
+        .transactions(&transactions)
+        .unwrap()
+        .payload(&Payload::new("payload"))
+        .unwrap()
+        .prove(&mut hasher, &mut prover)
+        .unwrap()
+        .finalize(&mut hasher)
+        .unwrap()
+}
+
+#[test]
+fn test_blockheader_meta() {
+    let valid_meta = Meta::default();
+
+    let res = BlockHeader::new().meta(&valid_meta);
+    assert!(res.is_ok());
+
+    let mut invalid_version = Version::default();
+    invalid_version.buildmeta = "/\\".into();
+
+    let mut invalid_meta = Meta::default();
+    invalid_meta.version = invalid_version;
+
+    let res = BlockHeader::new().meta(&invalid_meta);
+    assert!(res.is_err())
+}
+
+#[test]
+fn test_blockheader_block() {
+    let block = header_block();
+
+    let res = BlockHeader::new()
+                .block(&block);
+    assert!(res.is_ok());
+
+    let header = res.unwrap();
+    assert_eq!(header.block_id, block.id);
+    assert_eq!(header.height, block.height);
+    assert_eq!(header.prev_blocks, block.prev_blocks);
+    assert_eq!(header.transactions_len, block.transactions_len);
+    assert_eq!(header.transactions_root, block.transactions_root);
+    assert_eq!(header.payload, block.payload);
+    assert_eq!(header.proof, block.proof);
+
+    let res = header.check();
+    assert!(res.is_ok());
+
+    let mut invalid_block = block.clone();
+    invalid_block.transactions_len += 1;
+
+    let res = BlockHeader::new()
+                .block(&invalid_block);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_blockheader_digest() {
+    let mut hasher = Hasher{};
+
+    let block = header_block();
+
+    let res = block.header(&mut hasher);
+    assert!(res.is_ok());
+
+    let header = res.unwrap();
+    assert_eq!(header.meta.timestamp, block.meta.timestamp);
+
+    let res = header.verify_digest(&mut hasher);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let res = header.check_digest(&mut hasher);
+    assert!(res.is_ok());
+
+    let res = header.digest(&mut hasher);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), header.id);
+
+    let mut tampered_header = header.clone();
+    tampered_header.height += 1;
+
+    let res = tampered_header.verify_digest(&mut hasher);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let res = tampered_header.check_digest(&mut hasher);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_blockheader_verify_block_id() {
+    let mut hasher = Hasher{};
+
+    let block = header_block();
+    let header = block.header(&mut hasher).unwrap();
+
+    let res = header.verify_block_id(&mut hasher);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let res = header.check_block_id(&mut hasher);
+    assert!(res.is_ok());
+
+    let res = block.digest(&mut hasher);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), header.block_id);
+
+    let mut tampered_header = header.clone();
+    tampered_header.transactions_root = Digest::default();
+    tampered_header = tampered_header.finalize(&mut hasher).unwrap();
+
+    let res = tampered_header.verify_block_id(&mut hasher);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let res = tampered_header.check_block_id(&mut hasher);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_blockheader_verify_proof() {
+    let mut hasher = Hasher{};
+
+    let bits = 3;
+    let mut prover = Prover::new(bits);
+
+    let block = header_block();
+    let header = block.header(&mut hasher).unwrap();
+
+    let res = header.verify_proof(&mut prover);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let res = header.check_proof(&mut prover);
+    assert!(res.is_ok());
+
+    let mut tampered_header = header.clone();
+
+    if tampered_header.proof.is_some() {
+        tampered_header.proof = None;
+    } else {
+        tampered_header.proof = Some(0);
+    }
+
+    let res = tampered_header.verify_proof(&mut prover);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let res = tampered_header.check_proof(&mut prover);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_blockheader_verify_block() {
+    let mut hasher = Hasher{};
+
+    let block = header_block();
+    let header = block.header(&mut hasher).unwrap();
+
+    let res = block.verify_header(&mut hasher, &header);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let res = block.check_header(&mut hasher, &header);
+    assert!(res.is_ok());
+
+    let mut tampered_header = header.clone();
+    tampered_header.payload = Payload::new("tampered");
+    tampered_header = tampered_header.finalize(&mut hasher).unwrap();
+
+    let res = block.verify_header(&mut hasher, &tampered_header);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let res = block.check_header(&mut hasher, &tampered_header);
+    assert!(res.is_err());
+
+    let mut tampered_block = block.clone();
+    tampered_block.transactions.swap(0, 1);
+
+    let res = tampered_block.check_header(&mut hasher, &header);
+    assert!(res.is_err());
+
+    let other_block = Block::new()
+                        .finalize(&mut hasher)
+                        .unwrap();
+
+    let res = other_block.check_header(&mut hasher, &header);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_blockheader_verify_transaction_proof() {
+    let mut hasher = Hasher{};
+
+    let block = header_block();
+    let header = block.header(&mut hasher).unwrap();
+
+    for tx in block.transactions.iter() {
+        let proof = block.transaction_proof(&mut hasher, &tx.id).unwrap();
+
+        let res = header.verify_transaction_proof(&mut hasher, &tx.id, &proof);
+        assert!(res.is_ok());
+        assert!(res.unwrap());
+
+        let res = header.verify_transaction_proof(&mut hasher, &Digest::default(), &proof);
+        assert!(res.is_ok());
+        assert!(!res.unwrap());
+
+        let mut invalid_proof = proof.clone();
+        invalid_proof.leaves_len += 1;
+
+        let res = header.verify_transaction_proof(&mut hasher, &tx.id, &invalid_proof);
+        assert!(res.is_ok());
+        assert!(!res.unwrap());
+    }
+}
+
+#[test]
+fn test_blockheader_check() {
+    let mut hasher = Hasher{};
+
+    let block = header_block();
+    let header = block.header(&mut hasher).unwrap();
+
+    let res = header.check();
+    assert!(res.is_ok());
+
+    let mut invalid_header = header.clone();
+    invalid_header.prev_blocks_len += 1;
+    invalid_header.update_size();
+
+    let res = invalid_header.check();
+    assert!(res.is_err());
+
+    let mut invalid_header = header.clone();
+    invalid_header.prev_blocks.push(header.prev_blocks[0].clone());
+    invalid_header.prev_blocks_len += 1;
+    invalid_header.update_size();
+
+    let res = invalid_header.check();
+    assert!(res.is_err());
+
+    let mut invalid_header = header.clone();
+    invalid_header.height += 1;
+
+    let res = invalid_header.check();
+    assert!(res.is_ok());
+
+    invalid_header.meta.set_size(0);
+
+    let res = invalid_header.check();
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_blockheader_eval() {
+    let mut hasher = Hasher{};
+
+    let block = header_block();
+    let mut header = block.header(&mut hasher).unwrap();
+
+    let payload = header.payload.clone();
+
+    let mut evaluator = BlockHeaderEvaluator{};
+
+    let res = header.eval(&PayloadEvalParams::Const, &evaluator);
+    assert!(res.is_ok());
+
+    let const_res = res.unwrap();
+    assert_eq!(const_res, PayloadEvalResult::Const(payload.to_string()));
+
+    let res = header.eval_mut(&PayloadEvalMutParams::ToUppercase, &mut evaluator);
+    assert!(res.is_ok());
+
+    let to_uppercase_res = res.unwrap();
+
+    let uppercase_payload = payload.to_string().to_uppercase();
+    assert_eq!(to_uppercase_res, PayloadEvalMutResult::ToUppercase(uppercase_payload.clone()));
+    assert_eq!(header.payload.to_string(), uppercase_payload)
+}
+
+#[test]
+fn test_blockheader_size() {
+    let header = BlockHeader::new();
+
+    let meta_size = header.meta.get_size();
+    let header_size = header.size();
+
+    assert_eq!(meta_size, header_size);
+}
+
+#[test]
+fn test_blockheader_json() {
+    let header_a = BlockHeader::new();
+
+    let res = header_a.to_json();
+    assert!(res.is_ok());
+
+    let header_json = res.unwrap();
+
+    let res = BlockHeader::from_json(&header_json);
+    assert!(res.is_ok());
+
+    let header_b = res.unwrap();
+
+    assert_eq!(header_a, header_b);
+}
+
+#[test]
+fn test_blockheader_bytes() {
+    let header_a = BlockHeader::new();
+
+    let res = header_a.to_bytes();
+    assert!(res.is_ok());
+
+    let header_bytes = res.unwrap();
+
+    let res = BlockHeader::from_bytes(&header_bytes);
+    assert!(res.is_ok());
+
+    let header_b = res.unwrap();
+
+    assert_eq!(header_a, header_b);
+}
+
+#[test]
+fn test_blockheader_hex() {
+    let header_a = BlockHeader::new();
+
+    let res = header_a.to_hex();
+    assert!(res.is_ok());
+
+    let header_hex = res.unwrap();
+
+    let res = BlockHeader::from_hex(&header_hex);
+    assert!(res.is_ok());
+
+    let header_b = res.unwrap();
+
+    assert_eq!(header_a, header_b);
+}
+
+#[test]
+fn test_blockheader_store() {
+    let mut hasher = Hasher{};
+
+    let block = header_block();
+    let header = block.header(&mut hasher).unwrap();
+
+    let mut store = Store::new();
+
+    let res = BlockHeader::store_lookup(&mut store, &header.id);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let res = header.store_create(&mut store);
+    assert!(res.is_ok());
+
+    let res = header.store_create(&mut store);
+    assert!(res.is_err());
+
+    let res = BlockHeader::store_get(&mut store, &header.id);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), header);
+
+    let height_key = IndexKey::from_u64("height", header.height).unwrap();
+
+    let res = BlockHeader::store_get_by_index(&mut store, &height_key);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![header.clone()]);
+
+    let block_id_key = IndexKey::from_key("block_id", &block.id).unwrap();
+
+    let res = BlockHeader::store_get_by_index(&mut store, &block_id_key);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![header.clone()]);
+
+    let res = header.store_delete(&mut store);
+    assert!(res.is_ok());
+
+    let res = BlockHeader::store_get_by_index(&mut store, &block_id_key);
+    assert!(res.is_ok());
+    assert!(res.unwrap().is_empty());
+}
\ No newline at end of file
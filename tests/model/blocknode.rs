@@ -4,6 +4,8 @@ use mitrid_core::base::Serializable;
 use mitrid_core::util::Version;
 use mitrid_core::base::Meta;
 use mitrid_core::io::Storable;
+use mitrid_core::io::IndexKey;
+use mitrid_core::model::blocknode::BLOCKNODE_BLOCK_HEIGHT_INDEX;
 
 use fixture::crypto::{Digest, Hasher};
 use fixture::model::blocknode::*;
@@ -356,4 +358,70 @@ fn test_blocknode_store() {
 
     let list = res.unwrap();
     assert_eq!(list, vec![blocknode.clone()]);
+}
+
+#[test]
+fn test_blocknode_store_index() {
+    let mut hasher = Hasher{};
+
+    let mut store = Store::new();
+
+    let heights = vec![300, 2, 256, 1, 1];
+
+    for height in heights.iter() {
+        let bn = BlockNode::new()
+                    .meta(&Meta::default())
+                    .unwrap()
+                    .block_data(&Digest::from_slice(&[*height as u8; 64]).unwrap(), *height)
+                    .unwrap()
+                    .finalize(&mut hasher)
+                    .unwrap();
+
+        bn.store_create(&mut store).unwrap();
+    }
+
+    let index_key = IndexKey::from_u64(BLOCKNODE_BLOCK_HEIGHT_INDEX, 1).unwrap();
+
+    let res = BlockNode::store_get_by_index(&mut store, &index_key);
+    assert!(res.is_ok());
+
+    let found = res.unwrap();
+    assert_eq!(found.len(), 2);
+    assert!(found.iter().all(|bn| bn.block_height == 1));
+
+    let res = BlockNode::store_list_by_index(&mut store, BLOCKNODE_BLOCK_HEIGHT_INDEX, None, None, None, 0);
+    assert!(res.is_ok());
+
+    let found_heights: Vec<u64> = res.unwrap().iter().map(|bn| bn.block_height).collect();
+    assert_eq!(found_heights, vec![1, 1, 2, 256, 300]);
+
+    let from = IndexKey::from_u64(BLOCKNODE_BLOCK_HEIGHT_INDEX, 2).unwrap().key;
+    let to = IndexKey::from_u64(BLOCKNODE_BLOCK_HEIGHT_INDEX, 300).unwrap().key;
+
+    let res = BlockNode::store_list_by_index(&mut store,
+                                             BLOCKNODE_BLOCK_HEIGHT_INDEX,
+                                             Some(from.clone()),
+                                             Some(to.clone()),
+                                             None,
+                                             0);
+    assert!(res.is_ok());
+
+    let found_heights: Vec<u64> = res.unwrap().iter().map(|bn| bn.block_height).collect();
+    assert_eq!(found_heights, vec![2, 256]);
+
+    let res = BlockNode::store_count_by_index(&mut store, BLOCKNODE_BLOCK_HEIGHT_INDEX, Some(from.clone()), None);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), 3);
+
+    let res = BlockNode::store_list_by_index(&mut store,
+                                             BLOCKNODE_BLOCK_HEIGHT_INDEX,
+                                             Some(to),
+                                             Some(from),
+                                             None,
+                                             0);
+    assert!(res.is_err());
+
+    let res = BlockNode::store_count(&mut store, None, None);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), heights.len() as u64);
 }
\ No newline at end of file
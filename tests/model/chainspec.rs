@@ -0,0 +1,207 @@
+use mitrid_core::base::Checkable;
+use mitrid_core::base::Serializable;
+use mitrid_core::base::Stage;
+use mitrid_core::util::{Version, Timestamp};
+use mitrid_core::model::TipRule;
+
+use fixture::base::Payload;
+use fixture::crypto::Hasher;
+use fixture::crypto::Proof;
+use fixture::model::output::*;
+use fixture::model::block::*;
+use fixture::model::blockgraph::*;
+use fixture::model::chainspec::*;
+use fixture::io::store::*;
+
+fn chainspec() -> ChainSpec {
+    let mut hasher = Hasher{};
+
+    let mut outputs = Vec::new();
+
+    for amount in 1..3 {
+        let output = Output::new()
+                        .amount(&amount)
+                        .unwrap()
+                        .finalize(&mut hasher)
+                        .unwrap();
+
+        outputs.push(output);
+    }
+
+    ChainSpec::new()
+        .chain("chain", &Version::default(), &Stage::Testing)
+        .unwrap()
+        .consensus(&Payload::new("consensus"))
+        .unwrap()
+        .outputs(&outputs)
+        .unwrap()
+        .genesis(&mut hasher, &Timestamp::from_secs(1), &Payload::new("genesis"), &Proof::default())
+        .unwrap()
+}
+
+#[test]
+fn test_chainspec_chain() {
+    let res = ChainSpec::new().chain("chain", &Version::default(), &Stage::Production);
+    assert!(res.is_ok());
+
+    let mut invalid_version = Version::default();
+    invalid_version.buildmeta = "/\\".into();
+
+    let res = ChainSpec::new().chain("chain", &invalid_version, &Stage::Production);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_chainspec_genesis() {
+    let mut hasher = Hasher{};
+
+    let spec = chainspec();
+
+    assert!(spec.genesis.is_genesis());
+    assert_eq!(spec.genesis.meta.chain, spec.chain);
+    assert_eq!(spec.genesis.meta.stage, Stage::Testing);
+    assert_eq!(spec.genesis.transactions_len, 1);
+    assert_eq!(spec.genesis.transactions[0].outputs, spec.outputs);
+
+    let same_genesis = spec.build_genesis(&mut hasher,
+                                          &Timestamp::from_secs(1),
+                                          &Payload::new("genesis"),
+                                          &Proof::default())
+                            .unwrap();
+    assert_eq!(same_genesis, spec.genesis);
+    assert_eq!(same_genesis.id, spec.genesis.id);
+
+    let res = spec.genesis.verify_digest(&mut hasher);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let res = spec.verify_genesis(&mut hasher);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let res = spec.check_genesis(&mut hasher);
+    assert!(res.is_ok());
+
+    let other_genesis = spec.build_genesis(&mut hasher,
+                                           &Timestamp::from_secs(2),
+                                           &Payload::new("genesis"),
+                                           &Proof::default())
+                            .unwrap();
+    assert_ne!(other_genesis.id, spec.genesis.id);
+
+    let mut invalid_spec = spec.clone();
+    invalid_spec.outputs.pop();
+    invalid_spec.outputs_len -= 1;
+
+    let res = invalid_spec.check();
+    assert!(res.is_ok());
+
+    let res = invalid_spec.check_genesis(&mut hasher);
+    assert!(res.is_err());
+
+    let mut invalid_spec = spec.clone();
+    invalid_spec.chain = "other chain".into();
+
+    let res = invalid_spec.check();
+    assert!(res.is_err());
+
+    let mut invalid_spec = spec.clone();
+    invalid_spec.genesis = Block::new()
+                            .prev_blocks(&spec.genesis.prev_blocks)
+                            .unwrap()
+                            .finalize(&mut hasher)
+                            .unwrap();
+
+    let res = invalid_spec.check();
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_chainspec_json() {
+    let mut hasher = Hasher{};
+
+    let spec_a = chainspec();
+
+    let res = spec_a.to_json();
+    assert!(res.is_ok());
+
+    let spec_json = res.unwrap();
+
+    let res = ChainSpec::from_json(&spec_json);
+    assert!(res.is_ok());
+
+    let spec_b = res.unwrap();
+
+    assert_eq!(spec_a, spec_b);
+
+    let res = spec_b.check_genesis(&mut hasher);
+    assert!(res.is_ok());
+}
+
+#[test]
+fn test_chainspec_bytes() {
+    let mut hasher = Hasher{};
+
+    let spec_a = chainspec();
+
+    let res = spec_a.to_bytes();
+    assert!(res.is_ok());
+
+    let spec_bytes = res.unwrap();
+
+    let res = ChainSpec::from_bytes(&spec_bytes);
+    assert!(res.is_ok());
+
+    let spec_b = res.unwrap();
+
+    assert_eq!(spec_a, spec_b);
+
+    let res = spec_b.check_genesis(&mut hasher);
+    assert!(res.is_ok());
+}
+
+#[test]
+fn test_chainspec_graph() {
+    let mut hasher = Hasher{};
+
+    let spec = chainspec();
+
+    let mut engine = BlockGraphEngine::new(Store::new(), &TipRule::Longest);
+
+    let res = spec.check_graph(&mut engine);
+    assert!(res.is_err());
+
+    let genesis_node = engine.add_block(&mut hasher, &spec.genesis).unwrap()[0].clone();
+
+    let res = spec.check_graph(&mut engine);
+    assert!(res.is_ok());
+
+    let block = Block::new()
+                    .prev_blocks(&vec![genesis_node])
+                    .unwrap()
+                    .finalize(&mut hasher)
+                    .unwrap();
+
+    engine.add_block(&mut hasher, &block).unwrap();
+
+    let res = spec.verify_graph(&mut engine);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let mut other_engine = BlockGraphEngine::new(Store::new(), &TipRule::Longest);
+
+    let other_genesis = spec.build_genesis(&mut hasher,
+                                           &Timestamp::from_secs(2),
+                                           &Payload::new("genesis"),
+                                           &Proof::default())
+                            .unwrap();
+
+    other_engine.add_block(&mut hasher, &other_genesis).unwrap();
+
+    let res = spec.verify_graph(&mut other_engine);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let res = spec.check_graph(&mut other_engine);
+    assert!(res.is_err());
+}
\ No newline at end of file
@@ -4,6 +4,11 @@ use mitrid_core::base::Checkable;
 use mitrid_core::base::Sizable;
 use mitrid_core::base::Serializable;
 use mitrid_core::io::Storable;
+use mitrid_core::io::Store as BasicStore;
+use mitrid_core::io::{Permission, Batch};
+use mitrid_core::io::Direction;
+use mitrid_core::io::IndexKey;
+use mitrid_core::model::coin::COIN_TX_ID_INDEX;
 
 use fixture::crypto::{Digest, Hasher};
 use fixture::model::Amount;
@@ -370,4 +375,198 @@ fn test_coin_store() {
 
     let list = res.unwrap();
     assert_eq!(list, vec![coin.clone()]);
+}
+
+#[test]
+fn test_coin_store_iter() {
+    let mut hasher = Hasher{};
+
+    let mut store = Store::new();
+
+    let mut coins = Vec::new();
+
+    for out_idx in 0..5 {
+        let coin = Coin::new()
+                        .meta(&Meta::default())
+                        .unwrap()
+                        .output_data(&Digest::default(), out_idx, &Amount::default())
+                        .unwrap()
+                        .finalize(&mut hasher)
+                        .unwrap();
+
+        coin.store_create(&mut store).unwrap();
+
+        coins.push(coin);
+    }
+
+    let res = Coin::store_iter(&mut store, Some(coins[0].id.clone()), Some(coins[0].id.clone()), Direction::Forward);
+    assert!(res.is_err());
+
+    let res = Coin::store_iter(&mut store, None, None, Direction::Forward);
+    assert!(res.is_ok());
+
+    let res: Result<Vec<Coin>, String> = res.unwrap().page_size(2).unwrap().collect();
+    assert!(res.is_ok());
+
+    let forward = res.unwrap();
+    assert_eq!(forward.len(), coins.len());
+
+    let mut expected = forward.clone();
+    expected.sort();
+    coins.sort();
+    assert_eq!(expected, coins);
+
+    let res = Coin::store_iter(&mut store, None, None, Direction::Reverse);
+    assert!(res.is_ok());
+
+    let res: Result<Vec<Coin>, String> = res.unwrap().collect();
+    assert!(res.is_ok());
+
+    let mut reverse = res.unwrap();
+    reverse.reverse();
+    assert_eq!(reverse, forward);
+}
+
+#[test]
+fn test_coin_store_index() {
+    let mut hasher = Hasher{};
+
+    let mut store = Store::new();
+
+    let tx_id_a = Digest::from_slice(&[1; 64]).unwrap();
+    let tx_id_b = Digest::from_slice(&[2; 64]).unwrap();
+
+    let mut coins = Vec::new();
+
+    for out_idx in 0..3 {
+        let coin = Coin::new()
+                        .meta(&Meta::default())
+                        .unwrap()
+                        .output_data(&tx_id_a, out_idx, &Amount::default())
+                        .unwrap()
+                        .finalize(&mut hasher)
+                        .unwrap();
+
+        coin.store_create(&mut store).unwrap();
+
+        coins.push(coin);
+    }
+
+    let index_key_a = IndexKey::from_key(COIN_TX_ID_INDEX, &tx_id_a).unwrap();
+    let index_key_b = IndexKey::from_key(COIN_TX_ID_INDEX, &tx_id_b).unwrap();
+
+    let res = Coin::store_get_by_index(&mut store, &index_key_a);
+    assert!(res.is_ok());
+
+    let mut found = res.unwrap();
+    found.sort();
+    coins.sort();
+    assert_eq!(found, coins);
+
+    let res = Coin::store_get_by_index(&mut store, &index_key_b);
+    assert!(res.is_ok());
+    assert!(res.unwrap().is_empty());
+
+    let res = Coin::store_count(&mut store, None, None);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), coins.len() as u64);
+
+    let mut moved_coin = coins[0].clone();
+    moved_coin.tx_id = tx_id_b.clone();
+
+    let res = moved_coin.store_update(&mut store);
+    assert!(res.is_ok());
+
+    let res = Coin::store_get_by_index(&mut store, &index_key_a);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().len(), coins.len() - 1);
+
+    let res = Coin::store_get_by_index(&mut store, &index_key_b);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![moved_coin.clone()]);
+
+    let res = Coin::store_count_by_index(&mut store, COIN_TX_ID_INDEX, None, None);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), coins.len() as u64);
+
+    let res = moved_coin.store_delete(&mut store);
+    assert!(res.is_ok());
+
+    let res = Coin::store_get_by_index(&mut store, &index_key_b);
+    assert!(res.is_ok());
+    assert!(res.unwrap().is_empty());
+
+    let res = Coin::store_count_by_index(&mut store, COIN_TX_ID_INDEX, None, None);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), coins.len() as u64 - 1);
+}
+
+#[test]
+fn test_coin_store_batch_index() {
+    let mut hasher = Hasher{};
+
+    let mut store = Store::new();
+
+    let session = store.session(&Permission::Write).unwrap();
+
+    let tx_id_a = Digest::from_slice(&[1; 64]).unwrap();
+    let tx_id_b = Digest::from_slice(&[2; 64]).unwrap();
+
+    let coin = Coin::new()
+                .meta(&Meta::default())
+                .unwrap()
+                .output_data(&tx_id_a, 0, &Amount::default())
+                .unwrap()
+                .finalize(&mut hasher)
+                .unwrap();
+
+    let mut moved_coin = coin.clone();
+    moved_coin.tx_id = tx_id_b.clone();
+
+    let index_key_a = IndexKey::from_key(COIN_TX_ID_INDEX, &tx_id_a).unwrap();
+    let index_key_b = IndexKey::from_key(COIN_TX_ID_INDEX, &tx_id_b).unwrap();
+
+    let mut batch = Batch::new();
+
+    let res = moved_coin.store_batch_update(&mut store, &mut batch);
+    assert!(res.is_err());
+
+    let res = coin.store_batch_create(&mut store, &mut batch);
+    assert!(res.is_ok());
+
+    let res = moved_coin.store_batch_update(&mut store, &mut batch);
+    assert!(res.is_ok());
+
+    let res = store.commit(&session, &batch);
+    assert!(res.is_ok());
+
+    let res = Coin::store_get_by_index(&mut store, &index_key_a);
+    assert!(res.is_ok());
+    assert!(res.unwrap().is_empty());
+
+    let res = Coin::store_get_by_index(&mut store, &index_key_b);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![moved_coin.clone()]);
+
+    batch.clear();
+
+    let res = coin.store_batch_update(&mut store, &mut batch);
+    assert!(res.is_ok());
+
+    let res = coin.store_batch_delete(&mut store, &mut batch);
+    assert!(res.is_ok());
+
+    let res = coin.store_batch_delete(&mut store, &mut batch);
+    assert!(res.is_err());
+
+    let res = store.commit(&session, &batch);
+    assert!(res.is_ok());
+
+    let res = Coin::store_lookup(&mut store, &coin.id);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let res = Coin::store_count_by_index(&mut store, COIN_TX_ID_INDEX, None, None);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), 0);
 }
\ No newline at end of file
@@ -11,6 +11,9 @@ use fixture::crypto::{Digest, Hasher};
 use fixture::model::Amount;
 use fixture::model::coin::*;
 use fixture::model::input::*;
+use mitrid_core::crypto::Sign;
+use fixture::crypto::sign_ed25519::Ed25519;
+use mitrid_core::model::SighashMode;
 use fixture::io::store::*;
 
 #[test]
@@ -239,6 +242,66 @@ fn test_input_eval() {
     assert_eq!(input.payload.to_string(), uppsercase_payload)
 }
 
+#[test]
+fn test_input_sign() {
+    let mut signer = Ed25519{};
+    let (pk, sk) = signer.generate_keys(None).unwrap();
+    let (other_pk, _) = signer.generate_keys(None).unwrap();
+
+    let msg = b"sighash";
+
+    let input = Input::new();
+    assert!(!input.is_signed());
+
+    let res = input.verify_signature(msg, &mut signer, &pk);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let res = Input::new().sign(msg, &mut signer, &sk);
+    assert!(res.is_ok());
+
+    let input = res.unwrap();
+    assert!(input.is_signed());
+
+    let res = input.verify_signature(msg, &mut signer, &pk);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let res = input.check_signature(msg, &mut signer, &pk);
+    assert!(res.is_ok());
+
+    let res = input.check_signature(b"other", &mut signer, &pk);
+    assert!(res.is_err());
+
+    let res = input.check_signature(msg, &mut signer, &other_pk);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_input_sighash_mode() {
+    let mut signer = Ed25519{};
+    let (_, sk) = signer.generate_keys(None).unwrap();
+
+    let msg = b"sighash";
+
+    let input = Input::new();
+    assert_eq!(input.witness.mode, SighashMode::All);
+
+    let res = input.sighash_mode(&SighashMode::AnyoneCanPay);
+    assert!(res.is_ok());
+
+    let input = res.unwrap();
+    assert_eq!(input.witness.mode, SighashMode::AnyoneCanPay);
+
+    let input = input.sign(msg, &mut signer, &sk).unwrap();
+
+    let res = input.clone().sighash_mode(&SighashMode::AnyoneCanPay);
+    assert!(res.is_ok());
+
+    let res = input.sighash_mode(&SighashMode::All);
+    assert!(res.is_err());
+}
+
 #[test]
 fn test_input_size() {
     let input = Input::new();
@@ -0,0 +1,202 @@
+use mitrid_core::base::Checkable;
+use mitrid_core::base::Sizable;
+use mitrid_core::base::VariableSize;
+use mitrid_core::base::Serializable;
+use mitrid_core::crypto::{Hash, Sign};
+use mitrid_core::util::Timestamp;
+use mitrid_core::model::{Lock, Witness};
+use mitrid_core::model::lock::{LOCK_MAX_DEPTH, LOCK_MAX_SIZE};
+
+use fixture::crypto::Hasher;
+use fixture::crypto::sign_ed25519::{Ed25519, SecretKey};
+
+fn sign_witness(msg: &[u8], signer: &mut Ed25519, sks: &[SecretKey]) -> Witness {
+    let mut witness = Witness::new();
+
+    for sk in sks.iter() {
+        let signature = signer.sign(msg, sk).unwrap();
+        witness.signatures.push(signature.to_bytes().unwrap());
+    }
+
+    witness
+}
+
+#[test]
+fn test_lock_check() {
+    let mut signer = Ed25519{};
+    let (pk_a, _) = signer.generate_keys(None).unwrap();
+    let (pk_b, _) = signer.generate_keys(None).unwrap();
+
+    let res = Lock::key(&pk_a);
+    assert!(res.is_ok());
+
+    let res = Lock::multisig(2, &[pk_a.clone(), pk_b.clone()]);
+    assert!(res.is_ok());
+
+    let res = Lock::multisig(0, &[pk_a.clone(), pk_b.clone()]);
+    assert!(res.is_err());
+
+    let res = Lock::multisig(3, &[pk_a.clone(), pk_b.clone()]);
+    assert!(res.is_err());
+
+    let res = Lock::multisig(1, &[pk_a.clone(), pk_a.clone()]);
+    assert!(res.is_err());
+
+    let res = Lock::Key(vec![]).check();
+    assert!(res.is_err());
+
+    let res = Lock::All(vec![]).check();
+    assert!(res.is_err());
+
+    let mut lock = Lock::Unlocked;
+
+    for _ in 0..LOCK_MAX_DEPTH - 1 {
+        lock = Lock::Any(vec![lock]);
+    }
+
+    assert_eq!(lock.depth(), LOCK_MAX_DEPTH);
+
+    let res = lock.check();
+    assert!(res.is_ok());
+
+    let lock = Lock::Any(vec![lock]);
+
+    let res = lock.check();
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_lock_size() {
+    let lock = Lock::Preimage(vec![0; 64]);
+    assert_eq!(lock.size(), 1 + 64);
+
+    let res = lock.check_size();
+    assert!(res.is_ok());
+
+    let lock = Lock::Preimage(vec![0; LOCK_MAX_SIZE as usize]);
+
+    let res = lock.check_size();
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_lock_verify_keys() {
+    let mut hasher = Hasher{};
+    let mut signer = Ed25519{};
+
+    let (pk_a, sk_a) = signer.generate_keys(None).unwrap();
+    let (pk_b, sk_b) = signer.generate_keys(None).unwrap();
+    let (pk_c, _) = signer.generate_keys(None).unwrap();
+
+    let msg = b"sighash";
+    let time = Timestamp::now().unwrap();
+
+    let lock = Lock::key(&pk_a).unwrap();
+
+    let witness = sign_witness(msg, &mut signer, &[sk_a.clone()]);
+    let res = lock.verify(&witness, msg, &time, &mut hasher, &mut signer);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let witness = sign_witness(msg, &mut signer, &[sk_b.clone()]);
+    let res = lock.check_witness(&witness, msg, &time, &mut hasher, &mut signer);
+    assert!(res.is_err());
+
+    let lock = Lock::multisig(2, &[pk_a, pk_b, pk_c]).unwrap();
+
+    let witness = sign_witness(msg, &mut signer, &[sk_a.clone()]);
+    let res = lock.verify(&witness, msg, &time, &mut hasher, &mut signer);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let witness = sign_witness(msg, &mut signer, &[sk_a.clone(), sk_a.clone()]);
+    let res = lock.verify(&witness, msg, &time, &mut hasher, &mut signer);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let witness = sign_witness(msg, &mut signer, &[sk_b, sk_a]);
+    let res = lock.verify(&witness, msg, &time, &mut hasher, &mut signer);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let res = lock.verify(&witness, b"other", &time, &mut hasher, &mut signer);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+}
+
+#[test]
+fn test_lock_verify_preimage_timelock() {
+    let mut hasher = Hasher{};
+    let mut signer = Ed25519{};
+
+    let msg = b"sighash";
+    let preimage = b"secret".to_vec();
+    let digest = hasher.digest(&preimage).unwrap();
+
+    let now = Timestamp::now().unwrap();
+    let later = Timestamp::from_u64(now.as_u64() + 1000);
+
+    let preimage_lock = Lock::preimage(&digest).unwrap();
+    let after_lock = Lock::After(later);
+    let before_lock = Lock::Before(later);
+
+    let mut witness = Witness::new();
+
+    let res = preimage_lock.verify(&witness, msg, &now, &mut hasher, &mut signer);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    witness.preimages.push(preimage);
+
+    let res = preimage_lock.verify(&witness, msg, &now, &mut hasher, &mut signer);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let res = after_lock.verify(&witness, msg, &now, &mut hasher, &mut signer);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let res = after_lock.verify(&witness, msg, &later, &mut hasher, &mut signer);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let res = before_lock.verify(&witness, msg, &later, &mut hasher, &mut signer);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let all_lock = Lock::All(vec![preimage_lock.clone(), after_lock.clone()]);
+
+    let res = all_lock.verify(&witness, msg, &now, &mut hasher, &mut signer);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let res = all_lock.verify(&witness, msg, &later, &mut hasher, &mut signer);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let any_lock = Lock::Any(vec![preimage_lock, after_lock]);
+
+    let res = any_lock.verify(&Witness::new(), msg, &now, &mut hasher, &mut signer);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let res = any_lock.verify(&witness, msg, &now, &mut hasher, &mut signer);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+}
+
+#[test]
+fn test_lock_bytes() {
+    let mut signer = Ed25519{};
+    let (pk, _) = signer.generate_keys(None).unwrap();
+
+    let lock = Lock::Any(vec![Lock::key(&pk).unwrap(), Lock::After(Timestamp::default())]);
+
+    let res = lock.to_bytes();
+    assert!(res.is_ok());
+
+    let res = Lock::from_bytes(&res.unwrap());
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), lock);
+
+}
\ No newline at end of file
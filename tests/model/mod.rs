@@ -1,8 +1,13 @@
 mod coin;
 mod input;
 mod output;
+mod lock;
 mod transaction;
+mod utxo;
 mod blocknode;
 mod block;
+mod blockheader;
 mod blockgraph;
+mod reorg;
+mod chainspec;
 mod wallet;
\ No newline at end of file
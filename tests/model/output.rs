@@ -10,6 +10,9 @@ use fixture::base::Payload;
 use fixture::crypto::{Digest, Hasher};
 use fixture::model::Amount;
 use fixture::model::output::*;
+use mitrid_core::crypto::Sign;
+use fixture::crypto::sign_ed25519::{Ed25519, PublicKey};
+use mitrid_core::model::Lock;
 use fixture::io::store::*;
 
 #[test]
@@ -170,6 +173,32 @@ fn test_output_eval() {
     assert_eq!(output.payload.to_string(), uppsercase_payload)
 }
 
+#[test]
+fn test_output_lock() {
+    let mut signer = Ed25519{};
+    let (pk, _) = signer.generate_keys(None).unwrap();
+
+    let output = Output::new();
+    assert!(!output.is_locked());
+
+    let res = output.owner::<PublicKey>();
+    assert!(res.is_ok());
+    assert!(res.unwrap().is_none());
+
+    let res = Output::new().lock(&Lock::key(&pk).unwrap());
+    assert!(res.is_ok());
+
+    let output = res.unwrap();
+    assert!(output.is_locked());
+
+    let res = output.owner::<PublicKey>();
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), Some(pk));
+
+    let res = output.check();
+    assert!(res.is_ok());
+}
+
 #[test]
 fn test_output_size() {
     let output = Output::new();
@@ -0,0 +1,317 @@
+use mitrid_core::base::Checkable;
+use mitrid_core::base::Serializable;
+use mitrid_core::model::{Lock, TipRule};
+
+use fixture::base::Payload;
+use fixture::crypto::Hasher;
+use fixture::model::Amount;
+use fixture::model::coin::*;
+use fixture::model::input::*;
+use fixture::model::output::*;
+use fixture::model::transaction::*;
+use fixture::model::blocknode::*;
+use fixture::model::block::*;
+use fixture::model::blockgraph::*;
+use fixture::model::utxo::*;
+use fixture::model::reorg::*;
+use fixture::io::store::*;
+
+fn reorg_transaction(coins: &[Coin], amounts: &[Amount]) -> Transaction {
+    let mut hasher = Hasher{};
+
+    let mut inputs = Vec::new();
+
+    for coin in coins.iter() {
+        let input = Input::new()
+                        .coin(coin)
+                        .unwrap()
+                        .finalize(&mut hasher)
+                        .unwrap();
+
+        inputs.push(input);
+    }
+
+    let mut outputs = Vec::new();
+
+    for amount in amounts.iter() {
+        let output = Output::new()
+                        .amount(amount)
+                        .unwrap()
+                        .finalize(&mut hasher)
+                        .unwrap();
+
+        outputs.push(output);
+    }
+
+    Transaction::new()
+        .inputs(&inputs)
+        .unwrap()
+        .outputs(&outputs)
+        .unwrap()
+        .finalize(&mut hasher)
+        .unwrap()
+}
+
+fn reorg_block(prev_blocks: &[BlockNode], transactions: &[Transaction], payload: &str) -> Block {
+    let mut hasher = Hasher{};
+
+    Block::new()
+        .prev_blocks(&prev_blocks.to_vec())
+        .unwrap()
+        .transactions(&transactions.to_vec())
+        .unwrap()
+        .payload(&Payload::new(payload))
+        .unwrap()
+        .finalize(&mut hasher)
+        .unwrap()
+}
+
+#[test]
+fn test_reorg_new() {
+    let mut hasher = Hasher{};
+
+    let genesis = reorg_block(&[], &[], "genesis");
+    let genesis_node = genesis.node(&mut hasher).unwrap();
+
+    let node_a1 = reorg_block(&[genesis_node.clone()], &[], "a1").node(&mut hasher).unwrap();
+    let node_a2 = reorg_block(&[node_a1.clone()], &[], "a2").node(&mut hasher).unwrap();
+    let node_b1 = reorg_block(&[genesis_node.clone()], &[], "b1").node(&mut hasher).unwrap();
+
+    let from = vec![node_a2.clone(), genesis_node.clone(), node_a1.clone()];
+    let to = vec![genesis_node.clone(), node_b1.clone()];
+
+    let res = Reorg::new(&from, &to);
+    assert!(res.is_ok());
+
+    let reorg = res.unwrap();
+    assert_eq!(reorg.fork, Some(genesis_node.clone()));
+    assert_eq!(reorg.undo, vec![node_a2.clone(), node_a1.clone()]);
+    assert_eq!(reorg.redo, vec![node_b1.clone()]);
+    assert!(!reorg.is_empty());
+
+    let res = reorg.check();
+    assert!(res.is_ok());
+
+    let res = Reorg::new(&from, &from);
+    assert!(res.is_ok());
+
+    let reorg = res.unwrap();
+    assert_eq!(reorg.fork, Some(node_a2.clone()));
+    assert!(reorg.is_empty());
+
+    let res = Reorg::new(&[], &to);
+    assert!(res.is_ok());
+
+    let reorg = res.unwrap();
+    assert_eq!(reorg.fork, None);
+    assert!(reorg.undo.is_empty());
+    assert_eq!(reorg.redo, vec![genesis_node.clone(), node_b1.clone()]);
+
+    let mut invalid_reorg = reorg.clone();
+    invalid_reorg.undo.push(node_b1.clone());
+
+    let res = invalid_reorg.check();
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_reorg_bytes() {
+    let reorg_a = Reorg::default();
+
+    let res = reorg_a.to_bytes();
+    assert!(res.is_ok());
+
+    let reorg_bytes = res.unwrap();
+
+    let res = Reorg::from_bytes(&reorg_bytes);
+    assert!(res.is_ok());
+
+    let reorg_b = res.unwrap();
+
+    assert_eq!(reorg_a, reorg_b);
+}
+
+#[test]
+fn test_reorg_transition() {
+    let mut hasher = Hasher{};
+
+    let mut engine = BlockGraphEngine::new(Store::new(), &TipRule::Longest);
+    let mut utxos = UtxoSet::new(Store::new());
+
+    let coinbase = reorg_transaction(&[], &[10]);
+    let coinbase_coins = coinbase.output_coins(&mut hasher).unwrap();
+
+    let genesis = reorg_block(&[], &[coinbase.clone()], "genesis");
+    let genesis_node = engine.add_block(&mut hasher, &genesis).unwrap()[0].clone();
+
+    let res = engine.transition(&mut hasher, None, &mut utxos);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().redo, vec![genesis_node.clone()]);
+
+    assert!(utxos.is_unspent(&coinbase_coins[0]).unwrap());
+
+    let spend_a = reorg_transaction(&coinbase_coins, &[7, 3]);
+    let spend_a_coins = spend_a.output_coins(&mut hasher).unwrap();
+
+    let block_a1 = reorg_block(&[genesis_node.clone()], &[spend_a.clone()], "a1");
+    let node_a1 = engine.add_block(&mut hasher, &block_a1).unwrap()[0].clone();
+
+    let res = engine.transition(&mut hasher, Some(&genesis_node), &mut utxos);
+    assert!(res.is_ok());
+
+    assert!(!utxos.is_unspent(&coinbase_coins[0]).unwrap());
+    assert!(utxos.is_unspent(&spend_a_coins[0]).unwrap());
+
+    let spend_b = reorg_transaction(&coinbase_coins, &[10]);
+    let spend_b_coins = spend_b.output_coins(&mut hasher).unwrap();
+
+    let block_b1 = reorg_block(&[genesis_node.clone()], &[spend_b.clone()], "b1");
+    let node_b1 = block_b1.node(&mut hasher).unwrap();
+
+    let block_b2 = reorg_block(&[node_b1.clone()], &[], "b2");
+    let node_b2 = block_b2.node(&mut hasher).unwrap();
+
+    let tip = engine.tip();
+
+    engine.add_block(&mut hasher, &block_b1).unwrap();
+    engine.add_block(&mut hasher, &block_b2).unwrap();
+
+    assert_eq!(engine.tip(), Some(node_b2.clone()));
+
+    let res = engine.reorg(Some(&node_a1), Some(&node_b2));
+    assert!(res.is_ok());
+
+    let reorg = res.unwrap();
+    assert_eq!(reorg.fork, Some(genesis_node.clone()));
+    assert_eq!(reorg.undo, vec![node_a1.clone()]);
+    assert_eq!(reorg.redo, vec![node_b1.clone(), node_b2.clone()]);
+
+    let res = engine.transition(&mut hasher, tip.as_ref(), &mut utxos);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), reorg);
+
+    assert!(!utxos.lookup(&spend_a_coins[0].id).unwrap());
+    assert!(utxos.is_unspent(&spend_b_coins[0]).unwrap());
+    assert_eq!(utxos.get(&coinbase_coins[0].id).unwrap().spent_by, Some(spend_b.id.clone()));
+    assert_eq!(utxos.total_balance().unwrap(), 10);
+
+    let double_spend = reorg_transaction(&spend_a_coins, &[10]);
+
+    let block_c1 = reorg_block(&[node_a1.clone()], &[], "c1");
+    let node_c1 = block_c1.node(&mut hasher).unwrap();
+
+    let block_c2 = reorg_block(&[node_c1.clone()], &[double_spend.clone()], "c2");
+    let node_c2 = block_c2.node(&mut hasher).unwrap();
+
+    let block_c3 = reorg_block(&[node_c2.clone()], &[double_spend], "c3");
+
+    let tip = engine.tip();
+
+    engine.add_block(&mut hasher, &block_c1).unwrap();
+    engine.add_block(&mut hasher, &block_c2).unwrap();
+    engine.add_block(&mut hasher, &block_c3).unwrap();
+
+    let res = engine.transition(&mut hasher, tip.as_ref(), &mut utxos);
+    assert!(res.is_err());
+
+    assert!(!utxos.lookup(&spend_a_coins[0].id).unwrap());
+    assert!(utxos.is_unspent(&spend_b_coins[0]).unwrap());
+    assert_eq!(utxos.total_balance().unwrap(), 10);
+
+    let unknown_node = reorg_block(&[], &[], "unknown").node(&mut hasher).unwrap();
+
+    let res = engine.reorg(Some(&unknown_node), Some(&node_b2));
+    assert!(res.is_err());
+
+    let res = utxos.balance(&Lock::Unlocked);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), 10);
+}
+
+#[test]
+fn test_reorg_merge() {
+    let mut hasher = Hasher{};
+
+    let mut engine = BlockGraphEngine::new(Store::new(), &TipRule::Longest);
+
+    let genesis = reorg_block(&[], &[], "genesis");
+    let genesis_node = engine.add_block(&mut hasher, &genesis).unwrap()[0].clone();
+
+    let block_a1 = reorg_block(&[genesis_node.clone()], &[], "a1");
+    let node_a1 = engine.add_block(&mut hasher, &block_a1).unwrap()[0].clone();
+
+    let block_b1 = reorg_block(&[genesis_node.clone()], &[], "b1");
+    let node_b1 = engine.add_block(&mut hasher, &block_b1).unwrap()[0].clone();
+
+    let block_m = reorg_block(&[node_a1.clone(), node_b1.clone()], &[], "m");
+    let node_m = engine.add_block(&mut hasher, &block_m).unwrap()[0].clone();
+
+    let res = engine.reorg(Some(&node_m), Some(&node_b1));
+    assert!(res.is_ok());
+
+    let reorg = res.unwrap();
+    assert_eq!(reorg.fork, Some(node_b1.clone()));
+    assert_eq!(reorg.undo, vec![node_m.clone(), node_a1.clone()]);
+    assert!(reorg.redo.is_empty());
+
+    let res = engine.reorg(Some(&node_a1), Some(&node_m));
+    assert!(res.is_ok());
+
+    let reorg = res.unwrap();
+    assert_eq!(reorg.fork, Some(node_a1.clone()));
+    assert!(reorg.undo.is_empty());
+    assert_eq!(reorg.redo, vec![node_b1.clone(), node_m.clone()]);
+
+    let res = engine.reorg(Some(&node_m), Some(&node_m));
+    assert!(res.is_ok());
+
+    let reorg = res.unwrap();
+    assert_eq!(reorg.fork, Some(node_m.clone()));
+    assert!(reorg.is_empty());
+
+    let res = engine.reorg(None, Some(&node_m));
+    assert!(res.is_ok());
+
+    let reorg = res.unwrap();
+    assert!(reorg.fork.is_none());
+    assert_eq!(reorg.redo.len(), 4);
+    assert_eq!(reorg.redo[0], genesis_node);
+}
+
+#[test]
+fn test_reorg_rollback() {
+    let mut hasher = Hasher{};
+
+    let mut engine = BlockGraphEngine::new(Store::new(), &TipRule::Longest);
+
+    let genesis = reorg_block(&[], &[], "genesis");
+    let genesis_node = engine.add_block(&mut hasher, &genesis).unwrap()[0].clone();
+
+    let block_a1 = reorg_block(&[genesis_node.clone()], &[], "a1");
+    let node_a1 = engine.add_block(&mut hasher, &block_a1).unwrap()[0].clone();
+
+    let mut state = BlocksState::default();
+
+    engine.transition(&mut hasher, None, &mut state).unwrap();
+    assert_eq!(state.applied, vec![genesis.id.clone(), block_a1.id.clone()]);
+
+    let block_b1 = reorg_block(&[genesis_node.clone()], &[], "b1");
+    let node_b1 = engine.add_block(&mut hasher, &block_b1).unwrap()[0].clone();
+
+    let block_b2 = reorg_block(&[node_b1], &[], "b2");
+    engine.add_block(&mut hasher, &block_b2).unwrap();
+
+    let mut rolled_back_state = state.clone();
+    rolled_back_state.invalid = vec![block_b2.id.clone()];
+
+    let res = engine.transition(&mut hasher, Some(&node_a1), &mut rolled_back_state);
+    assert_eq!(res, Err(String::from("invalid block")));
+    assert_eq!(rolled_back_state.applied, state.applied);
+
+    let mut failed_state = state.clone();
+    failed_state.invalid = vec![block_b2.id.clone(), block_a1.id.clone()];
+
+    let res = engine.transition(&mut hasher, Some(&node_a1), &mut failed_state);
+    assert_eq!(res, Err(String::from("invalid block: rollback failed: invalid block")));
+    assert_eq!(failed_state.applied, vec![genesis.id.clone()]);
+}
\ No newline at end of file
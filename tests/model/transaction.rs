@@ -13,6 +13,12 @@ use fixture::model::coin::*;
 use fixture::model::input::*;
 use fixture::model::output::*;
 use fixture::model::transaction::*;
+use mitrid_core::model::FeePolicy;
+use mitrid_core::model::SighashMode;
+use mitrid_core::crypto::Sign;
+use fixture::crypto::sign_ed25519::Ed25519;
+use mitrid_core::model::Lock;
+use mitrid_core::util::Timestamp;
 use fixture::io::store::*;
 
 #[test]
@@ -108,6 +114,400 @@ fn test_transaction_payload() {
     assert!(res.is_ok())
 }
 
+fn balance_transaction(inputs_amounts: &[Amount], outputs_amounts: &[Amount]) -> Transaction {
+    let mut hasher = Hasher{};
+
+    let mut inputs = Vec::new();
+
+    for (idx, amount) in inputs_amounts.iter().enumerate() {
+        let coin = Coin::new()
+                        .output_data(&Digest::default(), idx as u64, amount)
+                        .unwrap()
+                        .finalize(&mut hasher)
+                        .unwrap();
+
+        let input = Input::new()
+                        .coin(&coin)
+                        .unwrap()
+                        .finalize(&mut hasher)
+                        .unwrap();
+
+        inputs.push(input);
+    }
+
+    let mut outputs = Vec::new();
+
+    for amount in outputs_amounts.iter() {
+        let output = Output::new()
+                        .amount(amount)
+                        .unwrap()
+                        .finalize(&mut hasher)
+                        .unwrap();
+
+        outputs.push(output);
+    }
+
+    Transaction::new()
+        .inputs(&inputs)
+        .unwrap()
+        .outputs(&outputs)
+        .unwrap()
+        .finalize(&mut hasher)
+        .unwrap()
+}
+
+#[test]
+fn test_transaction_amounts() {
+    let tx = balance_transaction(&[10, 5], &[7, 6]);
+
+    let res = tx.inputs_amount();
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), 15);
+
+    let res = tx.outputs_amount();
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), 13);
+
+    let res = tx.fee();
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), 2);
+
+    let tx = balance_transaction(&[10], &[7, 6]);
+
+    let res = tx.fee();
+    assert!(res.is_err());
+
+    let tx = balance_transaction(&[Amount::max_value(), 1], &[1]);
+
+    let res = tx.inputs_amount();
+    assert!(res.is_err());
+
+    let res = tx.fee();
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_transaction_balance() {
+    let exact_tx = balance_transaction(&[10, 5], &[8, 7]);
+    let fee_tx = balance_transaction(&[10, 5], &[8, 6]);
+    let unbalanced_tx = balance_transaction(&[10, 5], &[8, 8]);
+
+    let res = exact_tx.verify_balance(&FeePolicy::Exact);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let res = exact_tx.check_balance(&FeePolicy::Fee);
+    assert!(res.is_ok());
+
+    let res = fee_tx.verify_balance(&FeePolicy::Exact);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let res = fee_tx.check_balance(&FeePolicy::Fee);
+    assert!(res.is_ok());
+
+    let res = unbalanced_tx.check_balance(&FeePolicy::Exact);
+    assert!(res.is_err());
+
+    let res = unbalanced_tx.check_balance(&FeePolicy::Fee);
+    assert!(res.is_err());
+
+    let overflow_tx = balance_transaction(&[1], &[Amount::max_value(), 1]);
+
+    let res = overflow_tx.verify_balance(&FeePolicy::Fee);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_transaction_sign_inputs() {
+    let mut hasher = Hasher{};
+    let mut signer = Ed25519{};
+
+    let (pk, sk) = signer.generate_keys(None).unwrap();
+    let (other_pk, other_sk) = signer.generate_keys(None).unwrap();
+
+    let locked_output = Output::new()
+                            .amount(&10)
+                            .unwrap()
+                            .lock(&Lock::key(&pk).unwrap())
+                            .unwrap()
+                            .finalize(&mut hasher)
+                            .unwrap();
+
+    let unlocked_output = Output::new()
+                            .amount(&5)
+                            .unwrap()
+                            .finalize(&mut hasher)
+                            .unwrap();
+
+    let spent_outputs = vec![locked_output.clone(), unlocked_output.clone()];
+
+    let time = Timestamp::now().unwrap();
+
+    let tx = balance_transaction(&[10, 5], &[15]);
+
+    let res = tx.verify_inputs(&mut hasher, &mut signer, &spent_outputs, &time);
+    assert!(res.is_ok());
+    assert!(!res.unwrap());
+
+    let res = tx.clone().sign_inputs(&mut hasher, &mut signer, &[Some(sk.clone())]);
+    assert!(res.is_err());
+
+    let res = tx.clone().sign_inputs(&mut hasher, &mut signer, &[Some(sk.clone()), None]);
+    assert!(res.is_ok());
+
+    let signed_tx = res.unwrap();
+    assert!(signed_tx.inputs[0].is_signed());
+    assert!(!signed_tx.inputs[1].is_signed());
+    assert_eq!(signed_tx.sighash(&mut hasher).unwrap(), tx.sighash(&mut hasher).unwrap());
+
+    let res = signed_tx.verify_digest(&mut hasher);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let res = signed_tx.inputs[0].verify_digest(&mut hasher);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let res = signed_tx.verify_inputs(&mut hasher, &mut signer, &spent_outputs, &time);
+    assert!(res.is_ok());
+    assert!(res.unwrap());
+
+    let res = signed_tx.check_inputs(&mut hasher, &mut signer, &spent_outputs, &time);
+    assert!(res.is_ok());
+
+    let res = signed_tx.check_inputs(&mut hasher, &mut signer, &spent_outputs[..1], &time);
+    assert!(res.is_err());
+
+    let other_outputs = vec![locked_output.clone().lock(&Lock::key(&other_pk).unwrap()).unwrap(), unlocked_output.clone()];
+
+    let res = signed_tx.check_inputs(&mut hasher, &mut signer, &other_outputs, &time);
+    assert!(res.is_err());
+
+    let res = tx.clone().sign_inputs(&mut hasher, &mut signer, &[Some(other_sk), None]);
+    assert!(res.is_ok());
+
+    let res = res.unwrap().check_inputs(&mut hasher, &mut signer, &spent_outputs, &time);
+    assert!(res.is_err());
+
+    let mut tampered_tx = signed_tx.clone();
+    tampered_tx.outputs[0].amount = 14;
+    tampered_tx.outputs[0].update_size();
+
+    let res = tampered_tx.check_inputs(&mut hasher, &mut signer, &spent_outputs, &time);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_transaction_multisig_inputs() {
+    let mut hasher = Hasher{};
+    let mut signer = Ed25519{};
+
+    let (pk_a, sk_a) = signer.generate_keys(None).unwrap();
+    let (pk_b, sk_b) = signer.generate_keys(None).unwrap();
+    let (pk_c, _) = signer.generate_keys(None).unwrap();
+
+    let lock = Lock::multisig(2, &[pk_a, pk_b, pk_c]).unwrap();
+
+    let spent_output = Output::new()
+                            .amount(&10)
+                            .unwrap()
+                            .lock(&lock)
+                            .unwrap()
+                            .finalize(&mut hasher)
+                            .unwrap();
+
+    let spent_outputs = vec![spent_output];
+
+    let time = Timestamp::now().unwrap();
+
+    let tx = balance_transaction(&[10], &[10])
+                .sign_inputs(&mut hasher, &mut signer, &[Some(sk_a)])
+                .unwrap();
+
+    let res = tx.check_inputs(&mut hasher, &mut signer, &spent_outputs, &time);
+    assert!(res.is_err());
+
+    let tx = tx.sign_inputs(&mut hasher, &mut signer, &[Some(sk_b)]).unwrap();
+
+    let res = tx.check_inputs(&mut hasher, &mut signer, &spent_outputs, &time);
+    assert!(res.is_ok());
+
+    let mut invalid_tx = tx.clone();
+    invalid_tx.outputs[0].lock = Lock::Multisig(0, vec![]);
+    invalid_tx.outputs[0].update_size();
+    invalid_tx.update_size();
+
+    let res = invalid_tx.check();
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_transaction_input_sighash() {
+    let mut hasher = Hasher{};
+
+    let modes = vec![SighashMode::All,
+                     SighashMode::SingleInput,
+                     SighashMode::AnyoneCanPay,
+                     SighashMode::OutputsOnly];
+
+    let tx = balance_transaction(&[10, 5], &[12, 3]);
+
+    for mode in modes.iter() {
+        let res = tx.input_sighash(&mut hasher, 1, mode);
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), tx.input_sighash(&mut hasher, 1, mode).unwrap());
+    }
+
+    for (i, mode_a) in modes.iter().enumerate() {
+        for mode_b in modes[i + 1..].iter() {
+            let sighash_a = tx.input_sighash(&mut hasher, 0, mode_a).unwrap();
+            let sighash_b = tx.input_sighash(&mut hasher, 0, mode_b).unwrap();
+            assert_ne!(sighash_a, sighash_b);
+        }
+    }
+
+    assert_eq!(tx.sighash(&mut hasher).unwrap(),
+               tx.input_sighash(&mut hasher, 0, &SighashMode::All).unwrap());
+
+    let res = tx.input_sighash(&mut hasher, 2, &SighashMode::All);
+    assert!(res.is_ok());
+
+    let res = tx.input_sighash(&mut hasher, 2, &SighashMode::SingleInput);
+    assert!(res.is_err());
+
+    let res = tx.input_sighash(&mut hasher, 2, &SighashMode::AnyoneCanPay);
+    assert!(res.is_err());
+
+    let res = tx.input_sighash(&mut hasher, 2, &SighashMode::OutputsOnly);
+    assert!(res.is_ok());
+
+    let single_tx = balance_transaction(&[10, 5], &[15]);
+
+    let res = single_tx.input_sighash(&mut hasher, 1, &SighashMode::SingleInput);
+    assert!(res.is_err());
+
+    let mut other_input_tx = tx.clone();
+    other_input_tx.inputs[1].coin.out_idx = 7;
+
+    assert_ne!(tx.input_sighash(&mut hasher, 0, &SighashMode::All).unwrap(),
+               other_input_tx.input_sighash(&mut hasher, 0, &SighashMode::All).unwrap());
+    assert_eq!(tx.input_sighash(&mut hasher, 0, &SighashMode::SingleInput).unwrap(),
+               other_input_tx.input_sighash(&mut hasher, 0, &SighashMode::SingleInput).unwrap());
+    assert_eq!(tx.input_sighash(&mut hasher, 0, &SighashMode::AnyoneCanPay).unwrap(),
+               other_input_tx.input_sighash(&mut hasher, 0, &SighashMode::AnyoneCanPay).unwrap());
+    assert_eq!(tx.input_sighash(&mut hasher, 0, &SighashMode::OutputsOnly).unwrap(),
+               other_input_tx.input_sighash(&mut hasher, 0, &SighashMode::OutputsOnly).unwrap());
+
+    let mut other_output_tx = tx.clone();
+    other_output_tx.outputs[1].amount = 4;
+
+    assert_ne!(tx.input_sighash(&mut hasher, 0, &SighashMode::All).unwrap(),
+               other_output_tx.input_sighash(&mut hasher, 0, &SighashMode::All).unwrap());
+    assert_eq!(tx.input_sighash(&mut hasher, 0, &SighashMode::SingleInput).unwrap(),
+               other_output_tx.input_sighash(&mut hasher, 0, &SighashMode::SingleInput).unwrap());
+    assert_ne!(tx.input_sighash(&mut hasher, 0, &SighashMode::AnyoneCanPay).unwrap(),
+               other_output_tx.input_sighash(&mut hasher, 0, &SighashMode::AnyoneCanPay).unwrap());
+    assert_ne!(tx.input_sighash(&mut hasher, 0, &SighashMode::OutputsOnly).unwrap(),
+               other_output_tx.input_sighash(&mut hasher, 0, &SighashMode::OutputsOnly).unwrap());
+}
+
+#[test]
+fn test_transaction_sighash_modes() {
+    let mut hasher = Hasher{};
+    let mut signer = Ed25519{};
+
+    let (pk_a, sk_a) = signer.generate_keys(None).unwrap();
+    let (pk_b, sk_b) = signer.generate_keys(None).unwrap();
+
+    let output_a = Output::new()
+                        .amount(&10)
+                        .unwrap()
+                        .lock(&Lock::key(&pk_a).unwrap())
+                        .unwrap()
+                        .finalize(&mut hasher)
+                        .unwrap();
+
+    let output_b = Output::new()
+                        .amount(&5)
+                        .unwrap()
+                        .lock(&Lock::key(&pk_b).unwrap())
+                        .unwrap()
+                        .finalize(&mut hasher)
+                        .unwrap();
+
+    let time = Timestamp::now().unwrap();
+
+    let partial_tx = balance_transaction(&[10, 5], &[15]);
+
+    let input_a = partial_tx.inputs[0]
+                    .clone()
+                    .sighash_mode(&SighashMode::AnyoneCanPay)
+                    .unwrap();
+
+    let input_b = partial_tx.inputs[1].clone();
+
+    let tx_a = partial_tx.clone()
+                .inputs(&vec![input_a])
+                .unwrap()
+                .finalize(&mut hasher)
+                .unwrap()
+                .sign_inputs(&mut hasher, &mut signer, &[Some(sk_a.clone())])
+                .unwrap();
+
+    let res = tx_a.check_inputs(&mut hasher, &mut signer, &[output_a.clone()], &time);
+    assert!(res.is_ok());
+
+    let res = tx_a.inputs[0].clone().sighash_mode(&SighashMode::All);
+    assert!(res.is_err());
+
+    let tx = tx_a.clone()
+                .inputs(&vec![tx_a.inputs[0].clone(), input_b.clone()])
+                .unwrap()
+                .finalize(&mut hasher)
+                .unwrap()
+                .sign_inputs(&mut hasher, &mut signer, &[None, Some(sk_b.clone())])
+                .unwrap();
+
+    let spent_outputs = vec![output_a.clone(), output_b.clone()];
+
+    let res = tx.check_inputs(&mut hasher, &mut signer, &spent_outputs, &time);
+    assert!(res.is_ok());
+
+    let mut tampered_tx = tx.clone();
+    tampered_tx.outputs[0].amount = 14;
+    tampered_tx.outputs[0].update_size();
+
+    let res = tampered_tx.check_inputs(&mut hasher, &mut signer, &spent_outputs, &time);
+    assert!(res.is_err());
+
+    let mut tampered_tx = tx.clone();
+    tampered_tx.inputs[0].witness.mode = SighashMode::OutputsOnly;
+    tampered_tx.inputs[0].update_size();
+
+    let res = tampered_tx.check_inputs(&mut hasher, &mut signer, &spent_outputs, &time);
+    assert!(res.is_err());
+
+    let input_b = input_b.sighash_mode(&SighashMode::OutputsOnly).unwrap();
+
+    let tx = partial_tx.clone()
+                .inputs(&vec![partial_tx.inputs[0].clone(), input_b])
+                .unwrap()
+                .finalize(&mut hasher)
+                .unwrap()
+                .sign_inputs(&mut hasher, &mut signer, &[Some(sk_a), Some(sk_b)])
+                .unwrap();
+
+    let res = tx.check_inputs(&mut hasher, &mut signer, &spent_outputs, &time);
+    assert!(res.is_ok());
+
+    let mut other_tx = tx.clone();
+    other_tx.inputs[0].coin.out_idx = 7;
+
+    let res = other_tx.check_inputs(&mut hasher, &mut signer, &spent_outputs, &time);
+    assert!(res.is_err());
+}
+
 #[test]
 fn test_transaction_verify_digest() {
     let mut tx = Transaction::new();
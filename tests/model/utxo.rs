@@ -0,0 +1,336 @@
+use mitrid_core::base::Checkable;
+use mitrid_core::base::Serializable;
+use mitrid_core::crypto::Sign;
+use mitrid_core::model::Lock;
+use mitrid_core::model::FeePolicy;
+use mitrid_core::io::Storable;
+
+use fixture::crypto::{Digest, Hasher};
+use fixture::crypto::sign_ed25519::Ed25519;
+use fixture::model::Amount;
+use fixture::model::coin::*;
+use fixture::model::input::*;
+use fixture::model::output::*;
+use fixture::model::transaction::*;
+use fixture::model::block::*;
+use fixture::model::utxo::*;
+use fixture::io::store::*;
+
+fn utxo_transaction(coins: &[Coin], outputs: &[(Amount, Lock)]) -> Transaction {
+    let mut hasher = Hasher{};
+
+    let mut inputs = Vec::new();
+
+    for coin in coins.iter() {
+        let input = Input::new()
+                        .coin(coin)
+                        .unwrap()
+                        .finalize(&mut hasher)
+                        .unwrap();
+
+        inputs.push(input);
+    }
+
+    let mut outs = Vec::new();
+
+    for &(amount, ref lock) in outputs.iter() {
+        let output = Output::new()
+                        .amount(&amount)
+                        .unwrap()
+                        .lock(lock)
+                        .unwrap()
+                        .finalize(&mut hasher)
+                        .unwrap();
+
+        outs.push(output);
+    }
+
+    Transaction::new()
+        .inputs(&inputs)
+        .unwrap()
+        .outputs(&outs)
+        .unwrap()
+        .finalize(&mut hasher)
+        .unwrap()
+}
+
+#[test]
+fn test_utxo_new() {
+    let mut hasher = Hasher{};
+
+    let output = Output::new()
+                    .amount(&10)
+                    .unwrap()
+                    .finalize(&mut hasher)
+                    .unwrap();
+
+    let coin = Coin::new()
+                    .output_data(&Digest::default(), 0, &10)
+                    .unwrap()
+                    .finalize(&mut hasher)
+                    .unwrap();
+
+    let res = Utxo::new(&coin, &output);
+    assert!(res.is_ok());
+
+    let utxo = res.unwrap();
+    assert!(!utxo.is_spent());
+
+    let other_coin = Coin::new()
+                        .output_data(&Digest::default(), 0, &5)
+                        .unwrap()
+                        .finalize(&mut hasher)
+                        .unwrap();
+
+    let res = Utxo::new(&other_coin, &output);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_utxo_store() {
+    let mut hasher = Hasher{};
+
+    let transaction = utxo_transaction(&[], &[(10, Lock::Unlocked)]);
+    let coins = transaction.output_coins(&mut hasher).unwrap();
+
+    let utxo = Utxo::new(&coins[0], &transaction.outputs[0]).unwrap();
+
+    let mut store = Store::new();
+
+    let res = utxo.store_create(&mut store);
+    assert!(res.is_ok());
+
+    let res = utxo.store_create(&mut store);
+    assert!(res.is_err());
+
+    let res = Utxo::store_get(&mut store, &utxo.coin.id);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), utxo);
+
+    let bytes = utxo.to_bytes().unwrap();
+    let res = Utxo::from_bytes(&bytes);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), utxo);
+}
+
+#[test]
+fn test_utxoset_apply_transaction() {
+    let mut hasher = Hasher{};
+    let mut signer = Ed25519{};
+
+    let (pk_a, _) = signer.generate_keys(None).unwrap();
+    let (pk_b, _) = signer.generate_keys(None).unwrap();
+
+    let lock_a = Lock::key(&pk_a).unwrap();
+    let lock_b = Lock::key(&pk_b).unwrap();
+
+    let mut utxos = UtxoSet::new(Store::new());
+
+    let coinbase = utxo_transaction(&[], &[(10, lock_a.clone()), (5, lock_b.clone())]);
+    let coinbase_coins = coinbase.output_coins(&mut hasher).unwrap();
+
+    assert_eq!(coinbase_coins.len(), 2);
+    assert_eq!(coinbase_coins[1].tx_id, coinbase.id);
+    assert_eq!(coinbase_coins[1].out_idx, 1);
+    assert_eq!(coinbase_coins[1].out_amount, 5);
+    assert_eq!(coinbase.output_coins(&mut hasher).unwrap(), coinbase_coins);
+
+    let res = utxos.apply_transaction(&mut hasher, &coinbase);
+    assert!(res.is_ok());
+
+    let res = utxos.apply_transaction(&mut hasher, &coinbase);
+    assert!(res.is_err());
+
+    assert!(utxos.is_unspent(&coinbase_coins[0]).unwrap());
+    assert_eq!(utxos.balance(&lock_a).unwrap(), 10);
+    assert_eq!(utxos.balance(&lock_b).unwrap(), 5);
+    assert_eq!(utxos.balance(&Lock::Unlocked).unwrap(), 0);
+    assert_eq!(utxos.total_balance().unwrap(), 15);
+
+    let spend = utxo_transaction(&coinbase_coins[..1], &[(4, lock_b.clone()), (6, lock_a.clone())]);
+    let spend_coins = spend.output_coins(&mut hasher).unwrap();
+
+    let res = utxos.spent_outputs(&spend);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec![coinbase.outputs[0].clone()]);
+
+    let res = utxos.apply_transaction(&mut hasher, &spend);
+    assert!(res.is_ok());
+
+    assert!(!utxos.is_unspent(&coinbase_coins[0]).unwrap());
+    assert_eq!(utxos.get(&coinbase_coins[0].id).unwrap().spent_by, Some(spend.id.clone()));
+    assert_eq!(utxos.balance(&lock_a).unwrap(), 6);
+    assert_eq!(utxos.balance(&lock_b).unwrap(), 9);
+    assert_eq!(utxos.unspent(&lock_b).unwrap().len(), 2);
+    assert_eq!(utxos.total_balance().unwrap(), 15);
+
+    let double_spend = utxo_transaction(&coinbase_coins[..1], &[(10, lock_b.clone())]);
+
+    let res = utxos.apply_transaction(&mut hasher, &double_spend);
+    assert!(res.is_err());
+
+    let duplicate_spend = utxo_transaction(&[coinbase_coins[1].clone(), coinbase_coins[1].clone()],
+                                           &[(10, lock_a.clone())]);
+
+    let res = utxos.apply_transaction(&mut hasher, &duplicate_spend);
+    assert!(res.is_err());
+    assert!(utxos.is_unspent(&coinbase_coins[1]).unwrap());
+
+    let unknown_coin = Coin::new()
+                        .output_data(&Digest::default(), 0, &5)
+                        .unwrap()
+                        .finalize(&mut hasher)
+                        .unwrap();
+
+    let unknown_spend = utxo_transaction(&[unknown_coin], &[(5, lock_a.clone())]);
+
+    let res = utxos.apply_transaction(&mut hasher, &unknown_spend);
+    assert!(res.is_err());
+
+    let res = utxos.spent_outputs(&unknown_spend);
+    assert!(res.is_err());
+
+    let mut invalid_coin = coinbase_coins[1].clone();
+    invalid_coin.out_amount = 50;
+    invalid_coin = invalid_coin.finalize(&mut hasher).unwrap();
+    invalid_coin.id = coinbase_coins[1].id.clone();
+
+    let invalid_spend = utxo_transaction(&[invalid_coin], &[(50, lock_a.clone())]);
+
+    let res = utxos.apply_transaction(&mut hasher, &invalid_spend);
+    assert!(res.is_err());
+
+    let res = utxos.revert_transaction(&mut hasher, &coinbase);
+    assert!(res.is_err());
+
+    let res = utxos.revert_transaction(&mut hasher, &double_spend);
+    assert!(res.is_err());
+
+    let res = utxos.revert_transaction(&mut hasher, &spend);
+    assert!(res.is_ok());
+
+    assert!(utxos.is_unspent(&coinbase_coins[0]).unwrap());
+    assert!(!utxos.lookup(&spend_coins[0].id).unwrap());
+    assert_eq!(utxos.balance(&lock_a).unwrap(), 10);
+    assert_eq!(utxos.balance(&lock_b).unwrap(), 5);
+
+    let res = utxos.revert_transaction(&mut hasher, &coinbase);
+    assert!(res.is_ok());
+
+    assert_eq!(utxos.total_balance().unwrap(), 0);
+
+    let res = utxos.revert_transaction(&mut hasher, &coinbase);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_utxoset_fee_policy() {
+    let mut hasher = Hasher{};
+
+    let mut utxos = UtxoSet::new(Store::new());
+
+    let coinbase = utxo_transaction(&[], &[(10, Lock::Unlocked)]);
+    let coinbase_coins = coinbase.output_coins(&mut hasher).unwrap();
+
+    let res = utxos.apply_transaction(&mut hasher, &coinbase);
+    assert!(res.is_ok());
+
+    let overspend = utxo_transaction(&coinbase_coins, &[(11, Lock::Unlocked)]);
+
+    let res = utxos.apply_transaction(&mut hasher, &overspend);
+    assert!(res.is_err());
+
+    let fee_spend = utxo_transaction(&coinbase_coins, &[(8, Lock::Unlocked)]);
+
+    let res = utxos.apply_transaction(&mut hasher, &fee_spend);
+    assert!(res.is_err());
+    assert!(utxos.is_unspent(&coinbase_coins[0]).unwrap());
+
+    let mut utxos = UtxoSet::new(utxos.into_store())
+                        .fee_policy(&FeePolicy::Fee)
+                        .unwrap();
+
+    let res = utxos.apply_transaction(&mut hasher, &overspend);
+    assert!(res.is_err());
+
+    let res = utxos.apply_transaction(&mut hasher, &fee_spend);
+    assert!(res.is_ok());
+    assert_eq!(utxos.total_balance().unwrap(), 8);
+}
+
+#[test]
+fn test_utxoset_apply_block() {
+    let mut hasher = Hasher{};
+
+    let mut utxos = UtxoSet::new(Store::new());
+
+    let coinbase = utxo_transaction(&[], &[(10, Lock::Unlocked)]);
+    let coinbase_coins = coinbase.output_coins(&mut hasher).unwrap();
+
+    let spend = utxo_transaction(&coinbase_coins, &[(7, Lock::Unlocked), (3, Lock::Unlocked)]);
+    let spend_coins = spend.output_coins(&mut hasher).unwrap();
+
+    let block = Block::new()
+                    .transactions(&vec![coinbase.clone(), spend.clone()])
+                    .unwrap()
+                    .finalize(&mut hasher)
+                    .unwrap();
+
+    let mut forged_block = block.clone();
+    forged_block.id = coinbase.id.clone();
+
+    let res = utxos.apply_block(&mut hasher, &forged_block);
+    assert!(res.is_err());
+
+    let other_spend = utxo_transaction(&coinbase_coins, &[(6, Lock::Unlocked), (4, Lock::Unlocked)]);
+
+    let mut forged_block = block.clone();
+    forged_block.transactions = vec![coinbase.clone(), other_spend];
+    forged_block.update_size();
+
+    let res = utxos.apply_block(&mut hasher, &forged_block);
+    assert!(res.is_err());
+    assert_eq!(utxos.total_balance().unwrap(), 0);
+
+    let res = utxos.revert_block(&mut hasher, &forged_block);
+    assert!(res.is_err());
+
+    let res = utxos.apply_block(&mut hasher, &block);
+    assert!(res.is_ok());
+
+    assert!(!utxos.is_unspent(&coinbase_coins[0]).unwrap());
+    assert!(utxos.is_unspent(&spend_coins[0]).unwrap());
+    assert!(utxos.is_unspent(&spend_coins[1]).unwrap());
+    assert_eq!(utxos.balance(&Lock::Unlocked).unwrap(), 10);
+
+    let res = utxos.apply_block(&mut hasher, &block);
+    assert!(res.is_err());
+
+    let double_spend = utxo_transaction(&spend_coins[..1], &[(7, Lock::Unlocked)]);
+    let other_double_spend = utxo_transaction(&spend_coins[..1], &[(6, Lock::Unlocked)]);
+
+    let invalid_block = Block::new()
+                            .transactions(&vec![double_spend.clone(), other_double_spend])
+                            .unwrap()
+                            .finalize(&mut hasher)
+                            .unwrap();
+
+    let res = utxos.apply_block(&mut hasher, &invalid_block);
+    assert!(res.is_err());
+
+    assert!(utxos.is_unspent(&spend_coins[0]).unwrap());
+    assert!(!utxos.lookup(&double_spend.output_coins(&mut hasher).unwrap()[0].id).unwrap());
+
+    let res = utxos.revert_block(&mut hasher, &block);
+    assert!(res.is_ok());
+
+    assert!(!utxos.lookup(&coinbase_coins[0].id).unwrap());
+    assert!(!utxos.lookup(&spend_coins[0].id).unwrap());
+    assert_eq!(utxos.total_balance().unwrap(), 0);
+
+    let res = utxos.revert_block(&mut hasher, &block);
+    assert!(res.is_err());
+
+    let res = block.check();
+    assert!(res.is_ok());
+}
\ No newline at end of file